@@ -1,11 +1,24 @@
 use super::ApiClient;
 use crate::errors::FrontendResult;
+use chrono::{DateTime, Utc};
 use http::Method;
 use ibis_database::common::{
     ResolveObjectParams,
     SuccessResponse,
-    article::{ApiConflict, Article, ArticleView, EditVersion, EditView},
-    newtypes::{ArticleId, ConflictId, InstanceId, PersonId},
+    article::{
+        ApiConflict,
+        Article,
+        ArticleBlame,
+        ArticleDiff,
+        ArticleView,
+        DiffGranularity,
+        EditVersion,
+        EditView,
+        LinkedArticle,
+        WantedArticle,
+    },
+    newtypes::{ArticleId, ConflictId, EditId, InstanceId, PersonId},
+    pagination::{PaginatedResponse, PaginationCursor},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -23,6 +36,10 @@ pub struct ListArticlesParams {
     pub only_local: Option<bool>,
     pub instance_id: Option<InstanceId>,
     pub include_removed: Option<bool>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -55,10 +72,36 @@ pub struct EditArticleParams {
     pub resolve_conflict_id: Option<ConflictId>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RevertArticleParams {
+    pub article_id: ArticleId,
+    /// The earlier version which should be restored
+    pub version: EditVersion,
+    pub reason: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ProtectArticleParams {
     pub article_id: ArticleId,
     pub protected: bool,
+    /// Shown in the moderation log
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RequireReviewParams {
+    pub article_id: ArticleId,
+    /// If true, all edits to the article need to be approved by a reviewer
+    pub require_review: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReviewEditParams {
+    pub edit_id: EditId,
+    /// Apply the edit if true, otherwise reject it
+    pub approve: bool,
+    pub reason: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -67,16 +110,55 @@ pub struct ForkArticleParams {
     pub new_title: String,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MoveArticleParams {
+    pub article_id: ArticleId,
+    pub new_title: String,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct RemoveArticleParams {
     pub article_id: ArticleId,
     pub remove: bool,
+    pub reason: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct GetEditList {
     pub article_id: Option<ArticleId>,
     pub person_id: Option<PersonId>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ListRecentChangesParams {
+    /// Only edits of articles from this instance
+    pub instance_id: Option<InstanceId>,
+    pub person_id: Option<PersonId>,
+    /// Only edits of local articles
+    pub local_only: Option<bool>,
+    /// Only edits published at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only edits published before this time
+    pub until: Option<DateTime<Utc>>,
+    pub hide_bots: Option<bool>,
+    /// Only edits which are waiting for approval, requires moderator permission
+    pub unreviewed_only: Option<bool>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListEditsAwaitingReviewParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -89,6 +171,46 @@ pub struct GetConflictParams {
     pub conflict_id: ConflictId,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetBacklinksParams {
+    pub article_id: ArticleId,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListWantedArticlesParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListOrphanedArticlesParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GetBlameParams {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CompareVersionsParams {
+    pub article_id: ArticleId,
+    /// Use `EditVersion::default()` to compare with empty text
+    pub from: EditVersion,
+    pub to: EditVersion,
+    #[serde(default)]
+    pub granularity: DiffGranularity,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FollowArticleParams {
     pub id: ArticleId,
@@ -107,7 +229,10 @@ impl ApiClient {
         self.send(Method::GET, "/api/v1/article", Some(data)).await
     }
 
-    pub async fn list_articles(&self, data: ListArticlesParams) -> FrontendResult<Vec<Article>> {
+    pub async fn list_articles(
+        &self,
+        data: ListArticlesParams,
+    ) -> FrontendResult<PaginatedResponse<Article>> {
         self.get("/api/v1/article/list", Some(data)).await
     }
 
@@ -118,32 +243,122 @@ impl ApiClient {
         self.patch("/api/v1/article", Some(&params)).await
     }
 
+    pub async fn revert_article(
+        &self,
+        params: &RevertArticleParams,
+    ) -> FrontendResult<Option<ApiConflict>> {
+        self.post("/api/v1/article/revert", Some(params)).await
+    }
+
     pub async fn fork_article(&self, params: &ForkArticleParams) -> FrontendResult<ArticleView> {
         self.post("/api/v1/article/fork", Some(params)).await
     }
 
+    pub async fn move_article(&self, params: &MoveArticleParams) -> FrontendResult<ArticleView> {
+        self.post("/api/v1/article/move", Some(params)).await
+    }
+
     pub async fn protect_article(&self, params: &ProtectArticleParams) -> FrontendResult<Article> {
         self.post("/api/v1/article/protect", Some(params)).await
     }
 
+    pub async fn require_review(&self, params: &RequireReviewParams) -> FrontendResult<Article> {
+        self.post("/api/v1/article/require_review", Some(params))
+            .await
+    }
+
+    pub async fn list_recent_changes(
+        &self,
+        params: &ListRecentChangesParams,
+    ) -> FrontendResult<PaginatedResponse<EditView>> {
+        self.get("/api/v1/edit/recent", Some(params)).await
+    }
+
+    pub async fn list_edits_awaiting_review(
+        &self,
+        params: &ListEditsAwaitingReviewParams,
+    ) -> FrontendResult<PaginatedResponse<EditView>> {
+        self.get("/api/v1/edit/review/list", Some(params)).await
+    }
+
+    pub async fn review_edit(&self, params: &ReviewEditParams) -> FrontendResult<()> {
+        self.post("/api/v1/edit/review", Some(params)).await
+    }
+
     pub async fn resolve_article(&self, id: Url) -> FrontendResult<ArticleView> {
         let resolve_object = ResolveObjectParams { id };
         self.send(Method::GET, "/api/v1/article/resolve", Some(resolve_object))
             .await
     }
 
+    pub async fn list_edits(
+        &self,
+        params: &GetEditList,
+    ) -> FrontendResult<PaginatedResponse<EditView>> {
+        self.get("/api/v1/edit/list", Some(params)).await
+    }
+
+    /// Read all pages of edits which match `params`.
+    pub(crate) async fn list_all_edits(
+        &self,
+        mut params: GetEditList,
+    ) -> FrontendResult<Vec<EditView>> {
+        params.limit = Some(100);
+        let mut edits = vec![];
+        loop {
+            let page = self.list_edits(&params).await?;
+            edits.extend(page.items);
+            match page.next_page {
+                Some(next_page) => params.cursor = Some(next_page),
+                None => return Ok(edits),
+            }
+        }
+    }
+
+    /// Read the whole history of an article, which may take multiple requests.
     pub async fn get_article_edits(&self, article_id: ArticleId) -> FrontendResult<Vec<EditView>> {
-        let data = GetEditList {
+        let params = GetEditList {
             article_id: Some(article_id),
             ..Default::default()
         };
-        self.send(Method::GET, "/api/v1/edit/list", Some(data))
-            .await
+        self.list_all_edits(params).await
+    }
+
+    pub async fn get_backlinks(
+        &self,
+        params: &GetBacklinksParams,
+    ) -> FrontendResult<PaginatedResponse<LinkedArticle>> {
+        self.get("/api/v1/article/backlinks", Some(params)).await
+    }
+
+    pub async fn get_article_blame(&self, article_id: ArticleId) -> FrontendResult<ArticleBlame> {
+        let params = GetBlameParams { article_id };
+        self.get("/api/v1/article/blame", Some(params)).await
+    }
+
+    pub async fn compare_versions(
+        &self,
+        params: &CompareVersionsParams,
+    ) -> FrontendResult<ArticleDiff> {
+        self.get("/api/v1/article/compare", Some(params)).await
+    }
+
+    pub async fn list_wanted_articles(
+        &self,
+        params: &ListWantedArticlesParams,
+    ) -> FrontendResult<PaginatedResponse<WantedArticle>> {
+        self.get("/api/v1/article/wanted", Some(params)).await
+    }
+
+    pub async fn list_orphaned_articles(
+        &self,
+        params: &ListOrphanedArticlesParams,
+    ) -> FrontendResult<PaginatedResponse<LinkedArticle>> {
+        self.get("/api/v1/article/orphaned", Some(params)).await
     }
 
-    pub async fn remove_article(&self, article_id: ArticleId, remove: bool) -> FrontendResult<()> {
-        let params = RemoveArticleParams { article_id, remove };
-        self.post("/api/v1/article/remove", Some(&params)).await
+    pub async fn remove_article(&self, params: &RemoveArticleParams) -> FrontendResult<()> {
+        self.post("/api/v1/article/remove", Some(params)).await
     }
 
     pub async fn get_conflict(&self, conflict_id: ConflictId) -> FrontendResult<ApiConflict> {
@@ -3,6 +3,7 @@ use crate::errors::FrontendResult;
 use ibis_database::common::{
     comment::{CommentView, CommentViewWithArticle},
     newtypes::{ArticleId, CommentId},
+    pagination::{PaginatedResponse, PaginationCursor},
 };
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +26,15 @@ pub struct GetCommentParams {
     pub id: CommentId,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ListCommentsParams {
+    pub article_id: ArticleId,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct DeleteCommentParams {
     pub id: CommentId,
@@ -46,4 +56,12 @@ impl ApiClient {
         let params = GetCommentParams { id };
         self.get("/api/v1/comment", Some(&params)).await
     }
+
+    /// Comments of an article, newest first.
+    pub async fn list_comments(
+        &self,
+        params: &ListCommentsParams,
+    ) -> FrontendResult<PaginatedResponse<CommentView>> {
+        self.get("/api/v1/comment/list", Some(params)).await
+    }
 }
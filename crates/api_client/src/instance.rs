@@ -1,12 +1,16 @@
 use super::ApiClient;
 use crate::errors::FrontendResult;
+use chrono::{DateTime, Utc};
 use http::Method;
 use ibis_database::common::{
     ResolveObjectParams,
     SuccessResponse,
-    article::Article,
+    article::SearchArticleResponse,
+    delivery::DeliveryStatusView,
+    federation_list::{FederationListEntry, FederationMode},
     instance::{Instance, InstanceView, SiteView},
     newtypes::InstanceId,
+    pagination::{PaginatedResponse, PaginationCursor},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -14,6 +18,23 @@ use url::Url;
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct SearchArticleParams {
     pub query: String,
+    /// Only return articles from this instance
+    pub instance_id: Option<InstanceId>,
+    pub only_local: Option<bool>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 20
+    pub limit: Option<i64>,
+    /// Also search on all known remote instances
+    pub federated: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListInstancesParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -32,6 +53,59 @@ pub struct FollowInstanceParams {
 pub struct UpdateInstanceParams {
     pub name: Option<String>,
     pub topic: Option<String>,
+    /// If true, edits to all local articles need to be approved by a reviewer
+    pub review_all_edits: Option<bool>,
+    /// Users need review until they have this many approved edits, 0 to disable
+    pub review_new_user_edits: Option<i32>,
+    /// If true, moderators and admins need two-factor authentication to use their permissions
+    pub require_totp_for_moderators: Option<bool>,
+    pub reason: Option<String>,
+}
+
+/// Allow or block federation with a domain. Replaces any existing entry for the domain.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct UpdateFederationListParams {
+    pub domain: String,
+    pub mode: FederationMode,
+    pub reason: String,
+    /// Permanent entry if `None`
+    pub expires: Option<DateTime<Utc>>,
+    /// What to do with existing content from a blocked domain, ignored for allowed domains
+    pub content: Option<BlockedContent>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockedContent {
+    /// Keep articles, comments and users from the instance
+    #[default]
+    Keep,
+    /// Remove articles and delete comments, which can be restored by moderators
+    Hide,
+    /// Delete the instance, its articles and comments, and its users which didn't edit articles
+    /// elsewhere
+    Purge,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RemoveFederationListParams {
+    pub domain: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListFederationParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListDeliveryStatusParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
 }
 
 impl ApiClient {
@@ -39,8 +113,11 @@ impl ApiClient {
         self.get("/api/v1/instance", Some(&params)).await
     }
 
-    pub async fn list_instances(&self) -> FrontendResult<Vec<InstanceView>> {
-        self.get("/api/v1/instance/list", None::<i32>).await
+    pub async fn list_instances(
+        &self,
+        params: &ListInstancesParams,
+    ) -> FrontendResult<PaginatedResponse<InstanceView>> {
+        self.get("/api/v1/instance/list", Some(params)).await
     }
 
     pub async fn update_local_instance(
@@ -50,7 +127,10 @@ impl ApiClient {
         self.patch("/api/v1/instance", Some(params)).await
     }
 
-    pub async fn search(&self, params: &SearchArticleParams) -> FrontendResult<Vec<Article>> {
+    pub async fn search(
+        &self,
+        params: &SearchArticleParams,
+    ) -> FrontendResult<SearchArticleResponse> {
         self.send(Method::GET, "/api/v1/search", Some(params)).await
     }
 
@@ -73,6 +153,35 @@ impl ApiClient {
         self.post("/api/v1/instance/follow", Some(params)).await
     }
 
+    pub async fn list_federation(
+        &self,
+        params: &ListFederationParams,
+    ) -> FrontendResult<PaginatedResponse<FederationListEntry>> {
+        self.get("/api/v1/instance/federation", Some(params)).await
+    }
+
+    pub async fn update_federation_list(
+        &self,
+        params: &UpdateFederationListParams,
+    ) -> FrontendResult<FederationListEntry> {
+        self.post("/api/v1/instance/federation", Some(params)).await
+    }
+
+    pub async fn remove_federation_list(
+        &self,
+        params: &RemoveFederationListParams,
+    ) -> FrontendResult<FederationListEntry> {
+        self.send(Method::DELETE, "/api/v1/instance/federation", Some(params))
+            .await
+    }
+
+    pub async fn list_delivery_status(
+        &self,
+        params: &ListDeliveryStatusParams,
+    ) -> FrontendResult<PaginatedResponse<DeliveryStatusView>> {
+        self.get("/api/v1/instance/delivery", Some(params)).await
+    }
+
     pub async fn site(&self) -> FrontendResult<SiteView> {
         self.get("/api/v1/site", None::<()>).await
     }
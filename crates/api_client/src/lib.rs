@@ -8,7 +8,10 @@ pub mod article;
 pub mod comment;
 pub mod errors;
 pub mod instance;
+pub mod media;
+pub mod modlog;
 pub mod notifications;
+pub mod report;
 pub mod user;
 
 pub static CLIENT: LazyLock<ApiClient> = LazyLock::new(|| ApiClient::new(None));
@@ -149,6 +152,61 @@ impl ApiClient {
         })
     }
 
+    /// Send a POST request with raw bytes as body, used for file uploads.
+    #[cfg(feature = "ssr")]
+    async fn send_bytes<T>(&self, path: &str, data: Vec<u8>) -> FrontendResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        use ibis_database::common::{AUTH_COOKIE, Auth};
+        use leptos::prelude::use_context;
+        use reqwest::header::HeaderName;
+
+        let mut req = self
+            .client
+            .post(self.request_endpoint(path, true)?)
+            .header("content-type", "application/octet-stream")
+            .body(data);
+        let auth = use_context::<Auth>();
+        if let Some(Auth(Some(auth))) = auth {
+            req = req.header(HeaderName::from_static(AUTH_COOKIE), auth);
+        }
+        let res = req.send().await?;
+        let status = res.status();
+        let url = res.url().to_string();
+        let text = res.text().await?;
+        Self::response(status.into(), text, &url)
+    }
+
+    /// Send a POST request with raw bytes as body, used for file uploads.
+    #[cfg(not(feature = "ssr"))]
+    fn send_bytes<'a, T>(
+        &'a self,
+        path: &'a str,
+        data: Vec<u8>,
+    ) -> impl std::future::Future<Output = FrontendResult<T>> + Send + 'a
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        use gloo_net::http::*;
+        use leptos::web_sys::{RequestCredentials, js_sys::Uint8Array};
+        use send_wrapper::SendWrapper;
+
+        SendWrapper::new(async move {
+            let path = self.request_endpoint(path, false)?;
+            let body = Uint8Array::from(data.as_slice());
+            let req = RequestBuilder::new(&path)
+                .method(Method::POST)
+                .credentials(RequestCredentials::Include)
+                .header("content-type", "application/octet-stream")
+                .body(body)?;
+            let res = req.send().await?;
+            let status = res.status();
+            let text = res.text().await?;
+            Self::response(status, text, &res.url())
+        })
+    }
+
     fn response<T>(status: u16, text: String, url: &str) -> FrontendResult<T>
     where
         T: for<'de> Deserialize<'de>,
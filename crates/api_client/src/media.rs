@@ -0,0 +1,43 @@
+use super::ApiClient;
+use crate::errors::FrontendResult;
+use http::Method;
+use ibis_database::common::{
+    SuccessResponse,
+    media::Media,
+    newtypes::MediaId,
+    pagination::{PaginatedResponse, PaginationCursor},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeleteMediaParams {
+    pub id: MediaId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListMediaParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+impl ApiClient {
+    /// Upload an image file, returns the stored media which can be embedded in articles.
+    pub async fn upload_media(&self, data: Vec<u8>) -> FrontendResult<Media> {
+        self.send_bytes("/api/v1/media", data).await
+    }
+
+    pub async fn list_media(
+        &self,
+        params: &ListMediaParams,
+    ) -> FrontendResult<PaginatedResponse<Media>> {
+        self.get("/api/v1/media/list", Some(params)).await
+    }
+
+    pub async fn delete_media(&self, id: MediaId) -> FrontendResult<SuccessResponse> {
+        let params = DeleteMediaParams { id };
+        self.send(Method::DELETE, "/api/v1/media", Some(params))
+            .await
+    }
+}
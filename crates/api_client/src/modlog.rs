@@ -0,0 +1,28 @@
+use super::ApiClient;
+use crate::errors::FrontendResult;
+use ibis_database::common::{
+    modlog::{ModAction, ModlogView},
+    newtypes::{ArticleId, PersonId},
+    pagination::{PaginatedResponse, PaginationCursor},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ListModlogParams {
+    pub moderator_id: Option<PersonId>,
+    pub article_id: Option<ArticleId>,
+    pub action: Option<ModAction>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+impl ApiClient {
+    pub async fn list_modlog(
+        &self,
+        params: &ListModlogParams,
+    ) -> FrontendResult<PaginatedResponse<ModlogView>> {
+        self.get("/api/v1/modlog", Some(params)).await
+    }
+}
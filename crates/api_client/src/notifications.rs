@@ -4,17 +4,29 @@ use ibis_database::common::{
     SuccessResponse,
     newtypes::NotificationId,
     notifications::ApiNotification,
+    pagination::{PaginatedResponse, PaginationCursor},
 };
 use serde::{Deserialize, Serialize};
 
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ListNotificationsParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct MarkAsReadParams {
     pub id: NotificationId,
 }
 
 impl ApiClient {
-    pub async fn notifications_list(&self) -> FrontendResult<Vec<ApiNotification>> {
-        self.get("/api/v1/user/notifications/list", None::<()>)
+    pub async fn notifications_list(
+        &self,
+        params: &ListNotificationsParams,
+    ) -> FrontendResult<PaginatedResponse<ApiNotification>> {
+        self.get("/api/v1/user/notifications/list", Some(params))
             .await
     }
 
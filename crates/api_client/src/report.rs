@@ -0,0 +1,52 @@
+use super::ApiClient;
+use crate::errors::FrontendResult;
+use ibis_database::common::{
+    newtypes::{ArticleId, CommentId, EditId, ReportId},
+    pagination::{PaginatedResponse, PaginationCursor},
+    report::{Report, ReportStatus, ReportView},
+};
+use serde::{Deserialize, Serialize};
+
+/// Report an article, or a single edit or comment on the article.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CreateReportParams {
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListReportsParams {
+    /// Defaults to open reports
+    pub status: Option<ReportStatus>,
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ResolveReportParams {
+    pub report_id: ReportId,
+    /// Dismiss the report if true, otherwise mark it as resolved
+    pub dismiss: bool,
+    pub reason: Option<String>,
+}
+
+impl ApiClient {
+    pub async fn create_report(&self, params: &CreateReportParams) -> FrontendResult<Report> {
+        self.post("/api/v1/report", Some(params)).await
+    }
+
+    pub async fn list_reports(
+        &self,
+        params: &ListReportsParams,
+    ) -> FrontendResult<PaginatedResponse<ReportView>> {
+        self.get("/api/v1/report/list", Some(params)).await
+    }
+
+    pub async fn resolve_report(&self, params: &ResolveReportParams) -> FrontendResult<Report> {
+        self.post("/api/v1/report/resolve", Some(params)).await
+    }
+}
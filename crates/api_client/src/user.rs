@@ -1,11 +1,18 @@
 use super::ApiClient;
 use crate::{article::GetEditList, errors::FrontendResult};
+use chrono::{DateTime, Utc};
+use http::Method;
 use ibis_database::common::{
     SuccessResponse,
+    api_token::{ApiToken, CreateApiTokenResponse},
     article::EditView,
+    ban::{PersonBan, PersonBanView},
     instance::InstanceFollow,
-    newtypes::PersonId,
-    user::{LocalUserView, Person},
+    newtypes::{ApiTokenId, LoginSessionId, PersonId},
+    pagination::{PaginatedResponse, PaginationCursor},
+    session::ListSessionsResponse,
+    totp::{TotpEnableResponse, TotpSetupResponse},
+    user::{LocalUser, LocalUserView, PersonView, Role},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -22,6 +29,8 @@ pub struct RegisterUserParams {
 pub struct LoginUserParams {
     pub username_or_email: String,
     pub password: String,
+    /// One-time code or recovery code, required if the user enabled two-factor authentication
+    pub totp_code: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -30,6 +39,39 @@ pub struct GetUserParams {
     pub domain: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SetUserRoleParams {
+    /// Must be a local user
+    pub person_id: PersonId,
+    pub role: Role,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct BanPersonParams {
+    /// Local or remote user
+    pub person_id: PersonId,
+    pub reason: String,
+    /// Permanent ban if not set
+    pub expires: Option<DateTime<Utc>>,
+    /// Revert edits and delete comments which the user made within this many days
+    pub remove_content_days: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct UnbanPersonParams {
+    pub person_id: PersonId,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListBansParams {
+    /// Value of `next_page` from the previous response, to read the following page
+    pub cursor: Option<PaginationCursor>,
+    /// Maximum number of results, defaults to 50
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct UpdateUserParams {
     pub display_name: Option<String>,
@@ -62,6 +104,9 @@ pub struct OAuthTokenResponse {
 pub struct RegistrationResponse {
     pub user: LocalUserView,
     pub email_verification_required: bool,
+    /// Set if the user has two-factor authentication enabled. Login needs to be finished by
+    /// passing this token and a code to [`ApiClient::login_totp`].
+    pub totp_token: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -88,6 +133,47 @@ pub struct ChangePasswordAfterReset {
     pub confirm_password: String,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct CreateApiTokenParams {
+    pub name: String,
+    /// Allow creating and editing articles. Without any scope the token can only read.
+    pub edit_articles: bool,
+    /// Allow writing comments
+    pub comment: bool,
+    /// Mark edits made with the token as bot edits
+    pub bot_edits: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DeleteApiTokenParams {
+    pub id: ApiTokenId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RevokeSessionParams {
+    pub id: LoginSessionId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LoginTotpParams {
+    /// Token from [`RegistrationResponse::totp_token`]
+    pub token: String,
+    /// One-time code or recovery code
+    pub code: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct EnableTotpParams {
+    /// Current code from the authenticator app, to confirm the setup
+    pub code: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DisableTotpParams {
+    /// Current code from the authenticator app, or a recovery code
+    pub code: String,
+}
+
 impl ApiClient {
     pub async fn register(
         &self,
@@ -100,14 +186,38 @@ impl ApiClient {
         self.post("/api/v1/account/login", Some(&params)).await
     }
 
+    /// Second login step after OAuth login, for users with two-factor authentication.
+    pub async fn login_totp(&self, params: LoginTotpParams) -> FrontendResult<LocalUserView> {
+        self.post("/api/v1/account/login/totp", Some(&params)).await
+    }
+
     pub async fn logout(&self) -> FrontendResult<SuccessResponse> {
         self.post("/api/v1/account/logout", None::<()>).await
     }
 
-    pub async fn get_user(&self, data: GetUserParams) -> FrontendResult<Person> {
+    pub async fn get_user(&self, data: GetUserParams) -> FrontendResult<PersonView> {
         self.get("/api/v1/user", Some(data)).await
     }
 
+    pub async fn ban_person(&self, params: &BanPersonParams) -> FrontendResult<PersonBan> {
+        self.post("/api/v1/user/ban", Some(params)).await
+    }
+
+    pub async fn unban_person(&self, params: &UnbanPersonParams) -> FrontendResult<()> {
+        self.post("/api/v1/user/unban", Some(params)).await
+    }
+
+    pub async fn list_bans(
+        &self,
+        params: &ListBansParams,
+    ) -> FrontendResult<PaginatedResponse<PersonBanView>> {
+        self.get("/api/v1/user/ban/list", Some(params)).await
+    }
+
+    pub async fn set_user_role(&self, params: &SetUserRoleParams) -> FrontendResult<LocalUser> {
+        self.post("/api/v1/user/role", Some(params)).await
+    }
+
     pub async fn get_follows(&self) -> FrontendResult<Vec<InstanceFollow>> {
         self.get("/api/v1/user/follows", None::<()>).await
     }
@@ -126,12 +236,57 @@ impl ApiClient {
             .await
     }
 
+    pub async fn list_api_tokens(&self) -> FrontendResult<Vec<ApiToken>> {
+        self.get("/api/v1/account/api_token/list", None::<()>).await
+    }
+
+    pub async fn create_api_token(
+        &self,
+        params: &CreateApiTokenParams,
+    ) -> FrontendResult<CreateApiTokenResponse> {
+        self.post("/api/v1/account/api_token", Some(params)).await
+    }
+
+    pub async fn delete_api_token(&self, id: ApiTokenId) -> FrontendResult<SuccessResponse> {
+        let params = DeleteApiTokenParams { id };
+        self.send(Method::DELETE, "/api/v1/account/api_token", Some(params))
+            .await
+    }
+
+    pub async fn list_sessions(&self) -> FrontendResult<ListSessionsResponse> {
+        self.get("/api/v1/account/session/list", None::<()>).await
+    }
+
+    pub async fn revoke_session(&self, id: LoginSessionId) -> FrontendResult<SuccessResponse> {
+        let params = RevokeSessionParams { id };
+        self.send(Method::DELETE, "/api/v1/account/session", Some(params))
+            .await
+    }
+
+    /// Generate a new secret for two-factor authentication, which needs to be confirmed with
+    /// [`ApiClient::enable_totp`].
+    pub async fn setup_totp(&self) -> FrontendResult<TotpSetupResponse> {
+        self.post("/api/v1/account/totp/setup", None::<()>).await
+    }
+
+    pub async fn enable_totp(&self, code: String) -> FrontendResult<TotpEnableResponse> {
+        let params = EnableTotpParams { code };
+        self.post("/api/v1/account/totp/enable", Some(params)).await
+    }
+
+    pub async fn disable_totp(&self, code: String) -> FrontendResult<SuccessResponse> {
+        let params = DisableTotpParams { code };
+        self.post("/api/v1/account/totp/disable", Some(params))
+            .await
+    }
+
+    /// Read all edits of a user, which may take multiple requests.
     pub async fn get_person_edits(&self, person_id: PersonId) -> FrontendResult<Vec<EditView>> {
-        let data = GetEditList {
+        let params = GetEditList {
             person_id: Some(person_id),
             ..Default::default()
         };
-        self.get("/api/v1/edit/list", Some(data)).await
+        self.list_all_edits(params).await
     }
 
     pub async fn verify_email(&self, token: String) -> FrontendResult<SuccessResponse> {
@@ -1,7 +1,12 @@
-use super::{UserExt, check_is_admin};
+use super::{UserExt, check_can_moderate, empty_to_none};
 use crate::{
     api::UserExtOpt,
-    utils::{generate_article_ap_id, generate_article_version},
+    utils::{
+        diff_versions,
+        generate_article_ap_id,
+        generate_article_version_from_snapshot,
+        generate_blame,
+    },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
@@ -12,17 +17,24 @@ use diffy::{Patch, apply, create_patch, merge};
 use dom_query::Document;
 use ibis_api_client::{
     article::{
+        CompareVersionsParams,
         CreateArticleParams,
         DeleteConflictParams,
         EditArticleParams,
         FollowArticleParams,
         ForkArticleParams,
         GetArticleParams,
+        GetBacklinksParams,
+        GetBlameParams,
         GetConflictParams,
         ImportArticleParams,
         ListArticlesParams,
+        ListOrphanedArticlesParams,
+        ListWantedArticlesParams,
+        MoveArticleParams,
         ProtectArticleParams,
         RemoveArticleParams,
+        RevertArticleParams,
     },
     instance::SearchArticleParams,
 };
@@ -33,18 +45,31 @@ use ibis_database::{
         article::{
             ApiConflict,
             Article,
+            ArticleBlame,
+            ArticleDiff,
             ArticleView,
+            BlameLine,
             Conflict,
             Edit,
             EditVersion,
+            LinkedArticle,
+            SearchArticleResponse,
+            WantedArticle,
             can_edit_article,
         },
         instance::Instance,
+        modlog::{ModAction, Modlog},
         newtypes::InstanceId,
+        pagination::PaginatedResponse,
         user::Person,
     },
     error::BackendResult,
-    impls::{IbisContext, article::DbArticleForm, conflict::DbConflictForm, edit::DbEditForm},
+    impls::{
+        IbisContext,
+        article::DbArticleForm,
+        conflict::DbConflictForm,
+        edit::{DbEditForm, ViewEditParams},
+    },
 };
 use ibis_federate::{
     activities::{
@@ -56,6 +81,7 @@ use ibis_federate::{
         submit_article_update,
     },
     objects::article::ArticleWrapper,
+    search::{FederatedSearchParams, search_remote_instances},
     validate::{validate_article_title, validate_not_empty},
 };
 use ibis_markdown::format_markdown;
@@ -155,6 +181,7 @@ async fn do_create(
         &article,
         user.person.clone().into(),
         true,
+        user.bot_edits(),
         &context,
     )
     .await?;
@@ -176,21 +203,68 @@ async fn do_create(
 pub(crate) async fn edit_article(
     user: UserExt,
     context: Data<IbisContext>,
-    Form(mut params): Form<EditArticleParams>,
+    Form(params): Form<EditArticleParams>,
 ) -> BackendResult<Json<Option<ApiConflict>>> {
     validate_not_empty(&params.new_text)?;
     // resolve conflict if any
     if let Some(resolve_conflict_id) = params.resolve_conflict_id {
         Conflict::delete(resolve_conflict_id, user.person.id, &context)?;
     }
-    let original_article = Article::read_view(params.article_id, Some(&user), &context)?;
+    do_edit(params, user, &context).await
+}
+
+/// Revert an article to an earlier version. The old text is regenerated from the edit history
+/// and submitted as a new edit, so it gets federated and may result in a conflict just like
+/// [edit_article].
+#[debug_handler]
+pub(crate) async fn revert_article(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<RevertArticleParams>,
+) -> BackendResult<Json<Option<ApiConflict>>> {
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    let article = Article::read_view(params.article_id, Some(&user), &context)?;
+    let edits = Edit::list_for_article(article.article.id, &context)?;
+    let new_text = generate_article_version_from_snapshot(&edits, &params.version, &context)?;
+    validate_not_empty(&new_text)?;
+    let reason = params.reason;
+    let moderator_id = user.person.id;
+    let params = EditArticleParams {
+        article_id: article.article.id,
+        new_text,
+        summary: format!("Revert to version {}", params.version.0),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+    };
+    let res = do_edit(params, user, &context).await?;
+    Modlog::log(
+        ModAction::RevertArticle,
+        moderator_id,
+        Some(article.article.id),
+        None,
+        reason,
+        &context,
+    )?;
+    Ok(res)
+}
+
+async fn do_edit(
+    mut params: EditArticleParams,
+    user: UserExt,
+    context: &Data<IbisContext>,
+) -> BackendResult<Json<Option<ApiConflict>>> {
+    let original_article = Article::read_view(params.article_id, Some(&user), context)?;
     if params.new_text == original_article.article.text {
         return Err(anyhow!("Edit contains no changes").into());
     }
     if params.summary.is_empty() {
         return Err(anyhow!("No summary given").into());
     }
-    can_edit_article(&original_article.article, user.local_user.admin)?;
+    can_edit_article(
+        &original_article.article,
+        user.local_user.role.can_moderate(),
+    )?;
     // ensure trailing newline for clean diffs
     if !params.new_text.ends_with('\n') {
         params.new_text.push('\n');
@@ -212,18 +286,20 @@ pub(crate) async fn edit_article(
             &original_article.article,
             user.person.clone().into(),
             false,
-            &context,
+            user.bot_edits(),
+            context,
         )
         .await?;
         Ok(Json(None))
     } else {
         // There have been other changes since this edit was initiated. Get the common ancestor
         // version and generate a diff to find out what exactly has changed.
-        let edits = Edit::list_for_article(original_article.article.id, &context)?;
-        let ancestor = generate_article_version(&edits, &params.previous_version_id)?;
+        let edits = Edit::list_for_article(original_article.article.id, context)?;
+        let ancestor =
+            generate_article_version_from_snapshot(&edits, &params.previous_version_id, context)?;
         let patch = create_patch(&ancestor, &new_text);
 
-        let previous_version = Edit::read(&params.previous_version_id, &context)?;
+        let previous_version = Edit::read(&params.previous_version_id, context)?;
         let form = DbConflictForm {
             hash: EditVersion::new(&patch.to_string()),
             diff: patch.to_string(),
@@ -232,9 +308,9 @@ pub(crate) async fn edit_article(
             article_id: original_article.article.id,
             previous_version_id: previous_version.hash,
         };
-        let conflict = Conflict::create(&form, &context)?;
+        let conflict = Conflict::create(&form, context)?;
         Ok(Json(
-            db_conflict_to_api_conflict(conflict, true, &context).await?,
+            db_conflict_to_api_conflict(conflict, true, context).await?,
         ))
     }
 }
@@ -269,16 +345,19 @@ pub(crate) async fn list_articles(
     user: UserExtOpt,
     Query(query): Query<ListArticlesParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<Article>>> {
+) -> BackendResult<Json<PaginatedResponse<Article>>> {
     let include_removed = user
         .as_ref()
-        .map(|u| u.local_user.admin)
+        .map(|u| u.local_user.role.can_moderate())
         .unwrap_or_default()
         && query.include_removed.unwrap_or_default();
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
     Ok(Json(Article::read_all(
         query.only_local,
         query.instance_id,
         include_removed,
+        query.cursor,
+        Some(limit),
         &context,
     )?))
 }
@@ -324,6 +403,8 @@ pub(crate) async fn fork_article(
             previous_version_id: e.previous_version_id,
             published: e.published,
             pending: false,
+            awaiting_review: false,
+            bot: e.bot,
         };
         Edit::create_or_update(&form, false, &context).await?;
     }
@@ -335,6 +416,41 @@ pub(crate) async fn fork_article(
     Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
 }
 
+/// Change the title of a local article. Links to the old title are redirected to the new one, and
+/// the rename is federated so that other instances update their copy.
+#[debug_handler]
+pub(crate) async fn move_article(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<MoveArticleParams>,
+) -> BackendResult<Json<ArticleView>> {
+    let article = Article::read_view(params.article_id, Some(&user), &context)?.article;
+    if !article.local {
+        return Err(anyhow!("Only local articles can be moved").into());
+    }
+    can_edit_article(&article, user.local_user.role.can_moderate())?;
+    validate_article_title(&params.new_title)?;
+    if params.new_title == article.title {
+        return Err(anyhow!("Article already has this title").into());
+    }
+    // Titles of other articles can't be used, but it is fine to overwrite a redirect
+    match Article::read_local_by_title(&params.new_title, &context)? {
+        Some(existing) if existing.removed => {
+            return Err(anyhow!("A removed article with this title already exists").into());
+        }
+        Some(_) => return Err(anyhow!("Article with this title already exists").into()),
+        None => {}
+    }
+
+    let local_instance = Instance::read_local(&context)?;
+    let ap_id = generate_article_ap_id(&params.new_title, &local_instance)?;
+    let article = Article::rename(article.id, params.new_title, ap_id, &context)?;
+
+    UpdateArticle::send(article.clone().into(), &context).await?;
+
+    Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
+}
+
 /// Fetch a remote article, including edits collection. Allows viewing and editing. Note that new
 /// article changes can only be received if we follow the instance, or if it is refetched manually.
 #[debug_handler]
@@ -347,27 +463,165 @@ pub(super) async fn resolve_article(
     Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
 }
 
-/// Search articles for matching title or body text.
+/// List articles which link to the given article.
+#[debug_handler]
+pub(super) async fn get_backlinks(
+    Query(query): Query<GetBacklinksParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<LinkedArticle>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Article::read_backlinks(
+        query.article_id,
+        query.cursor,
+        limit,
+        &context,
+    )?))
+}
+
+/// Attribute each line of the current article text to the edit which last changed it.
+#[debug_handler]
+pub(super) async fn get_blame(
+    Query(query): Query<GetBlameParams>,
+    user: UserExtOpt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<ArticleBlame>> {
+    // ensure that the article is visible for the user
+    Article::read_view(query.article_id, user.inner().as_ref(), &context)?;
+    let edits = Edit::list_views(
+        ViewEditParams::ArticleId(query.article_id),
+        &None,
+        None,
+        None,
+        &context,
+    )?
+    .items;
+    let blame = generate_blame(&edits.iter().map(|e| e.edit.clone()).collect::<Vec<_>>())?;
+
+    let mut used = vec![false; edits.len()];
+    let lines = blame
+        .into_iter()
+        .map(|(text, i)| {
+            used[i] = true;
+            BlameLine {
+                text,
+                edit: edits[i].edit.hash.clone(),
+            }
+        })
+        .collect();
+    let edits = edits
+        .into_iter()
+        .zip(used)
+        .filter_map(|(e, used)| used.then_some(e))
+        .collect();
+    Ok(Json(ArticleBlame { lines, edits }))
+}
+
+/// Compare two versions of an article, by lines, words or rendered HTML.
+#[debug_handler]
+pub(super) async fn compare_versions(
+    Query(params): Query<CompareVersionsParams>,
+    user: UserExtOpt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<ArticleDiff>> {
+    let article = Article::read_view(params.article_id, user.inner().as_ref(), &context)?;
+    let edits = Edit::list_for_article(article.article.id, &context)?;
+    let from = generate_article_version_from_snapshot(&edits, &params.from, &context)?;
+    let to = generate_article_version_from_snapshot(&edits, &params.to, &context)?;
+    Ok(Json(diff_versions(&from, &to, params.granularity)))
+}
+
+/// List titles which are linked from articles, but don't exist on the local instance.
+#[debug_handler]
+pub(super) async fn list_wanted_articles(
+    Query(query): Query<ListWantedArticlesParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<WantedArticle>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Article::read_wanted(query.cursor, limit, &context)?))
+}
+
+/// List local articles which are not linked from any other article.
+#[debug_handler]
+pub(super) async fn list_orphaned_articles(
+    Query(query): Query<ListOrphanedArticlesParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<LinkedArticle>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Article::read_orphaned(query.cursor, limit, &context)?))
+}
+
+/// Search articles for matching title or body text. With `federated` the query is also sent
+/// to all known remote instances, and their results are merged by rank.
 #[debug_handler]
 pub(super) async fn search_article(
     Query(query): Query<SearchArticleParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<Article>>> {
-    if query.query.is_empty() {
+) -> BackendResult<Json<SearchArticleResponse>> {
+    if query.query.trim().is_empty() {
         return Err(anyhow!("Query is empty").into());
     }
-    let article = Article::search(&query.query, &context)?;
-    Ok(Json(article))
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let first_page = query.cursor.is_none();
+    let PaginatedResponse {
+        items: mut results,
+        next_page,
+    } = Article::search(
+        &query.query,
+        query.instance_id,
+        query.only_local.unwrap_or_default(),
+        query.cursor,
+        limit,
+        &context,
+    )?;
+    let mut timed_out = vec![];
+    // Remote instances can't continue from a local cursor, so their results are only included
+    // in the first page
+    if query.federated.unwrap_or_default() && first_page {
+        let params = FederatedSearchParams {
+            query: query.query,
+            limit,
+        };
+        // Remote results can be ranked as high as the best local result, but not higher
+        let max_rank = results.first().map(|r| r.rank).unwrap_or(1.0);
+        let (remote_results, timed_out_) =
+            search_remote_instances(&params, max_rank, &context).await?;
+        timed_out = timed_out_;
+        // Skip remote articles which are already included from the local database
+        let remote_results: Vec<_> = remote_results
+            .into_iter()
+            .filter(|r| !results.iter().any(|l| l.ap_id == r.ap_id))
+            .collect();
+        results.extend(remote_results);
+        results.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+    }
+    Ok(Json(SearchArticleResponse {
+        results,
+        next_page,
+        timed_out,
+    }))
 }
 
 #[debug_handler]
 pub(crate) async fn protect_article(
     user: UserExt,
     context: Data<IbisContext>,
-    Form(params): Form<ProtectArticleParams>,
+    Form(mut params): Form<ProtectArticleParams>,
 ) -> BackendResult<Json<Article>> {
-    check_is_admin(&user)?;
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
     let article = Article::update_protected(params.article_id, params.protected, &context)?;
+    Modlog::log(
+        if params.protected {
+            ModAction::ProtectArticle
+        } else {
+            ModAction::UnprotectArticle
+        },
+        user.person.id,
+        Some(article.id),
+        None,
+        params.reason,
+        &context,
+    )?;
     Ok(Json(article))
 }
 
@@ -375,10 +629,23 @@ pub(crate) async fn protect_article(
 pub async fn remove_article(
     user: UserExt,
     context: Data<IbisContext>,
-    Form(params): Form<RemoveArticleParams>,
+    Form(mut params): Form<RemoveArticleParams>,
 ) -> BackendResult<Json<()>> {
-    check_is_admin(&user)?;
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
     let article = Article::update_removed(params.article_id, params.remove, &context)?;
+    Modlog::log(
+        if params.remove {
+            ModAction::RemoveArticle
+        } else {
+            ModAction::RestoreArticle
+        },
+        user.person.id,
+        Some(article.id),
+        None,
+        params.reason,
+        &context,
+    )?;
     let actor = user.person.ap_id.clone().into();
     if params.remove {
         RemoveArticle::send(actor, article.into(), &context).await?;
@@ -441,7 +708,8 @@ pub async fn db_conflict_to_api_conflict(
 
     // create common ancestor version
     let edits = Edit::list_for_article(original_article.id, context)?;
-    let ancestor = generate_article_version(&edits, &conflict.previous_version_id)?;
+    let ancestor =
+        generate_article_version_from_snapshot(&edits, &conflict.previous_version_id, context)?;
 
     let patch = Patch::from_str(&conflict.diff)?;
     // apply self.diff to ancestor to get `ours`
@@ -457,6 +725,7 @@ pub async fn db_conflict_to_api_conflict(
                 &original_article,
                 person,
                 false,
+                false,
                 context,
             )
             .await?;
@@ -1,13 +1,19 @@
 use super::UserExt;
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
-use axum::{Form, Json};
+use axum::{Form, Json, extract::Query};
 use axum_macros::debug_handler;
 use chrono::Utc;
-use ibis_api_client::comment::{CreateCommentParams, EditCommentParams, GetCommentParams};
+use ibis_api_client::comment::{
+    CreateCommentParams,
+    EditCommentParams,
+    GetCommentParams,
+    ListCommentsParams,
+};
 use ibis_database::{
     common::{
         comment::{Comment, CommentView, CommentViewWithArticle},
+        pagination::PaginatedResponse,
         utils::http_protocol_str,
     },
     error::BackendResult,
@@ -126,3 +132,18 @@ pub(crate) async fn get_comment(
 ) -> BackendResult<Json<CommentViewWithArticle>> {
     Ok(Json(Comment::read_view(params.id, &context)?))
 }
+
+/// Comments of an article, newest first.
+#[debug_handler]
+pub(crate) async fn list_comments(
+    Query(params): Query<ListCommentsParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<CommentView>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Comment::read_for_article(
+        params.article_id,
+        params.cursor,
+        Some(limit),
+        &context,
+    )?))
+}
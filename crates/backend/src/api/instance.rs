@@ -1,27 +1,44 @@
-use super::{UserExt, empty_to_none};
+use super::{UserExt, check_is_admin, empty_to_none};
 use crate::api::UserExtOpt;
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
 use axum::{Form, Json, extract::Query};
 use axum_macros::debug_handler;
-use ibis_api_client::instance::{FollowInstanceParams, GetInstanceParams, UpdateInstanceParams};
+use chrono::Utc;
+use ibis_api_client::instance::{
+    BlockedContent,
+    FollowInstanceParams,
+    GetInstanceParams,
+    ListDeliveryStatusParams,
+    ListFederationParams,
+    ListInstancesParams,
+    RemoveFederationListParams,
+    UpdateFederationListParams,
+    UpdateInstanceParams,
+};
 use ibis_database::{
     common::{
         ResolveObjectParams,
         SuccessResponse,
+        delivery::{DeliveryStatus, DeliveryStatusView},
+        federation_list::{FederationListEntry, FederationMode},
         instance::{Instance, InstanceView},
+        modlog::{ModAction, Modlog},
+        pagination::{PaginatedResponse, PaginationCursor},
         user::Person,
         utils::http_protocol_str,
     },
     error::BackendResult,
     impls::{
         IbisContext,
+        federation_list::FederationListForm,
         instance::{DbInstanceUpdateForm, InstanceViewQuery},
     },
 };
 use ibis_federate::{
     activities::following::{follow::Follow, undo_follow::UndoFollow},
     objects::instance::InstanceWrapper,
+    validate::validate_not_empty,
 };
 use moka::sync::Cache;
 use std::{ops::Deref, sync::LazyLock, time::Duration};
@@ -57,17 +74,39 @@ pub(crate) async fn get_instance(
     Ok(Json(instance))
 }
 
+#[debug_handler]
 pub(crate) async fn update_instance(
+    user: UserExt,
     context: Data<IbisContext>,
     Form(mut params): Form<UpdateInstanceParams>,
 ) -> BackendResult<Json<Instance>> {
+    check_is_admin(&user, &context)?;
     empty_to_none(&mut params.name);
     empty_to_none(&mut params.topic);
+    empty_to_none(&mut params.reason);
+    if params.review_new_user_edits.is_some_and(|e| e < 0) {
+        return Err(anyhow!("Number of edits to review must not be negative").into());
+    }
+    if params.require_totp_for_moderators == Some(true) && !user.local_user.totp_enabled {
+        return Err(anyhow!("Enable two-factor authentication for your own account first").into());
+    }
     let form = DbInstanceUpdateForm {
         name: params.name,
         topic: params.topic,
+        review_all_edits: params.review_all_edits,
+        review_new_user_edits: params.review_new_user_edits,
+        require_totp_for_moderators: params.require_totp_for_moderators,
     };
-    Ok(Json(Instance::update(form, &context)?))
+    let instance = Instance::update(form, &context)?;
+    Modlog::log(
+        ModAction::UpdateInstance,
+        user.person.id,
+        None,
+        None,
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(instance))
 }
 
 /// Make the local instance follow a given remote instance, to receive activities about new and
@@ -106,20 +145,27 @@ pub(super) async fn resolve_instance(
 #[debug_handler]
 pub(crate) async fn list_instance_views(
     user: UserExtOpt,
+    Query(params): Query<ListInstancesParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<InstanceView>>> {
+) -> BackendResult<Json<PaginatedResponse<InstanceView>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
     let mut instances = if cfg!(debug_assertions) {
-        Instance::list_with_articles(&context)?
+        Instance::list_with_articles(params.cursor, limit, &context)?
     } else {
         // Cache result of the db read in prod because it uses a lot of queries and rarely changes
-        static CACHE: LazyLock<Cache<(), Vec<InstanceView>>> = LazyLock::new(|| {
-            Cache::builder()
-                .max_capacity(1)
-                .time_to_live(Duration::from_secs(5 * 60))
-                .build()
-        });
+        type CacheKey = (Option<PaginationCursor>, i64);
+        static CACHE: LazyLock<Cache<CacheKey, PaginatedResponse<InstanceView>>> =
+            LazyLock::new(|| {
+                Cache::builder()
+                    .max_capacity(100)
+                    .time_to_live(Duration::from_secs(5 * 60))
+                    .build()
+            });
+        let key = (params.cursor.clone(), limit);
         CACHE
-            .try_get_with((), || Instance::list_with_articles(&context))
+            .try_get_with(key, || {
+                Instance::list_with_articles(params.cursor, limit, &context)
+            })
             .map_err(|e| anyhow!(e))?
     };
 
@@ -129,7 +175,7 @@ pub(crate) async fn list_instance_views(
             .iter()
             .map(|i| i.instance.id)
             .collect::<Vec<_>>();
-        for i in &mut instances {
+        for i in &mut instances.items {
             if following.contains(&i.instance.id) {
                 i.following = true;
             }
@@ -137,3 +183,101 @@ pub(crate) async fn list_instance_views(
     }
     Ok(Json(instances))
 }
+
+/// Domains which are allowed or blocked for federation. This is public so that other admins can
+/// see which instances are blocked and why.
+#[debug_handler]
+pub(crate) async fn list_federation(
+    Query(params): Query<ListFederationParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<FederationListEntry>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(FederationListEntry::list(
+        params.cursor,
+        limit,
+        &context,
+    )?))
+}
+
+#[debug_handler]
+pub(crate) async fn update_federation_list(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<UpdateFederationListParams>,
+) -> BackendResult<Json<FederationListEntry>> {
+    check_is_admin(&user, &context)?;
+    validate_not_empty(&params.reason)?;
+    let domain = normalize_domain(&params.domain)?;
+    if domain == Instance::read_local(&context)?.domain {
+        return Err(anyhow!("Cannot change federation with the local instance").into());
+    }
+    if params.expires.is_some_and(|e| e <= Utc::now()) {
+        return Err(anyhow!("Expiry must be in the future").into());
+    }
+    let form = FederationListForm {
+        domain,
+        mode: params.mode,
+        reason: params.reason.trim().to_string(),
+        expires: params.expires,
+    };
+    let entry = FederationListEntry::upsert(&form, &context)?;
+    let action = match entry.mode {
+        FederationMode::Allow => ModAction::AllowDomain,
+        FederationMode::Block => ModAction::BlockDomain,
+    };
+    Modlog::log_domain(
+        action,
+        user.person.id,
+        entry.domain.clone(),
+        Some(entry.reason.clone()),
+        &context,
+    )?;
+    if entry.mode == FederationMode::Block {
+        match params.content.unwrap_or_default() {
+            BlockedContent::Keep => {}
+            BlockedContent::Hide => Instance::hide_content(&entry.domain, &context)?,
+            BlockedContent::Purge => Instance::purge(&entry.domain, &context)?,
+        }
+    }
+    Ok(Json(entry))
+}
+
+#[debug_handler]
+pub(crate) async fn remove_federation_list(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RemoveFederationListParams>,
+) -> BackendResult<Json<FederationListEntry>> {
+    check_is_admin(&user, &context)?;
+    let domain = normalize_domain(&params.domain)?;
+    let entry = FederationListEntry::delete(&domain, &context)?;
+    Modlog::log_domain(
+        ModAction::RemoveFederationListEntry,
+        user.person.id,
+        domain,
+        None,
+        &context,
+    )?;
+    Ok(Json(entry))
+}
+
+/// Domains in the federation list are stored in lowercase without surrounding whitespace.
+fn normalize_domain(domain: &str) -> BackendResult<String> {
+    let domain = domain.trim().to_lowercase();
+    if domain.is_empty() || domain.contains(['/', ' ', ',']) {
+        return Err(anyhow!("Invalid domain {domain}").into());
+    }
+    Ok(domain)
+}
+
+/// Delivery of outgoing activities to each remote instance.
+#[debug_handler]
+pub(crate) async fn list_delivery_status(
+    user: UserExt,
+    Query(params): Query<ListDeliveryStatusParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<DeliveryStatusView>>> {
+    check_is_admin(&user, &context)?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(DeliveryStatus::list(params.cursor, limit, &context)?))
+}
@@ -0,0 +1,144 @@
+use super::{UserExt, check_is_admin};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{
+    Form,
+    Json,
+    body::{Body, to_bytes},
+    extract::Query,
+};
+use axum_macros::debug_handler;
+use ibis_api_client::media::{DeleteMediaParams, ListMediaParams};
+use ibis_database::{
+    common::{
+        SuccessResponse,
+        instance::Instance,
+        media::{ALLOWED_MEDIA_TYPES, MEDIA_PATH, Media},
+        pagination::PaginatedResponse,
+        utils::http_protocol_str,
+    },
+    config::IbisConfig,
+    error::BackendResult,
+    impls::{IbisContext, media::MediaInsertForm},
+};
+use image::{ImageFormat, guess_format, load_from_memory_with_format};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{create_dir_all, remove_file, write},
+    io::ErrorKind,
+    path::PathBuf,
+};
+use tokio::task::spawn_blocking;
+use url::Url;
+
+/// Maximum width and height of generated thumbnails
+const THUMBNAIL_SIZE: u32 = 256;
+
+/// Location of an uploaded file on the local filesystem.
+pub(crate) fn media_file_path(conf: &IbisConfig, filename: &str) -> PathBuf {
+    PathBuf::from(&conf.media.upload_dir).join(filename)
+}
+
+/// Location of the thumbnail for an uploaded file on the local filesystem.
+pub(crate) fn thumbnail_file_path(conf: &IbisConfig, filename: &str) -> PathBuf {
+    PathBuf::from(&conf.media.upload_dir)
+        .join("thumbnail")
+        .join(filename)
+}
+
+/// Upload an image, with the file content as request body. Files are stored by content hash,
+/// so uploading the same file again returns the existing media.
+#[debug_handler]
+pub(super) async fn upload_media(
+    user: UserExt,
+    context: Data<IbisContext>,
+    body: Body,
+) -> BackendResult<Json<Media>> {
+    let max_size = context.conf.media.max_upload_size;
+    let data = to_bytes(body, max_size)
+        .await
+        .map_err(|_| anyhow!("File is larger than maximum size of {max_size} bytes"))?;
+    let format = guess_format(&data).map_err(|_| anyhow!("Unknown file type"))?;
+    let mime_type = format.to_mime_type();
+    if !ALLOWED_MEDIA_TYPES.contains(&mime_type) {
+        return Err(anyhow!("File type {mime_type} is not allowed").into());
+    }
+    let extension = format
+        .extensions_str()
+        .first()
+        .ok_or(anyhow!("Unknown file extension"))?;
+    let filename = format!("{}.{extension}", hex::encode(Sha256::digest(&data)));
+
+    let file_path = media_file_path(&context.conf, &filename);
+    let thumbnail_path = thumbnail_file_path(&context.conf, &filename);
+    spawn_blocking(move || store_file(&data, format, file_path, thumbnail_path)).await??;
+
+    let ap_id = Url::parse(&format!(
+        "{}://{}{MEDIA_PATH}/{filename}",
+        http_protocol_str(),
+        context.conf.federation_domain()
+    ))?;
+    let form = MediaInsertForm {
+        filename,
+        ap_id: ap_id.into(),
+        instance_id: Instance::read_local(&context)?.id,
+        creator_id: Some(user.person.id),
+        mime_type: mime_type.to_string(),
+        local: true,
+    };
+    Ok(Json(Media::create(&form, &context)?))
+}
+
+/// Write the file and a scaled down thumbnail to disk. Decoding the image also ensures
+/// that the file is valid.
+fn store_file(
+    data: &[u8],
+    format: ImageFormat,
+    file_path: PathBuf,
+    thumbnail_path: PathBuf,
+) -> BackendResult<()> {
+    let image = load_from_memory_with_format(data, format)?;
+    for path in [&file_path, &thumbnail_path] {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+    }
+    write(file_path, data)?;
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .save_with_format(thumbnail_path, format)?;
+    Ok(())
+}
+
+/// List media which was uploaded to this instance.
+#[debug_handler]
+pub(super) async fn list_media(
+    Query(params): Query<ListMediaParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<Media>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Media::list_local(params.cursor, limit, &context)?))
+}
+
+/// Delete media and the stored files. Articles which embed it will show a broken image.
+#[debug_handler]
+pub(super) async fn delete_media(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteMediaParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user, &context)?;
+    let media = Media::delete(params.id, &context)?;
+    if media.local {
+        for path in [
+            media_file_path(&context.conf, &media.filename),
+            thumbnail_file_path(&context.conf, &media.filename),
+        ] {
+            match remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+    }
+    Ok(Json(SuccessResponse::default()))
+}
@@ -1,14 +1,21 @@
 use crate::api::{
     article::{
+        compare_versions,
         create_article,
         edit_article,
         fork_article,
         get_article,
+        get_backlinks,
+        get_blame,
         get_conflict,
         import_article,
         list_articles,
+        list_orphaned_articles,
+        list_wanted_articles,
+        move_article,
         protect_article,
         resolve_article,
+        revert_article,
         search_article,
     },
     comment::{create_comment, edit_comment},
@@ -27,29 +34,51 @@ use axum::{
     routing::{delete, get, patch, post},
 };
 use axum_macros::{FromRequestParts, debug_handler};
-use comment::get_comment;
+use comment::{get_comment, list_comments};
 use http::StatusCode;
-use ibis_api_client::article::GetEditList;
+use ibis_api_client::article::{GetEditList, ListRecentChangesParams};
 use ibis_database::{
     common::{
+        api_token::ApiToken,
         article::{Edit, EditView},
         instance::{Instance, SiteView},
+        pagination::PaginatedResponse,
         user::{LocalUserView, Person},
     },
     error::BackendResult,
-    impls::{IbisContext, edit::ViewEditParams},
+    impls::{
+        IbisContext,
+        edit::{RecentChangesQuery, ViewEditParams},
+    },
+};
+use instance::{
+    list_delivery_status,
+    list_federation,
+    list_instance_views,
+    remove_federation_list,
+    update_federation_list,
+    update_instance,
 };
-use instance::{list_instance_views, update_instance};
+use media::{delete_media, list_media, upload_media};
+use modlog::list_modlog;
+use report::{create_report, list_reports, resolve_report};
+use review::{list_edits_awaiting_review, require_review, review_edit};
 use std::ops::Deref;
 use user::{
+    api_token::{create_api_token, delete_api_token, list_api_tokens},
     article_notif_mark_as_read,
+    ban::{ban_person, list_bans, unban_person},
     change_password,
     change_password_after_reset,
     count_notifications,
     get_user_follows,
     list_notifications,
+    list_sessions,
     register::authenticate_with_oauth,
     request_reset_password,
+    revoke_session,
+    set_user_role,
+    totp::{disable_totp, enable_totp, login_totp, setup_totp},
     update_user_profile,
     verify_email,
 };
@@ -57,8 +86,14 @@ use user::{
 mod article;
 mod comment;
 mod instance;
+mod media;
+mod modlog;
+mod report;
+mod review;
 pub(super) mod user;
 
+pub(crate) use media::{media_file_path, thumbnail_file_path};
+
 pub fn api_routes() -> Router<()> {
     Router::new()
         .route(
@@ -68,24 +103,53 @@ pub fn api_routes() -> Router<()> {
         .route("/article/import", post(import_article))
         .route("/article/list", get(list_articles))
         .route("/article/fork", post(fork_article))
+        .route("/article/revert", post(revert_article))
+        .route("/article/move", post(move_article))
         .route("/article/resolve", get(resolve_article))
         .route("/article/protect", post(protect_article))
         .route("/article/remove", post(remove_article))
         .route("/article/follow", post(follow_article))
+        .route("/article/backlinks", get(get_backlinks))
+        .route("/article/blame", get(get_blame))
+        .route("/article/compare", get(compare_versions))
+        .route("/article/wanted", get(list_wanted_articles))
+        .route("/article/orphaned", get(list_orphaned_articles))
+        .route("/article/require_review", post(require_review))
         .route("/edit/list", get(edit_list))
+        .route("/edit/recent", get(list_recent_changes))
+        .route("/edit/review/list", get(list_edits_awaiting_review))
+        .route("/edit/review", post(review_edit))
         .route("/conflict", get(get_conflict))
         .route("/conflict", delete(delete_conflict))
         .route("/comment", post(create_comment))
         .route("/comment", patch(edit_comment))
         .route("/comment", get(get_comment))
+        .route("/comment/list", get(list_comments))
         .route("/instance", get(get_instance))
         .route("/instance", patch(update_instance))
         .route("/instance/follow", post(follow_instance))
         .route("/instance/resolve", get(resolve_instance))
         .route("/instance/list", get(list_instance_views))
+        .route("/instance/delivery", get(list_delivery_status))
+        .route(
+            "/instance/federation",
+            get(list_federation)
+                .post(update_federation_list)
+                .delete(remove_federation_list),
+        )
         .route("/search", get(search_article))
+        .route("/media", post(upload_media).delete(delete_media))
+        .route("/media/list", get(list_media))
+        .route("/modlog", get(list_modlog))
+        .route("/report", post(create_report))
+        .route("/report/list", get(list_reports))
+        .route("/report/resolve", post(resolve_report))
         .route("/user", get(get_user))
         .route("/user/follows", get(get_user_follows))
+        .route("/user/role", post(set_user_role))
+        .route("/user/ban", post(ban_person))
+        .route("/user/unban", post(unban_person))
+        .route("/user/ban/list", get(list_bans))
         .route("/user/notifications/list", get(list_notifications))
         .route("/user/notifications/count", get(count_notifications))
         .route(
@@ -94,11 +158,22 @@ pub fn api_routes() -> Router<()> {
         )
         .route("/account/register", post(register_user))
         .route("/account/login", post(login_user))
+        .route("/account/login/totp", post(login_totp))
         .route("/account/logout", post(logout_user))
         .route("/account/update", post(update_user_profile))
         .route("/account/oauth/authenticate", post(authenticate_with_oauth))
         .route("/account/verify_email", post(verify_email))
         .route("/account/change_password", post(change_password))
+        .route(
+            "/account/api_token",
+            post(create_api_token).delete(delete_api_token),
+        )
+        .route("/account/api_token/list", get(list_api_tokens))
+        .route("/account/session", delete(revoke_session))
+        .route("/account/session/list", get(list_sessions))
+        .route("/account/totp/setup", post(setup_totp))
+        .route("/account/totp/enable", post(enable_totp))
+        .route("/account/totp/disable", post(disable_totp))
         .route(
             "/account/request_reset_password",
             post(request_reset_password),
@@ -110,10 +185,26 @@ pub fn api_routes() -> Router<()> {
         .route("/site", get(site_view))
 }
 
-pub fn check_is_admin(user: &LocalUserView) -> BackendResult<()> {
-    if !user.local_user.admin {
+pub fn check_is_admin(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    if !user.local_user.role.is_admin() {
         return Err(anyhow!("Only admin can perform this action").into());
     }
+    check_totp_requirement(user, context)
+}
+
+pub fn check_can_moderate(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    if !user.local_user.role.can_moderate() {
+        return Err(anyhow!("Only moderators can perform this action").into());
+    }
+    check_totp_requirement(user, context)
+}
+
+/// Admins can require moderators to enable two-factor authentication before they can use
+/// their permissions.
+fn check_totp_requirement(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    if !user.local_user.totp_enabled && Instance::read_local(context)?.require_totp_for_moderators {
+        return Err(anyhow!("Enable two-factor authentication to perform this action").into());
+    }
     Ok(())
 }
 
@@ -139,13 +230,13 @@ pub(crate) async fn site_view(
     }))
 }
 
-/// Get a list of all unresolved edit conflicts.
+/// Get edits of an article or user, oldest first.
 #[debug_handler]
 pub async fn edit_list(
     Query(query): Query<GetEditList>,
     user: UserExtOpt,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<EditView>>> {
+) -> BackendResult<Json<PaginatedResponse<EditView>>> {
     let params = if let Some(article_id) = query.article_id {
         ViewEditParams::ArticleId(article_id)
     } else if let Some(person_id) = query.person_id {
@@ -153,7 +244,47 @@ pub async fn edit_list(
     } else {
         return Err(anyhow!("Must provide article_id or person_id").into());
     };
-    Ok(Json(Edit::list_views(params, &user.inner(), &context)?))
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Edit::list_views(
+        params,
+        &user.inner(),
+        query.cursor,
+        Some(limit),
+        &context,
+    )?))
+}
+
+/// Edits to all articles, local and federated, newest first.
+#[debug_handler]
+pub(crate) async fn list_recent_changes(
+    Query(params): Query<ListRecentChangesParams>,
+    user: UserExtOpt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<EditView>>> {
+    let unreviewed_only = params.unreviewed_only.unwrap_or_default();
+    if unreviewed_only {
+        let user = user
+            .inner()
+            .ok_or(anyhow!("Only moderators can view unreviewed edits"))?;
+        check_can_moderate(&user, &context)?;
+    }
+    let query = RecentChangesQuery {
+        instance_id: params.instance_id,
+        article_id: None,
+        person_id: params.person_id,
+        local_only: params.local_only.unwrap_or_default(),
+        since: params.since,
+        until: params.until,
+        hide_bots: params.hide_bots.unwrap_or_default(),
+        unreviewed_only,
+    };
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Edit::list_recent(
+        query,
+        params.cursor,
+        limit,
+        &context,
+    )?))
 }
 
 /// Trims the string param, and converts to None if it is empty
@@ -173,6 +304,9 @@ fn empty_to_none(val: &mut Option<String>) {
 pub struct UserExt {
     #[from_request(via(Extension))]
     local_user_view: LocalUserView,
+    /// Set if the request was authenticated with an API token instead of login
+    #[from_request(via(Extension))]
+    api_token: Option<ApiToken>,
 }
 
 #[derive(FromRequestParts)]
@@ -185,6 +319,12 @@ impl UserExt {
     pub fn inner(self) -> LocalUserView {
         self.local_user_view
     }
+
+    /// Whether edits should be marked as bot edits, because they are made with an API token
+    /// which has this option enabled.
+    pub fn bot_edits(&self) -> bool {
+        self.api_token.as_ref().is_some_and(|t| t.bot_edits)
+    }
 }
 impl UserExtOpt {
     pub fn inner(self) -> Option<LocalUserView> {
@@ -0,0 +1,29 @@
+use activitypub_federation::config::Data;
+use axum::{Json, extract::Query};
+use axum_macros::debug_handler;
+use ibis_api_client::modlog::ListModlogParams;
+use ibis_database::{
+    common::{
+        modlog::{Modlog, ModlogView},
+        pagination::PaginatedResponse,
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+
+/// Public log of moderation actions, newest first.
+#[debug_handler]
+pub(crate) async fn list_modlog(
+    Query(params): Query<ListModlogParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<PaginatedResponse<ModlogView>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Modlog::list(
+        params.moderator_id,
+        params.article_id,
+        params.action,
+        params.cursor,
+        limit,
+        &context,
+    )?))
+}
@@ -0,0 +1,106 @@
+use crate::api::{UserExt, check_can_moderate, empty_to_none};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{Form, Json, extract::Query};
+use axum_macros::debug_handler;
+use ibis_api_client::report::{CreateReportParams, ListReportsParams, ResolveReportParams};
+use ibis_database::{
+    common::{
+        article::{Article, Edit},
+        comment::Comment,
+        modlog::{ModAction, Modlog},
+        pagination::PaginatedResponse,
+        report::{Report, ReportStatus, ReportView},
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use ibis_federate::{activities::flag::Flag, validate::validate_not_empty};
+
+/// Report an article, edit or comment to the moderators. If the article is hosted on another
+/// instance, the report is also sent there.
+#[debug_handler]
+pub(crate) async fn create_report(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateReportParams>,
+) -> BackendResult<Json<Report>> {
+    validate_not_empty(&params.reason)?;
+    let article = Article::read(params.article_id, &context)?;
+    let edit = params
+        .edit_id
+        .map(|id| Edit::read_from_id(id, &context))
+        .transpose()?;
+    let comment = params
+        .comment_id
+        .map(|id| Comment::read(id, &context))
+        .transpose()?;
+    if edit.is_some() && comment.is_some() {
+        return Err(anyhow!("Cannot report an edit and a comment at the same time").into());
+    }
+    if edit.as_ref().is_some_and(|e| e.article_id != article.id)
+        || comment.as_ref().is_some_and(|c| c.article_id != article.id)
+    {
+        return Err(anyhow!("Invalid article_id/edit_id/comment_id combination").into());
+    }
+
+    let report = Flag::submit(
+        &article,
+        edit.as_ref(),
+        comment.as_ref(),
+        params.reason.trim().to_string(),
+        user.inner().person.into(),
+        &context,
+    )
+    .await?;
+    Ok(Json(report))
+}
+
+/// Reports which moderators need to handle, oldest first.
+#[debug_handler]
+pub(crate) async fn list_reports(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Query(params): Query<ListReportsParams>,
+) -> BackendResult<Json<PaginatedResponse<ReportView>>> {
+    check_can_moderate(&user, &context)?;
+    let status = params.status.unwrap_or_default();
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Report::list(status, params.cursor, limit, &context)?))
+}
+
+#[debug_handler]
+pub(crate) async fn resolve_report(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<ResolveReportParams>,
+) -> BackendResult<Json<Report>> {
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    let report = Report::read(params.report_id, &context)?;
+    if report.status != ReportStatus::Open {
+        return Err(anyhow!("Report was already handled").into());
+    }
+    let (status, action) = if params.dismiss {
+        (ReportStatus::Dismissed, ModAction::DismissReport)
+    } else {
+        (ReportStatus::Resolved, ModAction::ResolveReport)
+    };
+    let report = Report::update_status(report.id, status, &context)?;
+    // The modlog is public, so it references the author of the reported content and not the
+    // reporter
+    let target_person = match (report.edit_id, report.comment_id) {
+        (Some(edit_id), _) => Some(Edit::read_from_id(edit_id, &context)?.creator_id),
+        (_, Some(comment_id)) => Some(Comment::read(comment_id, &context)?.creator_id),
+        _ => None,
+    };
+    Modlog::log(
+        action,
+        user.person.id,
+        Some(report.article_id),
+        target_person,
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(report))
+}
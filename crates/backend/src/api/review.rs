@@ -0,0 +1,138 @@
+use super::{UserExt, check_can_moderate, empty_to_none};
+use activitypub_federation::{config::Data, traits::Object};
+use anyhow::anyhow;
+use axum::{Form, Json, extract::Query};
+use axum_macros::debug_handler;
+use diffy::{Patch, apply};
+use ibis_api_client::article::{
+    ListEditsAwaitingReviewParams,
+    RequireReviewParams,
+    ReviewEditParams,
+};
+use ibis_database::{
+    common::{
+        article::{Article, Conflict, Edit, EditVersion, EditView},
+        modlog::{ModAction, Modlog},
+        pagination::PaginatedResponse,
+        user::Person,
+    },
+    error::BackendResult,
+    impls::{IbisContext, conflict::DbConflictForm, notifications::Notification},
+};
+use ibis_federate::{
+    activities::{reject::RejectEdit, submit_approved_edit},
+    objects::{edit::EditWrapper, user::PersonWrapper},
+};
+
+/// List edits to local articles which are waiting for approval, oldest first.
+#[debug_handler]
+pub(crate) async fn list_edits_awaiting_review(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Query(params): Query<ListEditsAwaitingReviewParams>,
+) -> BackendResult<Json<PaginatedResponse<EditView>>> {
+    check_can_moderate(&user, &context)?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Edit::list_awaiting_review(
+        params.cursor,
+        limit,
+        &context,
+    )?))
+}
+
+/// Approve or reject an edit from the review queue. Approved edits are applied to the article
+/// and federated. Rejected edits are deleted, and returned to the author as conflict so that they
+/// can be changed and submitted again.
+#[debug_handler]
+pub(crate) async fn review_edit(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<ReviewEditParams>,
+) -> BackendResult<Json<()>> {
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    let edit = Edit::read_from_id(params.edit_id, &context)?;
+    if !edit.awaiting_review {
+        return Err(anyhow!("Edit is not awaiting review").into());
+    }
+    let article = Article::read(edit.article_id, &context)?;
+    if params.approve {
+        if edit.previous_version_id != article.latest_edit_version(&context)? {
+            return Err(anyhow!("Edit is based on an outdated version, reject it instead").into());
+        }
+        let text = apply(&article.text, &Patch::from_str(&edit.diff)?)?;
+        let (edit, article) = Edit::approve(edit.id, &text, &context)?;
+        Notification::notify_edit(&edit, &context).await?;
+        submit_approved_edit(edit, article, &context).await?;
+    } else {
+        reject_edit(&edit, &context).await?;
+    }
+    Modlog::log(
+        if params.approve {
+            ModAction::ApproveEdit
+        } else {
+            ModAction::RejectEdit
+        },
+        user.person.id,
+        Some(article.id),
+        Some(edit.creator_id),
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(()))
+}
+
+/// Delete an edit from the review queue and return it to the author as conflict.
+pub(super) async fn reject_edit(edit: &Edit, context: &Data<IbisContext>) -> BackendResult<()> {
+    Edit::delete(edit.id, context)?;
+    let creator = Person::read(edit.creator_id, context)?;
+    if creator.local {
+        let form = DbConflictForm {
+            hash: edit.hash.clone(),
+            diff: edit.diff.clone(),
+            summary: edit.summary.clone(),
+            creator_id: edit.creator_id,
+            article_id: edit.article_id,
+            previous_version_id: edit.previous_version_id.clone(),
+        };
+        Conflict::create(&form, context)?;
+    } else {
+        let creator: PersonWrapper = creator.into();
+        let json = EditWrapper(edit.clone()).into_json(context).await?;
+        RejectEdit::send(json, creator, context).await?;
+    }
+    // article was created by this edit, so it has no content
+    if edit.previous_version_id == EditVersion::default() {
+        Article::update_removed(edit.article_id, true, context)?;
+    }
+    Ok(())
+}
+
+/// Change whether all edits to a local article need to be approved.
+#[debug_handler]
+pub(crate) async fn require_review(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<RequireReviewParams>,
+) -> BackendResult<Json<Article>> {
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    let article = Article::read(params.article_id, &context)?;
+    if !article.local {
+        return Err(anyhow!("Only local articles can require review").into());
+    }
+    let article = Article::update_review_edits(article.id, params.require_review, &context)?;
+    Modlog::log(
+        if params.require_review {
+            ModAction::EnableReview
+        } else {
+            ModAction::DisableReview
+        },
+        user.person.id,
+        Some(article.id),
+        None,
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(article))
+}
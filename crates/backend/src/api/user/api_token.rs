@@ -0,0 +1,53 @@
+use crate::api::UserExt;
+use activitypub_federation::config::Data;
+use axum::{Form, Json};
+use axum_macros::debug_handler;
+use ibis_api_client::user::{CreateApiTokenParams, DeleteApiTokenParams};
+use ibis_database::{
+    common::{
+        SuccessResponse,
+        api_token::{ApiToken, ApiTokenScope, CreateApiTokenResponse},
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use ibis_federate::validate::validate_not_empty;
+
+#[debug_handler]
+pub(crate) async fn list_api_tokens(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<ApiToken>>> {
+    Ok(Json(ApiToken::list(user.local_user.id, &context)?))
+}
+
+/// Create a token for bots and scripts. The returned token can't be read again later.
+#[debug_handler]
+pub(crate) async fn create_api_token(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateApiTokenParams>,
+) -> BackendResult<Json<CreateApiTokenResponse>> {
+    let name = params.name.trim().to_string();
+    validate_not_empty(&name)?;
+    let mut scopes = vec![ApiTokenScope::Read];
+    if params.edit_articles {
+        scopes.push(ApiTokenScope::EditArticles);
+    }
+    if params.comment {
+        scopes.push(ApiTokenScope::Comment);
+    }
+    let (api_token, token) =
+        ApiToken::create(user.local_user.id, name, scopes, params.bot_edits, &context)?;
+    Ok(Json(CreateApiTokenResponse { api_token, token }))
+}
+
+#[debug_handler]
+pub(crate) async fn delete_api_token(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteApiTokenParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    ApiToken::delete(params.id, user.local_user.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,187 @@
+use crate::{
+    api::{UserExt, check_can_moderate, empty_to_none, review::reject_edit},
+    utils::generate_article_version_from_snapshot,
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{Form, Json, extract::Query};
+use axum_macros::debug_handler;
+use chrono::{DateTime, Days, Utc};
+use diffy::merge;
+use ibis_api_client::user::{BanPersonParams, ListBansParams, UnbanPersonParams};
+use ibis_database::{
+    common::{
+        article::{Article, Edit},
+        ban::{PersonBan, PersonBanView},
+        comment::Comment,
+        modlog::{ModAction, Modlog},
+        pagination::PaginatedResponse,
+        user::{LocalUser, LocalUserView, Person},
+    },
+    error::BackendResult,
+    impls::{IbisContext, ban::PersonBanInsertForm, comment::DbCommentUpdateForm},
+};
+use ibis_federate::{
+    activities::{
+        article::remove_article::RemoveArticle,
+        comment::delete_comment::DeleteComment,
+        submit_article_update,
+    },
+    objects::comment::CommentWrapper,
+    validate::validate_not_empty,
+};
+
+/// Ban a local or remote user. Local users are logged out and cannot login again until the ban
+/// expires, activities from remote users are rejected.
+#[debug_handler]
+pub(crate) async fn ban_person(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<BanPersonParams>,
+) -> BackendResult<Json<PersonBan>> {
+    check_can_moderate(&user, &context)?;
+    validate_not_empty(&params.reason)?;
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Cannot ban yourself").into());
+    }
+    if params.expires.is_some_and(|e| e <= Utc::now()) {
+        return Err(anyhow!("Ban expiry must be in the future").into());
+    }
+    let target = Person::read(params.person_id, &context)?;
+    let role = LocalUser::read_role(target.id, &context)?.unwrap_or_default();
+    if role >= user.local_user.role {
+        return Err(anyhow!("Cannot ban a user with the same or higher role").into());
+    }
+
+    let form = PersonBanInsertForm {
+        person_id: target.id,
+        moderator_id: user.person.id,
+        reason: params.reason.trim().to_string(),
+        expires: params.expires,
+    };
+    let ban = PersonBan::create(&form, &context)?;
+    Modlog::log(
+        ModAction::BanUser,
+        user.person.id,
+        None,
+        Some(target.id),
+        Some(ban.reason.clone()),
+        &context,
+    )?;
+
+    if let Some(days) = params.remove_content_days {
+        let since = Utc::now()
+            .checked_sub_days(Days::new(days.into()))
+            .ok_or(anyhow!("Invalid number of days"))?;
+        remove_content(&target, since, &user, &context).await?;
+    }
+    Ok(Json(ban))
+}
+
+#[debug_handler]
+pub(crate) async fn unban_person(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<UnbanPersonParams>,
+) -> BackendResult<Json<()>> {
+    check_can_moderate(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    PersonBan::unban(params.person_id, &context)?;
+    Modlog::log(
+        ModAction::UnbanUser,
+        user.person.id,
+        None,
+        Some(params.person_id),
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(()))
+}
+
+#[debug_handler]
+pub(crate) async fn list_bans(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Query(params): Query<ListBansParams>,
+) -> BackendResult<Json<PaginatedResponse<PersonBanView>>> {
+    check_can_moderate(&user, &context)?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(PersonBan::list_active(
+        params.cursor,
+        limit,
+        &context,
+    )?))
+}
+
+/// Delete comments of the user published after `since`, revert their edits to local articles and
+/// reject their edits which are waiting for review. Articles created by the user are removed if
+/// nobody else edited them.
+///
+/// Each edit is undone with a three-way merge, starting with the newest one, so that later changes
+/// by other users are kept. Edits which conflict with later changes can't be reverted this way and
+/// are left in place, as is the edit which created an article that others have edited since.
+async fn remove_content(
+    target: &Person,
+    since: DateTime<Utc>,
+    moderator: &LocalUserView,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    for comment in Comment::list_for_creator_since(target.id, since, context)? {
+        let form = DbCommentUpdateForm {
+            deleted: Some(true),
+            updated: Some(Utc::now()),
+            ..Default::default()
+        };
+        let comment = Comment::update(form, comment.id, context)?;
+        // only the author can federate deletion of remote comments
+        if target.local {
+            let comment: CommentWrapper = comment.comment.into();
+            DeleteComment::send(&comment, context).await?;
+        }
+    }
+
+    for edit in Edit::list_awaiting_review_for_creator(target.id, context)? {
+        reject_edit(&edit, context).await?;
+    }
+
+    let summary = format!("Revert edits by banned user {}", target.title());
+    for article_id in Edit::list_articles_for_creator_since(target.id, since, context)? {
+        let Ok(article) = Article::read(article_id, context) else {
+            continue;
+        };
+        let edits = Edit::list_for_article(article.id, context)?;
+        let is_reverted = |e: &Edit| e.creator_id == target.id && e.published >= since;
+        if edits.iter().all(is_reverted) {
+            let article = Article::update_removed(article.id, true, context)?;
+            let actor = moderator.person.ap_id.clone().into();
+            RemoveArticle::send(actor, article.into(), context).await?;
+            continue;
+        }
+        let mut text = article.text.clone();
+        for (i, edit) in edits.iter().enumerate().skip(1).rev() {
+            if !is_reverted(edit) {
+                continue;
+            }
+            let before =
+                generate_article_version_from_snapshot(&edits, &edits[i - 1].hash, context)?;
+            let after = generate_article_version_from_snapshot(&edits, &edit.hash, context)?;
+            if let Ok(merged) = merge(&after, &text, &before) {
+                text = merged;
+            }
+        }
+        if text != article.text {
+            submit_article_update(
+                text,
+                summary.clone(),
+                article.latest_edit_version(context)?,
+                &article,
+                moderator.person.clone().into(),
+                false,
+                false,
+                context,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
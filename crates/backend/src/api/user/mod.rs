@@ -1,20 +1,28 @@
-use super::{UserExt, empty_to_none};
+use super::{UserExt, check_is_admin, empty_to_none};
 use crate::api::UserExtOpt;
-use activitypub_federation::config::Data;
+use activitypub_federation::config::{Data, FederationConfig};
 use anyhow::anyhow;
-use axum::{Form, Json, extract::Query};
+use axum::{
+    Extension,
+    Form,
+    Json,
+    extract::{ConnectInfo, FromRequestParts, Query},
+};
 use axum_extra::extract::cookie::{Cookie, CookieJar, Expiration, SameSite};
 use axum_macros::debug_handler;
 use bcrypt::verify;
 use chrono::Utc;
+use http::{header::USER_AGENT, request::Parts};
 use ibis_api_client::{
-    notifications::MarkAsReadParams,
+    notifications::{ListNotificationsParams, MarkAsReadParams},
     user::{
         ChangePasswordAfterReset,
         ChangePasswordParams,
         GetUserParams,
         LoginUserParams,
         PasswordReset,
+        RevokeSessionParams,
+        SetUserRoleParams,
         UpdateUserParams,
         VerifyEmailParams,
     },
@@ -23,9 +31,15 @@ use ibis_database::{
     common::{
         AUTH_COOKIE,
         SuccessResponse,
+        api_token::ApiToken,
+        ban::PersonBan,
         instance::InstanceFollow,
+        modlog::{ModAction, Modlog},
         notifications::ApiNotification,
-        user::{LocalUser, LocalUserView, Person},
+        pagination::PaginatedResponse,
+        session::{ListSessionsResponse, LoginSession},
+        totp::TOTP_REQUIRED_ERROR,
+        user::{LocalUser, LocalUserView, Person, PersonView},
     },
     email::{
         reset_password::PasswordResetRequest,
@@ -36,6 +50,7 @@ use ibis_database::{
         IbisContext,
         notifications::Notification,
         read_jwt_secret,
+        session::LoginSessionForm,
         user::{LocalUserUpdateForm, LocalUserViewQuery, PersonUpdateForm},
     },
 };
@@ -52,10 +67,13 @@ use jsonwebtoken::{
 use log::warn;
 use register::validate_new_password;
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use std::{convert::Infallible, net::SocketAddr, ops::Deref};
 use time::{Duration, OffsetDateTime};
 
+pub mod api_token;
+pub mod ban;
 pub mod register;
+pub mod totp;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
@@ -67,17 +85,63 @@ struct Claims {
     pub iat: i64,
     /// Expiration time
     pub exp: u64,
+    /// login_session.token_id
+    pub jti: String,
 }
 
-pub async fn validate(jwt: &str, context: &IbisContext) -> BackendResult<LocalUserView> {
+/// Check that the login token is signed and that its session was not revoked.
+pub async fn validate(
+    jwt: &str,
+    context: &IbisContext,
+) -> BackendResult<(LocalUserView, LoginSession)> {
     let validation = Validation::default();
     let secret = read_jwt_secret(context)?;
     let key = DecodingKey::from_secret(secret.as_bytes());
     let claims = decode::<Claims>(jwt, &key, &validation)?;
-    LocalUserView::read(
-        LocalUserViewQuery::LocalNameOrEmail(&claims.claims.sub),
-        context,
-    )
+    let session = LoginSession::validate(&claims.claims.jti, context)?;
+    let user = LocalUserView::read(LocalUserViewQuery::Id(session.local_user_id), context)?;
+    // banned users are treated as logged out
+    PersonBan::check_not_banned(user.person.id, context)?;
+    Ok((user, session))
+}
+
+/// Client details which are stored with a new login session, so that the user can recognize
+/// it later.
+pub(crate) struct ClientInfo {
+    user_agent: Option<String>,
+    ip: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|h| h.to_str().ok())
+                .map(ToString::to_string)
+        };
+        // use the address of the client if behind a reverse proxy
+        let trusted_proxy = parts
+            .extensions
+            .get::<FederationConfig<IbisContext>>()
+            .is_some_and(|c| c.conf.trusted_proxy);
+        let forwarded = header("x-forwarded-for")
+            .filter(|_| trusted_proxy)
+            .and_then(|f| f.split(',').next().map(|f| f.trim().to_string()));
+        let ip = forwarded.or_else(|| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|c| c.0.ip().to_string())
+        });
+        Ok(ClientInfo {
+            user_agent: header(USER_AGENT.as_str()),
+            ip,
+        })
+    }
 }
 
 fn validate_password(user: &LocalUserView, password: &str) -> BackendResult<()> {
@@ -96,6 +160,7 @@ fn validate_password(user: &LocalUserView, password: &str) -> BackendResult<()>
 #[debug_handler]
 pub(crate) async fn login_user(
     context: Data<IbisContext>,
+    client: ClientInfo,
     jar: CookieJar,
     Form(params): Form<LoginUserParams>,
 ) -> BackendResult<(CookieJar, Json<LocalUserView>)> {
@@ -109,20 +174,37 @@ pub(crate) async fn login_user(
         return Err(anyhow!("Verify your email address to login").into());
     }
     validate_password(&user, &params.password)?;
-    let jar = add_login_cookie(&user.person, jar, &context)?;
+    if user.local_user.totp_enabled {
+        let code = params.totp_code.filter(|c| !c.trim().is_empty());
+        let Some(code) = code else {
+            return Err(anyhow!(TOTP_REQUIRED_ERROR).into());
+        };
+        LocalUser::totp_check(&user, &code, &context)?;
+    }
+    let jar = add_login_cookie(&user, client, jar, &context)?;
     Ok((jar, Json(user)))
 }
 
+/// Start a new login session and set the login token as cookie.
 pub(crate) fn add_login_cookie(
-    person: &Person,
+    user: &LocalUserView,
+    client: ClientInfo,
     jar: CookieJar,
     context: &Data<IbisContext>,
 ) -> BackendResult<CookieJar> {
+    PersonBan::check_not_banned(user.person.id, context)?;
+    let form = LoginSessionForm {
+        local_user_id: user.local_user.id,
+        user_agent: client.user_agent,
+        ip: client.ip,
+    };
+    let session = LoginSession::create(&form, context)?;
     let claims = Claims {
-        sub: person.username.clone(),
+        sub: user.person.username.clone(),
         iss: context.conf.domain.clone(),
         iat: Utc::now().timestamp(),
         exp: get_current_timestamp() + 60 * 60 * 24 * 365,
+        jti: session.token_id.to_string(),
     };
 
     let secret = read_jwt_secret(context)?;
@@ -155,24 +237,81 @@ pub fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static
 #[debug_handler]
 pub(crate) async fn logout_user(
     context: Data<IbisContext>,
+    session: Option<Extension<LoginSession>>,
     jar: CookieJar,
 ) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    if let Some(Extension(session)) = session {
+        LoginSession::delete(session.id, session.local_user_id, &context)?;
+    }
     let jar = jar.remove(create_cookie(String::new(), &context));
     Ok((jar, Json(SuccessResponse::default())))
 }
 
+/// Devices where the user is logged in.
+#[debug_handler]
+pub(crate) async fn list_sessions(
+    user: UserExt,
+    session: Option<Extension<LoginSession>>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<ListSessionsResponse>> {
+    Ok(Json(ListSessionsResponse {
+        sessions: LoginSession::list(user.local_user.id, &context)?,
+        current: session.map(|s| s.id),
+    }))
+}
+
+/// Logout the user on another device, or on this one.
+#[debug_handler]
+pub(crate) async fn revoke_session(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RevokeSessionParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    LoginSession::delete(params.id, user.local_user.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
 #[debug_handler]
 pub(crate) async fn get_user(
     params: Query<GetUserParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Person>> {
-    Ok(Json(Person::read_from_name(
+) -> BackendResult<Json<PersonView>> {
+    Ok(Json(Person::read_view_from_name(
         &params.name,
         &params.domain,
         &context,
     )?))
 }
 
+/// Assign a role to a local user. Admins can't change their own role, so that there is always
+/// at least one admin.
+#[debug_handler]
+pub(crate) async fn set_user_role(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<SetUserRoleParams>,
+) -> BackendResult<Json<LocalUser>> {
+    check_is_admin(&user, &context)?;
+    empty_to_none(&mut params.reason);
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Cannot change your own role").into());
+    }
+    let person = Person::read(params.person_id, &context)?;
+    if !person.local {
+        return Err(anyhow!("Roles can only be assigned to local users").into());
+    }
+    let local_user = LocalUser::update_role(person.id, params.role, &context)?;
+    Modlog::log(
+        ModAction::SetRole,
+        user.person.id,
+        None,
+        Some(person.id),
+        params.reason,
+        &context,
+    )?;
+    Ok(Json(local_user))
+}
+
 #[debug_handler]
 pub(crate) async fn get_user_follows(
     user: UserExt,
@@ -213,9 +352,16 @@ pub(crate) async fn update_user_profile(
 #[debug_handler]
 pub(crate) async fn list_notifications(
     user: UserExt,
+    Query(params): Query<ListNotificationsParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<ApiNotification>>> {
-    Ok(Json(Notification::list(&user, &context)?))
+) -> BackendResult<Json<PaginatedResponse<ApiNotification>>> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Notification::list(
+        &user,
+        params.cursor,
+        limit,
+        &context,
+    )?))
 }
 
 #[debug_handler]
@@ -249,15 +395,19 @@ pub(crate) async fn verify_email(
     Ok(Json(SuccessResponse::default()))
 }
 
+/// Change the password, logout all other sessions of the user and revoke all API tokens.
 #[debug_handler]
 pub(crate) async fn change_password(
     user: UserExt,
+    session: Option<Extension<LoginSession>>,
     context: Data<IbisContext>,
     Form(params): Form<ChangePasswordParams>,
 ) -> BackendResult<Json<SuccessResponse>> {
     validate_password(&user, &params.old_password)?;
     validate_new_password(&params.new_password, &params.confirm_new_password)?;
     LocalUser::update_password(params.new_password, user.local_user.id, &context)?;
+    LoginSession::delete_all(user.local_user.id, session.map(|s| s.id), &context)?;
+    ApiToken::delete_all(user.local_user.id, &context)?;
     Ok(Json(SuccessResponse::default()))
 }
 
@@ -285,6 +435,9 @@ pub async fn change_password_after_reset(
     validate_new_password(&params.password, &params.confirm_password)?;
 
     LocalUser::update_password(params.password, local_user_id, &context)?;
+    // the password may have been changed because the account was compromised
+    LoginSession::delete_all(local_user_id, None, &context)?;
+    ApiToken::delete_all(local_user_id, &context)?;
 
     Ok(Json(SuccessResponse::default()))
 }
@@ -1,4 +1,7 @@
-use crate::api::{empty_to_none, user::add_login_cookie};
+use crate::api::{
+    empty_to_none,
+    user::{ClientInfo, add_login_cookie, totp::start_totp_login},
+};
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
 use axum::{Form, Json};
@@ -11,7 +14,7 @@ use ibis_api_client::user::{
     RegistrationResponse,
 };
 use ibis_database::{
-    common::user::{LocalUser, LocalUserView},
+    common::user::{LocalUser, LocalUserView, Role},
     config::OAuthProvider,
     email::verification::send_verification_email,
     error::{BackendError, BackendResult},
@@ -29,6 +32,7 @@ type RegisterReturnType = BackendResult<(CookieJar, Json<RegistrationResponse>)>
 #[debug_handler]
 pub async fn register_user(
     context: Data<IbisContext>,
+    client: ClientInfo,
     jar: CookieJar,
     Form(mut params): Form<RegisterUserParams>,
 ) -> RegisterReturnType {
@@ -48,7 +52,7 @@ pub async fn register_user(
     let user = LocalUserView::create(
         params.username,
         Some(params.password),
-        false,
+        Role::User,
         params.email.clone(),
         &context,
     )?;
@@ -56,12 +60,19 @@ pub async fn register_user(
         send_verification_email(&user.local_user, email, &context).await?;
     }
 
-    register_return(user, jar, context.conf.options.email_required, &context)
+    register_return(
+        user,
+        client,
+        jar,
+        context.conf.options.email_required,
+        &context,
+    )
 }
 
 #[debug_handler]
 pub async fn authenticate_with_oauth(
     context: Data<IbisContext>,
+    client: ClientInfo,
     jar: CookieJar,
     Form(params): Form<AuthenticateWithOauth>,
 ) -> RegisterReturnType {
@@ -141,7 +152,7 @@ pub async fn authenticate_with_oauth(
                 .ok_or(anyhow!("Username is required to register new account"))?;
 
             check_new_user(&username, Some(&email), &context)?;
-            let user = LocalUserView::create(username, None, false, Some(email), &context)?;
+            let user = LocalUserView::create(username, None, Role::User, Some(email), &context)?;
 
             // Create the oauth account
             let oauth_account_form = OAuthAccountInsertForm {
@@ -155,8 +166,21 @@ pub async fn authenticate_with_oauth(
         }
     };
 
+    // the oauth provider doesn't replace the second login step
+    if user.local_user.totp_enabled {
+        let totp_token = start_totp_login(&user);
+        return Ok((
+            jar,
+            Json(RegistrationResponse {
+                user,
+                email_verification_required: false,
+                totp_token: Some(totp_token),
+            }),
+        ));
+    }
+
     // dont require any email validation for oauth
-    register_return(user, jar, false, &context)
+    register_return(user, client, jar, false, &context)
 }
 
 /// Request an Access Token from the OAUTH provider
@@ -226,12 +250,13 @@ fn check_new_user(username: &str, email: Option<&str>, context: &IbisContext) ->
 
 fn register_return(
     user: LocalUserView,
+    client: ClientInfo,
     mut jar: CookieJar,
     email_verification_required: bool,
     context: &Data<IbisContext>,
 ) -> RegisterReturnType {
     if !email_verification_required {
-        jar = add_login_cookie(&user.person, jar, context)?;
+        jar = add_login_cookie(&user, client, jar, context)?;
     }
 
     Ok((
@@ -239,6 +264,7 @@ fn register_return(
         Json(RegistrationResponse {
             user,
             email_verification_required,
+            totp_token: None,
         }),
     ))
 }
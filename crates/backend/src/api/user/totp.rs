@@ -0,0 +1,93 @@
+use super::{ClientInfo, add_login_cookie};
+use crate::api::UserExt;
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{Form, Json};
+use axum_extra::extract::CookieJar;
+use axum_macros::debug_handler;
+use ibis_api_client::user::{DisableTotpParams, EnableTotpParams, LoginTotpParams};
+use ibis_database::{
+    common::{
+        SuccessResponse,
+        newtypes::LocalUserId,
+        totp::{TotpEnableResponse, TotpSetupResponse},
+        user::{LocalUser, LocalUserView},
+    },
+    error::BackendResult,
+    impls::{IbisContext, user::LocalUserViewQuery},
+};
+use moka::sync::Cache;
+use std::{sync::LazyLock, time::Duration};
+use uuid::Uuid;
+
+/// Logins which passed the first step, but still need a two-factor code. Tokens expire after
+/// a few minutes.
+static PENDING_LOGINS: LazyLock<Cache<String, LocalUserId>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(300))
+        .build()
+});
+
+/// Remember that the user passed the first login step, and return a token to finish login with
+/// [`login_totp`].
+pub(crate) fn start_totp_login(user: &LocalUserView) -> String {
+    let token = Uuid::new_v4().simple().to_string();
+    PENDING_LOGINS.insert(token.clone(), user.local_user.id);
+    token
+}
+
+/// Second login step after OAuth login, which checks the code in the same way as password
+/// login.
+#[debug_handler]
+pub(crate) async fn login_totp(
+    context: Data<IbisContext>,
+    client: ClientInfo,
+    jar: CookieJar,
+    Form(params): Form<LoginTotpParams>,
+) -> BackendResult<(CookieJar, Json<LocalUserView>)> {
+    let local_user_id = PENDING_LOGINS
+        .get(&params.token)
+        .ok_or(anyhow!("Login expired, please try again"))?;
+    let user = LocalUserView::read(LocalUserViewQuery::Id(local_user_id), &context)?;
+    LocalUser::totp_check(&user, &params.code, &context)?;
+    PENDING_LOGINS.invalidate(&params.token);
+    let jar = add_login_cookie(&user, client, jar, &context)?;
+    Ok((jar, Json(user)))
+}
+
+/// Generate a secret for the authenticator app. Calling this again replaces the secret, as long
+/// as two-factor authentication is not enabled yet.
+#[debug_handler]
+pub(crate) async fn setup_totp(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<TotpSetupResponse>> {
+    Ok(Json(LocalUser::totp_setup(&user, &context)?))
+}
+
+#[debug_handler]
+pub(crate) async fn enable_totp(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<EnableTotpParams>,
+) -> BackendResult<Json<TotpEnableResponse>> {
+    let recovery_codes = LocalUser::totp_enable(&user, &params.code, &context)?;
+    Ok(Json(TotpEnableResponse { recovery_codes }))
+}
+
+/// Turn off two-factor authentication, which needs a valid code so that it can't be done with
+/// a stolen login token alone.
+#[debug_handler]
+pub(crate) async fn disable_totp(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<DisableTotpParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    if !user.local_user.totp_enabled {
+        return Err(anyhow!("Two-factor authentication is not enabled").into());
+    }
+    LocalUser::totp_check(&user, &params.code, &context)?;
+    LocalUser::totp_disable(user.local_user.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
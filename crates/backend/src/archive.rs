@@ -0,0 +1,453 @@
+//! Export all local articles into a portable archive, and restore them on another server.
+//!
+//! The archive is a tar file with the following layout:
+//!
+//! - `manifest.json`: Format version and instance metadata
+//! - `persons.json`: Authors of all exported edits and comments
+//! - `articles/<Title>/article.json`: Article with its full edit history and comments
+//! - `articles/<Title>/text.md`: Current article text, for convenient reading
+//!
+//! Edits are stored with their original diff and hash, so that restored articles have identical
+//! `EditVersion` and ap_id values. This is required for federation to keep working, so an
+//! archive can only be imported on an instance with the same domain. User accounts and private
+//! keys are not included.
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use diffy::{Patch, apply};
+use ibis_database::{
+    DbUrl,
+    common::{
+        article::{Article, Edit, EditVersion},
+        comment::Comment,
+        instance::Instance,
+        newtypes::{CommentId, PersonId},
+        user::Person,
+        utils::{extract_domain, http_protocol_str},
+    },
+    error::BackendResult,
+    impls::{
+        IbisContext,
+        article::DbArticleForm,
+        comment::DbCommentInsertForm,
+        edit::DbEditForm,
+        instance::DbInstanceUpdateForm,
+        user::PersonInsertForm,
+    },
+    utils::generate_keypair,
+};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
+use tar::{Archive, Builder, Header};
+
+/// Incremented on incompatible changes to the archive format.
+const ARCHIVE_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const PERSONS_FILE: &str = "persons.json";
+const ARTICLE_FILE: &str = "article.json";
+const TEXT_FILE: &str = "text.md";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchiveStats {
+    pub articles: usize,
+    pub edits: usize,
+    pub comments: usize,
+    pub skipped: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveManifest {
+    version: u32,
+    domain: String,
+    name: Option<String>,
+    topic: Option<String>,
+    exported: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivePerson {
+    ap_id: DbUrl,
+    username: String,
+    inbox_url: String,
+    public_key: String,
+    display_name: Option<String>,
+    bio: Option<String>,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveArticle {
+    title: String,
+    ap_id: DbUrl,
+    protected: bool,
+    removed: bool,
+    edits: Vec<ArchiveEdit>,
+    comments: Vec<ArchiveComment>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveEdit {
+    hash: EditVersion,
+    ap_id: DbUrl,
+    diff: String,
+    summary: String,
+    creator: DbUrl,
+    previous_version: EditVersion,
+    published: DateTime<Utc>,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveComment {
+    ap_id: DbUrl,
+    creator: DbUrl,
+    parent: Option<DbUrl>,
+    content: String,
+    deleted: bool,
+    published: DateTime<Utc>,
+    updated: Option<DateTime<Utc>>,
+}
+
+/// Write all local articles, including removed ones, to a new archive at `path`.
+pub fn export_archive(path: &Path, context: &IbisContext) -> BackendResult<ArchiveStats> {
+    let instance = Instance::read_local(context)?;
+    let mut stats = ArchiveStats::default();
+    let mut builder = Builder::new(File::create(path)?);
+    let manifest = ArchiveManifest {
+        version: ARCHIVE_VERSION,
+        domain: instance.domain,
+        name: instance.name,
+        topic: instance.topic,
+        exported: Utc::now(),
+    };
+    append_file(
+        &mut builder,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+
+    let mut persons = BTreeMap::new();
+    let mut read_person = |id: PersonId| -> BackendResult<DbUrl> {
+        let person = Person::read(id, context)?;
+        let ap_id = person.ap_id.clone();
+        persons.entry(ap_id.to_string()).or_insert(person);
+        Ok(ap_id)
+    };
+    for article in Article::read_all(Some(true), None, true, None, None, context)?.items {
+        let edits = Edit::list_for_article(article.id, context)?
+            .into_iter()
+            .map(|e| {
+                Ok(ArchiveEdit {
+                    creator: read_person(e.creator_id)?,
+                    hash: e.hash,
+                    ap_id: e.ap_id,
+                    diff: e.diff,
+                    summary: e.summary,
+                    previous_version: e.previous_version_id,
+                    published: e.published,
+                    bot: e.bot,
+                })
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+        // Oldest first, so that parent comments are restored before replies
+        let comments = Comment::read_for_article(article.id, None, None, context)?.items;
+        let comment_ap_ids: HashMap<CommentId, DbUrl> = comments
+            .iter()
+            .map(|c| (c.comment.id, c.comment.ap_id.clone()))
+            .collect();
+        let comments = comments
+            .into_iter()
+            .rev()
+            .map(|c| {
+                Ok(ArchiveComment {
+                    creator: read_person(c.comment.creator_id)?,
+                    parent: c
+                        .comment
+                        .parent_id
+                        .and_then(|id| comment_ap_ids.get(&id).cloned()),
+                    ap_id: c.comment.ap_id,
+                    content: c.comment.content,
+                    deleted: c.comment.deleted,
+                    published: c.comment.published,
+                    updated: c.comment.updated,
+                })
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+
+        stats.edits += edits.len();
+        stats.comments += comments.len();
+        stats.articles += 1;
+        let dir = format!("articles/{}", article.title.replace(' ', "_"));
+        let archive_article = ArchiveArticle {
+            title: article.title,
+            ap_id: article.ap_id,
+            protected: article.protected,
+            removed: article.removed,
+            edits,
+            comments,
+        };
+        append_file(
+            &mut builder,
+            &format!("{dir}/{ARTICLE_FILE}"),
+            &serde_json::to_vec_pretty(&archive_article)?,
+        )?;
+        append_file(
+            &mut builder,
+            &format!("{dir}/{TEXT_FILE}"),
+            article.text.as_bytes(),
+        )?;
+    }
+
+    let persons: Vec<_> = persons
+        .into_values()
+        .map(|p| ArchivePerson {
+            ap_id: p.ap_id,
+            username: p.username,
+            inbox_url: p.inbox_url,
+            public_key: p.public_key,
+            display_name: p.display_name,
+            bio: p.bio,
+            bot: p.bot,
+        })
+        .collect();
+    append_file(
+        &mut builder,
+        PERSONS_FILE,
+        &serde_json::to_vec_pretty(&persons)?,
+    )?;
+    builder.into_inner()?.flush()?;
+    Ok(stats)
+}
+
+fn append_file<W: Write>(builder: &mut Builder<W>, path: &str, data: &[u8]) -> BackendResult<()> {
+    let mut header = Header::new_gnu();
+    header.set_size(u64::try_from(data.len())?);
+    header.set_mode(0o644);
+    header.set_mtime(u64::try_from(Utc::now().timestamp())?);
+    header.set_cksum();
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+/// Restore articles from an archive which was created with [export_archive].
+///
+/// Articles which exist already are only updated if their edit history is the beginning of
+/// the archived history, as is the case for the default main page of a new instance. Then the
+/// missing edits are added. Otherwise the article is skipped.
+pub async fn import_archive(path: &Path, context: &IbisContext) -> BackendResult<ArchiveStats> {
+    let mut files = BTreeMap::new();
+    for entry in Archive::new(File::open(path)?).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        files.insert(name, content);
+    }
+    let read_file = |name: &str| {
+        files
+            .get(name)
+            .ok_or(anyhow!("Archive is missing file {name}"))
+    };
+
+    let manifest: ArchiveManifest = serde_json::from_str(read_file(MANIFEST_FILE)?)?;
+    if manifest.version != ARCHIVE_VERSION {
+        return Err(anyhow!("Unsupported archive version {}", manifest.version).into());
+    }
+    let instance = Instance::read_local(context)?;
+    if manifest.domain != instance.domain {
+        return Err(anyhow!(
+            "Archive was exported from {}, it can only be imported on an instance with the same domain",
+            manifest.domain
+        )
+        .into());
+    }
+    if manifest.name.is_some() || manifest.topic.is_some() {
+        let form = DbInstanceUpdateForm {
+            name: manifest.name,
+            topic: manifest.topic,
+            review_all_edits: None,
+            review_new_user_edits: None,
+            require_totp_for_moderators: None,
+        };
+        Instance::update(form, context)?;
+    }
+
+    let archive_persons: Vec<ArchivePerson> = serde_json::from_str(read_file(PERSONS_FILE)?)?;
+    let mut persons = HashMap::new();
+    for person in archive_persons {
+        let ap_id = person.ap_id.clone();
+        let id = match Person::read_from_ap_id(&ap_id, context) {
+            Ok(p) => p.id,
+            Err(_) => restore_person(person, &instance.domain, context)?.id,
+        };
+        persons.insert(ap_id, id);
+    }
+
+    let mut stats = ArchiveStats::default();
+    for (name, content) in &files {
+        let Some(dir) = name
+            .strip_prefix("articles/")
+            .and_then(|n| n.strip_suffix(&format!("/{ARTICLE_FILE}")))
+        else {
+            continue;
+        };
+        let article: ArchiveArticle = serde_json::from_str(content)?;
+        let text = read_file(&format!("articles/{dir}/{TEXT_FILE}"))?;
+        restore_article(article, text, &instance, &persons, &mut stats, context).await?;
+    }
+    Ok(stats)
+}
+
+/// Create an author who doesn't exist on this instance yet. Local persons get a new keypair,
+/// remote ones are refetched from their instance when needed.
+fn restore_person(
+    person: ArchivePerson,
+    domain: &str,
+    context: &IbisContext,
+) -> BackendResult<Person> {
+    let local = extract_domain(person.ap_id.inner()) == domain;
+    let (public_key, private_key, inbox_url) = if local {
+        let keypair = generate_keypair()?;
+        (
+            keypair.public_key,
+            Some(keypair.private_key),
+            format!("{}://{domain}/inbox", http_protocol_str()),
+        )
+    } else {
+        (person.public_key, None, person.inbox_url)
+    };
+    let form = PersonInsertForm {
+        username: person.username,
+        ap_id: person.ap_id,
+        inbox_url,
+        public_key,
+        private_key,
+        last_refreshed_at: DateTime::UNIX_EPOCH,
+        local,
+        display_name: person.display_name,
+        bio: person.bio,
+        bot: person.bot,
+    };
+    Person::create(&form, context)
+}
+
+async fn restore_article(
+    archived: ArchiveArticle,
+    text: &str,
+    instance: &Instance,
+    persons: &HashMap<DbUrl, PersonId>,
+    stats: &mut ArchiveStats,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let title = archived.title;
+    // Verify that the edit history is intact before writing anything
+    let mut generated = String::new();
+    for edit in &archived.edits {
+        if EditVersion::new(&edit.diff) != edit.hash {
+            return Err(anyhow!("Edit {} of {title} has invalid hash", edit.ap_id).into());
+        }
+        generated = apply(&generated, &Patch::from_str(&edit.diff)?)?;
+    }
+    if generated != text {
+        return Err(anyhow!("Edit history of {title} doesn't match article text").into());
+    }
+    let Some(last_edit) = archived.edits.last() else {
+        warn!("Skipping article {title} without edits");
+        stats.skipped += 1;
+        return Ok(());
+    };
+    let creator_id = |ap_id: &DbUrl| {
+        persons
+            .get(ap_id)
+            .copied()
+            .ok_or(anyhow!("Archive is missing person {ap_id}"))
+    };
+
+    let (article, existing_edits) = match Article::read_from_ap_id(&archived.ap_id, context) {
+        Ok(article) => {
+            let existing = Edit::list_for_article(article.id, context)?;
+            let is_prefix = article.local
+                && existing.len() <= archived.edits.len()
+                && existing
+                    .iter()
+                    .zip(&archived.edits)
+                    .all(|(a, b)| a.hash == b.hash);
+            if !is_prefix {
+                warn!("Skipping article {title} which exists with different edits");
+                stats.skipped += 1;
+                return Ok(());
+            }
+            (article, existing.len())
+        }
+        Err(_) => {
+            let form = DbArticleForm {
+                title: title.clone(),
+                text: String::new(),
+                ap_id: archived.ap_id,
+                instance_id: instance.id,
+                local: true,
+                protected: archived.protected,
+                updated: last_edit.published,
+                pending: false,
+            };
+            let creator_id = creator_id(&archived.edits[0].creator)?;
+            (Article::create(form, creator_id, context).await?, 0)
+        }
+    };
+
+    for edit in archived.edits.into_iter().skip(existing_edits) {
+        let form = DbEditForm {
+            creator_id: creator_id(&edit.creator)?,
+            hash: edit.hash,
+            ap_id: edit.ap_id,
+            diff: edit.diff,
+            summary: edit.summary,
+            article_id: article.id,
+            previous_version_id: edit.previous_version,
+            published: edit.published,
+            pending: false,
+            awaiting_review: false,
+            bot: edit.bot,
+        };
+        Edit::create_or_update(&form, false, context).await?;
+        stats.edits += 1;
+    }
+    Article::update_text(article.id, text, context)?;
+    Article::update_protected(article.id, archived.protected, context)?;
+    Article::update_removed(article.id, archived.removed, context)?;
+
+    let mut comments: HashMap<DbUrl, Comment> = HashMap::new();
+    for comment in archived.comments {
+        if let Ok(existing) = Comment::read_from_ap_id(&comment.ap_id, context) {
+            comments.insert(comment.ap_id, existing);
+            continue;
+        }
+        let parent = comment.parent.and_then(|p| comments.get(&p));
+        let form = DbCommentInsertForm {
+            creator_id: creator_id(&comment.creator)?,
+            article_id: article.id,
+            parent_id: parent.map(|p| p.id),
+            content: comment.content,
+            depth: parent.map(|p| p.depth + 1).unwrap_or_default(),
+            ap_id: Some(comment.ap_id.clone()),
+            local: extract_domain(comment.ap_id.inner()) == instance.domain,
+            deleted: comment.deleted,
+            published: comment.published,
+            updated: comment.updated,
+        };
+        let created = Comment::create_or_update(form, context).await?;
+        comments.insert(comment.ap_id, created);
+        stats.comments += 1;
+    }
+    info!("Restored article {title}");
+    stats.articles += 1;
+    Ok(())
+}
@@ -0,0 +1,257 @@
+//! Convert the edit history of local articles into a git repository, so that it can be
+//! explored with `git log`, `git blame` or `git diff`.
+//!
+//! Each edit becomes one commit which changes the file `<Title>.md`, with the edit author,
+//! timestamp and summary. The repository only consists of loose objects, which makes it possible
+//! to serve it with the dumb HTTP protocol, or to write it into a bare repository on disk.
+
+use diffy::{Patch, apply};
+use flate2::{Compression, write::ZlibEncoder};
+use ibis_database::{
+    common::{
+        article::{Article, Edit},
+        newtypes::{EditId, PersonId},
+        user::Person,
+        utils::extract_domain,
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{create_dir_all, write},
+    io::Write,
+    path::Path,
+};
+
+/// The only branch in generated repositories.
+pub const GIT_BRANCH: &str = "refs/heads/main";
+
+#[derive(Debug, Default)]
+pub struct GitRepository {
+    /// Zlib compressed loose objects, keyed by hex encoded object id.
+    pub objects: HashMap<String, Vec<u8>>,
+    /// Id of the latest commit, or `None` if there are no edits.
+    pub head: Option<String>,
+    /// Current text and blob id of each file
+    files: BTreeMap<String, (String, String)>,
+    authors: HashMap<PersonId, String>,
+    /// File name and edit of each commit, in order
+    commits: Vec<(String, EditId)>,
+}
+
+impl GitRepository {
+    /// Repository containing all local articles, with edits of all articles in chronological
+    /// order.
+    pub fn for_wiki(context: &IbisContext) -> BackendResult<Self> {
+        let mut repo = Self::default();
+        repo.update(None, context)?;
+        Ok(repo)
+    }
+
+    /// Repository containing only the history of a single article.
+    pub fn for_article(article: &Article, context: &IbisContext) -> BackendResult<Self> {
+        let mut repo = Self::default();
+        repo.update(Some(article), context)?;
+        Ok(repo)
+    }
+
+    /// Bring the repository up to date with the edits of `article`, or of the whole wiki if it
+    /// is `None`. If earlier commits are unchanged, only commits for new edits are added.
+    /// Otherwise, for example after an article was renamed or removed, the repository is
+    /// generated again from scratch.
+    pub fn update(
+        &mut self,
+        article: Option<&Article>,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let edits = match article {
+            Some(article) => {
+                let file = file_name(&article.title);
+                Edit::list_for_article(article.id, context)?
+                    .into_iter()
+                    .map(|e| (file.clone(), e))
+                    .collect()
+            }
+            None => {
+                let mut edits = vec![];
+                for article in
+                    Article::read_all(Some(true), None, false, None, None, context)?.items
+                {
+                    let file = file_name(&article.title);
+                    for edit in Edit::list_for_article(article.id, context)? {
+                        edits.push((file.clone(), edit));
+                    }
+                }
+                edits.sort_by_key(|(_, e)| (e.published, e.id.0));
+                edits
+            }
+        };
+        let unchanged = self.commits.len() <= edits.len()
+            && self
+                .commits
+                .iter()
+                .zip(&edits)
+                .all(|((file, id), (new_file, edit))| file == new_file && *id == edit.id);
+        if !unchanged {
+            *self = Self::default();
+        }
+        let skip = self.commits.len();
+        self.build(edits.into_iter().skip(skip), context)
+    }
+
+    fn build(
+        &mut self,
+        edits: impl Iterator<Item = (String, Edit)>,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        for (file, edit) in edits {
+            let previous = self
+                .files
+                .get(&file)
+                .map(|(t, _)| t.as_str())
+                .unwrap_or_default();
+            let text = apply(previous, &Patch::from_str(&edit.diff)?)?;
+            let blob = self.write_object("blob", text.as_bytes())?;
+            self.files.insert(file.clone(), (text, blob));
+            let tree = self.write_tree()?;
+
+            let author = match self.authors.get(&edit.creator_id) {
+                Some(author) => author.clone(),
+                None => {
+                    let author = git_author(&Person::read(edit.creator_id, context)?);
+                    self.authors.insert(edit.creator_id, author.clone());
+                    author
+                }
+            };
+            let mut commit = format!("tree {tree}\n");
+            if let Some(parent) = &self.head {
+                commit.push_str(&format!("parent {parent}\n"));
+            }
+            let time = format!("{} +0000", edit.published.timestamp());
+            let summary = edit.summary.trim();
+            let summary = if summary.is_empty() {
+                "Edit without summary"
+            } else {
+                summary
+            };
+            commit.push_str(&format!(
+                "author {author} {time}\ncommitter {author} {time}\n\n{summary}\n\nIbis-Edit: {}\n",
+                edit.ap_id
+            ));
+            self.head = Some(self.write_object("commit", commit.as_bytes())?);
+            self.commits.push((file, edit.id));
+        }
+        Ok(())
+    }
+
+    /// Store an object and return its id.
+    fn write_object(&mut self, kind: &str, content: &[u8]) -> BackendResult<String> {
+        let mut data = format!("{kind} {}\0", content.len()).into_bytes();
+        data.extend_from_slice(content);
+        let id = hex::encode(Sha1::digest(&data));
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder.write_all(&data)?;
+        self.objects.insert(id.clone(), encoder.finish()?);
+        Ok(id)
+    }
+
+    fn write_tree(&mut self) -> BackendResult<String> {
+        // Git sorts tree entries by bytes, same as BTreeMap
+        let mut content = vec![];
+        for (name, (_, blob)) in &self.files {
+            content.extend_from_slice(format!("100644 {name}\0").as_bytes());
+            content.extend_from_slice(&hex::decode(blob)?);
+        }
+        self.write_object("tree", &content)
+    }
+
+    /// Approximate memory usage in bytes.
+    pub fn size(&self) -> usize {
+        self.objects.values().map(Vec::len).sum::<usize>()
+            + self.files.values().map(|(t, _)| t.len()).sum::<usize>()
+    }
+
+    /// Content of `info/refs`, used by clients of the dumb HTTP protocol to find the branch.
+    pub fn info_refs(&self) -> String {
+        self.head
+            .as_ref()
+            .map(|head| format!("{head}\t{GIT_BRANCH}\n"))
+            .unwrap_or_default()
+    }
+
+    /// Content of `HEAD`.
+    pub fn head_ref() -> String {
+        format!("ref: {GIT_BRANCH}\n")
+    }
+
+    /// Write as bare repository, which can then be cloned with `git clone <path>`.
+    pub fn write_to_dir(&self, path: &Path) -> BackendResult<()> {
+        create_dir_all(path.join("refs/heads"))?;
+        create_dir_all(path.join("info"))?;
+        write(path.join("HEAD"), Self::head_ref())?;
+        write(
+            path.join("config"),
+            "[core]\n\trepositoryformatversion = 0\n\tbare = true\n",
+        )?;
+        write(path.join("info/refs"), self.info_refs())?;
+        if let Some(head) = &self.head {
+            write(path.join(GIT_BRANCH), format!("{head}\n"))?;
+        }
+        for (id, data) in &self.objects {
+            let dir = path.join("objects").join(&id[..2]);
+            create_dir_all(&dir)?;
+            write(dir.join(&id[2..]), data)?;
+        }
+        Ok(())
+    }
+}
+
+/// Name of the file for an article in the repository. Article titles may contain `/`, which
+/// is not allowed in tree entries, so it is percent encoded (together with `%` itself to keep
+/// names unique).
+fn file_name(title: &str) -> String {
+    let name = title
+        .replace('%', "%25")
+        .replace('/', "%2F")
+        .replace(' ', "_");
+    format!("{name}.md")
+}
+
+/// Author line in the form `Name <username@domain>`.
+fn git_author(person: &Person) -> String {
+    let name = person.display_name.as_ref().unwrap_or(&person.username);
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | '\n'))
+        .collect();
+    format!(
+        "{} <{}@{}>",
+        name.trim(),
+        person.username,
+        extract_domain(&person.ap_id.clone().into())
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_object_id() -> BackendResult<()> {
+        // Same as `echo hello | git hash-object --stdin`
+        let mut repo = GitRepository::default();
+        let id = repo.write_object("blob", b"hello\n")?;
+        assert_eq!("ce013625030ba8dba906f756967f9e9ca394464a", id);
+        assert!(repo.objects.contains_key(&id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!("Main_Page.md", file_name("Main Page"));
+        assert_eq!("AC%2FDC.md", file_name("AC/DC"));
+        assert_eq!("100%25_%2F_2.md", file_name("100% / 2"));
+    }
+}
@@ -1,18 +1,21 @@
 use activitypub_federation::config::FederationConfig;
 use ibis_database::{
-    common::instance::Instance,
+    common::{federation_list::FederationListEntry, instance::Instance, user::Person},
     config::IbisConfig,
     error::BackendResult,
     impls::IbisContext,
     scheduled_tasks,
 };
-use ibis_federate::VerifyUrlData;
+use ibis_federate::{VerifyUrlData, delivery::retry_failed_deliveries};
 use log::info;
 use server::{setup::setup, start_server};
 use std::{net::SocketAddr, thread};
 use tokio::sync::oneshot;
 
 pub mod api;
+pub mod archive;
+pub mod git;
+pub mod mediawiki;
 mod server;
 pub mod utils;
 
@@ -22,9 +25,11 @@ pub async fn start(
     notify_start: Option<oneshot::Sender<()>>,
 ) -> BackendResult<()> {
     let context = IbisContext::init(config, override_hostname.is_some())?;
+    FederationListEntry::import_from_config(&context.conf.federation, &context)?;
+    Person::mark_wikibot(&context)?;
     let data = FederationConfig::builder()
         .domain(context.conf.federation_domain())
-        .url_verifier(Box::new(VerifyUrlData(context.conf.clone())))
+        .url_verifier(Box::new(VerifyUrlData(context.clone())))
         .app_data(context.clone())
         .client(context.client.clone().into())
         .http_fetch_limit(1000)
@@ -41,6 +46,7 @@ pub async fn start(
     thread::spawn(move || {
         scheduled_tasks::start(db_pool);
     });
+    tokio::spawn(retry_failed_deliveries(data.to_request_data()));
 
     start_server(data, override_hostname, notify_start).await?;
 
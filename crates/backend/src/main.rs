@@ -1,13 +1,26 @@
+use anyhow::anyhow;
 use env_logger::Env;
-use ibis::start;
-use ibis_database::config::IbisConfig;
+use ibis::{
+    archive::{ArchiveStats, export_archive, import_archive},
+    git::GitRepository,
+    mediawiki::{MediaWikiImportOptions, import_mediawiki_dump},
+    start,
+};
+use ibis_database::{
+    common::article::Article,
+    config::IbisConfig,
+    error::BackendResult,
+    impls::IbisContext,
+};
+use std::path::PathBuf;
 
 #[tokio::main]
-pub async fn main() -> ibis_database::error::BackendResult<()> {
+pub async fn main() -> BackendResult<()> {
     // https://github.com/seanmonstar/reqwest/issues/2924
     let _ = rustls::crypto::ring::default_provider().install_default();
 
-    if std::env::args().collect::<Vec<_>>().get(1) == Some(&"--print-config".to_string()) {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--print-config") {
         println!("{}", doku::to_toml::<IbisConfig>());
         std::process::exit(0);
     }
@@ -18,6 +31,101 @@ pub async fn main() -> ibis_database::error::BackendResult<()> {
     .init();
 
     let ibis_config = IbisConfig::read()?;
-    start(ibis_config, None, None).await?;
+    match args.first().map(String::as_str) {
+        Some("import-mediawiki") => import_mediawiki(&args[1..], ibis_config).await,
+        Some("git-export") => git_export(&args[1..], ibis_config),
+        Some(command @ ("export" | "import")) => {
+            let path = args
+                .get(1)
+                .map(PathBuf::from)
+                .ok_or(anyhow!("Usage: ibis {command} <file>"))?;
+            let context = IbisContext::init(ibis_config, false)?;
+            let ArchiveStats {
+                articles,
+                edits,
+                comments,
+                skipped,
+            } = if command == "export" {
+                export_archive(&path, &context)?
+            } else {
+                import_archive(&path, &context).await?
+            };
+            println!(
+                "{command}: {articles} articles with {edits} edits and {comments} comments, skipped {skipped}"
+            );
+            Ok(())
+        }
+        _ => start(ibis_config, None, None).await,
+    }
+}
+
+/// Usage: `ibis import-mediawiki <file> [--all-revisions] [--user <username>]`
+async fn import_mediawiki(args: &[String], config: IbisConfig) -> BackendResult<()> {
+    let mut path = None;
+    let mut options = MediaWikiImportOptions {
+        all_revisions: false,
+        username: config.setup.admin_username.clone(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all-revisions" => options.all_revisions = true,
+            "--user" => {
+                options.username = args
+                    .next()
+                    .ok_or(anyhow!("Missing value for --user"))?
+                    .clone()
+            }
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+    let path = path.ok_or(anyhow!(
+        "Usage: ibis import-mediawiki <file> [--all-revisions] [--user <username>]"
+    ))?;
+
+    let context = IbisContext::init(config, false)?;
+    let stats = import_mediawiki_dump(&path, &options, &context).await?;
+    println!(
+        "Imported {} articles with {} edits and {} redirects, skipped {} pages",
+        stats.articles, stats.edits, stats.redirects, stats.skipped
+    );
+    Ok(())
+}
+
+/// Usage: `ibis git-export <directory> [--article <title>]`
+fn git_export(args: &[String], config: IbisConfig) -> BackendResult<()> {
+    let mut path = None;
+    let mut article = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--article" => {
+                article = Some(
+                    args.next()
+                        .ok_or(anyhow!("Missing value for --article"))?
+                        .clone(),
+                )
+            }
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+    let path = path.ok_or(anyhow!(
+        "Usage: ibis git-export <directory> [--article <title>]"
+    ))?;
+
+    let context = IbisContext::init(config, false)?;
+    let repo = match article {
+        Some(title) => {
+            let article = Article::read_view((&title, None), None, &context)?.article;
+            GitRepository::for_article(&article, &context)?
+        }
+        None => GitRepository::for_wiki(&context)?,
+    };
+    repo.write_to_dir(&path)?;
+    println!(
+        "Exported {} git objects to {}",
+        repo.objects.len(),
+        path.display()
+    );
     Ok(())
 }
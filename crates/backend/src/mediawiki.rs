@@ -0,0 +1,544 @@
+//! Import articles from a MediaWiki XML dump, as created by `dumpBackup.php` or downloaded
+//! from <https://dumps.wikimedia.org/> (`pages-articles.xml`).
+
+use crate::utils::generate_article_ap_id;
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use ibis_database::{
+    common::{
+        article::{Article, Edit},
+        instance::Instance,
+        user::Person,
+    },
+    error::BackendResult,
+    impls::{IbisContext, article::DbArticleForm, edit::DbEditForm},
+};
+use ibis_federate::validate::validate_article_title;
+use ibis_markdown::format_markdown;
+use log::{info, warn};
+use quick_xml::{
+    Reader,
+    escape::{escape, resolve_html5_entity, resolve_predefined_entity, unescape_with},
+    events::Event,
+};
+use std::{mem::take, path::Path};
+use wikipedia_article_transform::{ArticleFormat, WikiPage};
+
+/// Namespace of regular articles. Talk pages, templates, categories etc are not imported.
+const MAIN_NAMESPACE: &str = "0";
+
+/// Namespaces of links which are removed entirely, instead of being converted to text.
+const REMOVED_LINK_NAMESPACES: [&str; 4] = ["file", "image", "media", "category"];
+
+/// Characters which would get lost in the HTML conversion, so they are replaced with
+/// placeholders from the Unicode private use area until the markdown is generated.
+const PLACEHOLDERS: [(char, char); 3] = [('&', '\u{e000}'), ('<', '\u{e001}'), ('>', '\u{e002}')];
+
+#[derive(Debug, Clone)]
+pub struct MediaWikiImportOptions {
+    /// Store every revision of a page as separate edit, instead of only the latest one
+    pub all_revisions: bool,
+    /// Name of the local user who is set as author of imported edits
+    pub username: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MediaWikiImportStats {
+    pub articles: usize,
+    pub edits: usize,
+    pub redirects: usize,
+    pub skipped: usize,
+}
+
+#[derive(Default)]
+struct Page {
+    title: String,
+    namespace: String,
+    redirect: Option<String>,
+    revisions: Vec<Revision>,
+}
+
+#[derive(Default)]
+struct Revision {
+    timestamp: String,
+    comment: String,
+    text: String,
+}
+
+impl Page {
+    /// Add text content to the field which corresponds to the current XML element.
+    fn append_text(&mut self, elements: &[Vec<u8>], text: &str) {
+        let field = match elements {
+            [.., parent, name] if parent == b"page" => match name.as_slice() {
+                b"title" => &mut self.title,
+                b"ns" => &mut self.namespace,
+                _ => return,
+            },
+            [.., parent, name] if parent == b"revision" => {
+                let Some(revision) = self.revisions.last_mut() else {
+                    return;
+                };
+                match name.as_slice() {
+                    b"timestamp" => &mut revision.timestamp,
+                    b"comment" => &mut revision.comment,
+                    b"text" => &mut revision.text,
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        field.push_str(text);
+    }
+}
+
+/// Read the dump at `path` and create a local article for each page in the main namespace.
+///
+/// The file is parsed as a stream so that large dumps don't need to be held in memory. Pages
+/// which can't be imported, for example because the title is invalid, the content can't be
+/// converted or an article with the same title exists already (such as the default Main Page),
+/// are skipped with a warning. Redirect pages are stored as article redirects once all pages
+/// are imported.
+///
+/// Imported edits are not federated. Remote instances fetch the articles once they are linked
+/// or followed.
+pub async fn import_mediawiki_dump(
+    path: &Path,
+    options: &MediaWikiImportOptions,
+    context: &IbisContext,
+) -> BackendResult<MediaWikiImportStats> {
+    let person = Person::read_from_name(&options.username, &None, context)
+        .map_err(|_| anyhow!("User {} not found", options.username))?;
+    let instance = Instance::read_local(context)
+        .map_err(|_| anyhow!("Local instance not found, start Ibis once before importing"))?;
+    let mut wiki_page = WikiPage::new()?;
+    let mut stats = MediaWikiImportStats::default();
+    let mut redirects = vec![];
+
+    let mut reader = Reader::from_file(path)?;
+    let mut buf = vec![];
+    let mut elements: Vec<Vec<u8>> = vec![];
+    let mut page = Page::default();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                let name = e.name().as_ref().to_vec();
+                if name == b"revision" {
+                    page.revisions.push(Revision::default());
+                }
+                elements.push(name);
+            }
+            Event::Empty(e) if e.name().as_ref() == b"redirect" => {
+                if let Some(title) = e.try_get_attribute("title")? {
+                    page.redirect = Some(title.unescape_value()?.to_string());
+                }
+            }
+            Event::End(e) => {
+                elements.pop();
+                match e.name().as_ref() {
+                    b"revision" if !options.all_revisions && page.revisions.len() > 1 => {
+                        // Only keep the latest revision
+                        page.revisions.remove(0);
+                    }
+                    b"page" => {
+                        let page = take(&mut page);
+                        if page.namespace.trim() != MAIN_NAMESPACE {
+                            continue;
+                        }
+                        let title = normalize_title(&page.title);
+                        if let Err(e) = validate_article_title(&title) {
+                            warn!("Skipping page {title}: {e}");
+                            stats.skipped += 1;
+                        } else if let Some(target) = page.redirect {
+                            redirects.push((title, normalize_title(&target)));
+                        } else if let Err(e) = import_page(
+                            title.clone(),
+                            page.revisions,
+                            &person,
+                            &instance,
+                            &mut wiki_page,
+                            &mut stats,
+                            context,
+                        )
+                        .await
+                        {
+                            warn!("Skipping page {title}: {e}");
+                            stats.skipped += 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(e) => page.append_text(&elements, &e.decode()?),
+            Event::CData(e) => page.append_text(&elements, &e.decode()?),
+            Event::GeneralRef(e) => {
+                let text = match e.resolve_char_ref()? {
+                    Some(c) => c.to_string(),
+                    None => {
+                        let entity = e.decode()?;
+                        resolve_predefined_entity(&entity)
+                            .ok_or(anyhow!("Unknown XML entity {entity}"))?
+                            .to_string()
+                    }
+                };
+                page.append_text(&elements, &text);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    for (title, target) in redirects {
+        match Article::read_view((&target, None), None, context) {
+            Ok(view) => {
+                let ap_id = generate_article_ap_id(&title, &instance)?;
+                Article::create_redirect(view.article.id, title, ap_id, context)?;
+                stats.redirects += 1;
+            }
+            Err(_) => {
+                warn!("Skipping redirect {title}, target {target} not found");
+                stats.skipped += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Create the article and store each revision as an edit with its original timestamp.
+async fn import_page(
+    title: String,
+    revisions: Vec<Revision>,
+    person: &Person,
+    instance: &Instance,
+    wiki_page: &mut WikiPage,
+    stats: &mut MediaWikiImportStats,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let mut converted: Vec<(DateTime<Utc>, String, String)> = vec![];
+    for revision in revisions {
+        let text = convert_wikitext(&revision.text, &instance.domain, wiki_page)?;
+        // Changes to removed markup such as templates result in identical text
+        if text.trim().is_empty() || converted.last().is_some_and(|(_, _, t)| t == &text) {
+            continue;
+        }
+        let published = DateTime::parse_from_rfc3339(&revision.timestamp)?.with_timezone(&Utc);
+        converted.push((published, revision.comment, text));
+    }
+    let Some((updated, _, _)) = converted.last() else {
+        warn!("Skipping page {title} without content");
+        stats.skipped += 1;
+        return Ok(());
+    };
+
+    let form = DbArticleForm {
+        ap_id: generate_article_ap_id(&title, instance)?,
+        title: title.clone(),
+        text: String::new(),
+        instance_id: instance.id,
+        local: true,
+        protected: false,
+        updated: *updated,
+        pending: false,
+    };
+    let mut article = match Article::create(form, person.id, context).await {
+        Ok(article) => article,
+        Err(e) => {
+            warn!("Skipping page {title}: {e}");
+            stats.skipped += 1;
+            return Ok(());
+        }
+    };
+    let mut previous_version = article.latest_edit_version(context)?;
+    for (published, summary, text) in converted {
+        let mut form =
+            DbEditForm::new(&article, person.id, &text, summary, previous_version, false)?;
+        form.published = published;
+        Edit::create_or_update(&form, false, context).await?;
+        article = Article::update_text(article.id, &text, context)?;
+        previous_version = form.hash;
+        stats.edits += 1;
+    }
+    info!("Imported article {title}");
+    stats.articles += 1;
+    Ok(())
+}
+
+/// Convert wikitext to markdown. The text is first rendered as simple HTML, which is then
+/// converted to markdown in the same way as articles imported from Wikipedia.
+fn convert_wikitext(text: &str, domain: &str, wiki_page: &mut WikiPage) -> BackendResult<String> {
+    let html = wikitext_to_html(text, domain);
+    let mut markdown = format_markdown(&wiki_page.extract_text(&html)?.format_markdown())?;
+    for (c, placeholder) in PLACEHOLDERS {
+        markdown = markdown.replace(placeholder, &c.to_string());
+    }
+    Ok(markdown)
+}
+
+/// Render the common wikitext syntax for headings, paragraphs, lists, emphasis and links as
+/// HTML. Templates, tables, references and files are removed. Links to other pages are written
+/// as `[[Title@domain]]` article links.
+fn wikitext_to_html(text: &str, domain: &str) -> String {
+    let text = strip_references(&strip_between(text, "<!--", "-->"));
+    let text = strip_nested(&strip_nested(&text, "{{", "}}"), "{|", "|}");
+    let mut text = strip_html_tags(&text);
+    text = unescape_with(&text, resolve_html5_entity)
+        .map(|t| t.to_string())
+        .unwrap_or(text);
+    for (c, placeholder) in PLACEHOLDERS {
+        text = text.replace(c, &placeholder.to_string());
+    }
+
+    let mut html = String::new();
+    let mut paragraph: Vec<String> = vec![];
+    for line in text.lines().map(str::trim) {
+        if let Some((level, heading)) = parse_heading(line) {
+            finish_paragraph(&mut html, &mut paragraph);
+            let heading = render_inline(heading, domain);
+            html.push_str(&format!("<h{level}>{heading}</h{level}>\n"));
+        } else if line.is_empty() || line.starts_with("----") {
+            finish_paragraph(&mut html, &mut paragraph);
+        } else if let Some(item) = line.strip_prefix(['*', '#']) {
+            finish_paragraph(&mut html, &mut paragraph);
+            let marker = if line.starts_with('#') { "1." } else { "-" };
+            let item = render_inline(item.trim_start_matches(['*', '#', ':']).trim(), domain);
+            html.push_str(&format!("<p>{marker} {item}</p>\n"));
+        } else {
+            let line = render_inline(line.trim_start_matches([':', ';']).trim(), domain);
+            if !line.trim().is_empty() {
+                paragraph.push(line.trim().to_string());
+            }
+        }
+    }
+    finish_paragraph(&mut html, &mut paragraph);
+    html
+}
+
+fn finish_paragraph(html: &mut String, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", paragraph.join(" ")));
+        paragraph.clear();
+    }
+}
+
+/// Parse a heading like `== Title ==`, returning its level and text.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let start = line.len() - line.trim_start_matches('=').len();
+    let end = line.len() - line.trim_end_matches('=').len();
+    let level = start.min(end).clamp(1, 6);
+    let text = line.get(level..line.len().checked_sub(level)?)?.trim();
+    (start > 0 && end > 0 && !text.is_empty() && !text.contains('=')).then_some((level, text))
+}
+
+/// Render links, bold and italic text within a single line.
+fn render_inline(line: &str, domain: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if let Some(inner) = rest.strip_prefix("[[") {
+            if let Some(end) = find_closing(inner, "[[", "]]") {
+                out.push_str(&render_internal_link(&inner[..end], domain));
+                rest = &inner[end + 2..];
+                continue;
+            }
+        }
+        if let Some(inner) = rest.strip_prefix('[') {
+            if inner.starts_with("http://") || inner.starts_with("https://") {
+                if let Some(end) = inner.find(']') {
+                    let (url, label) = inner[..end].split_once(' ').unwrap_or((&inner[..end], ""));
+                    let label = if label.trim().is_empty() { url } else { label };
+                    // `&`, `<` and `>` are already replaced with placeholders, but quotes
+                    // could still end the attribute
+                    out.push_str(&format!(
+                        "<a href=\"{}\">{}</a>",
+                        escape(url),
+                        escape(label.trim())
+                    ));
+                    rest = &inner[end + 1..];
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    toggle_tags(&toggle_tags(&out, "'''", "b"), "''", "i")
+}
+
+/// Convert a wiki link like `[[Target#Section|label]]` to an article link.
+fn render_internal_link(inner: &str, domain: &str) -> String {
+    let (target, label) = inner.split_once('|').unwrap_or((inner, inner));
+    let label = label.replace("'''", "").replace("''", "");
+    if let Some((namespace, _)) = target.split_once(':') {
+        let namespace = namespace.trim().to_lowercase();
+        if REMOVED_LINK_NAMESPACES.contains(&namespace.as_str()) {
+            return String::new();
+        }
+        return label;
+    }
+    let title = normalize_title(target.split('#').next().unwrap_or_default());
+    if title.is_empty() {
+        label
+    } else if normalize_title(&label) == title {
+        format!("[[{title}@{domain}]]")
+    } else {
+        format!("[[{title}@{domain}|{label}]]")
+    }
+}
+
+/// MediaWiki uses underscores and spaces interchangeably, and always capitalizes the first
+/// letter of titles.
+fn normalize_title(title: &str) -> String {
+    let title = title.replace('_', " ");
+    let mut chars = title.trim().chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Replace pairs of `marker` with opening and closing HTML tags.
+fn toggle_tags(text: &str, marker: &str, tag: &str) -> String {
+    let mut out = String::new();
+    let mut open = false;
+    for (i, part) in text.split(marker).enumerate() {
+        if i > 0 {
+            out.push_str(if open { "</" } else { "<" });
+            out.push_str(tag);
+            out.push('>');
+            open = !open;
+        }
+        out.push_str(part);
+    }
+    if open {
+        out.push_str(&format!("</{tag}>"));
+    }
+    out
+}
+
+/// Position of the `close` marker which matches an already opened `open` marker.
+fn find_closing(text: &str, open: &str, close: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if rest.starts_with(open) {
+            depth += 1;
+            pos += open.len();
+        } else if rest.starts_with(close) {
+            if depth == 0 {
+                return Some(pos);
+            }
+            depth -= 1;
+            pos += close.len();
+        } else {
+            pos += rest.chars().next()?.len_utf8();
+        }
+    }
+    None
+}
+
+/// Remove blocks like templates which may contain further blocks of the same kind.
+fn strip_nested(text: &str, open: &str, close: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        out.push_str(&rest[..start]);
+        let inner = &rest[start + open.len()..];
+        rest = match find_closing(inner, open, close) {
+            Some(end) => &inner[end + close.len()..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_between(text: &str, open: &str, close: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        out.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find(close)
+            .map(|end| &rest[start + end + close.len()..])
+            .unwrap_or_default();
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Remove HTML tags like `<br />` or `<small>`, but keep their content.
+fn strip_html_tags(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let tag = &rest[start + 1..];
+        let is_tag = tag
+            .trim_start_matches('/')
+            .starts_with(|c: char| c.is_ascii_alphabetic());
+        match tag.find('>') {
+            Some(end) if is_tag => rest = &tag[end + 1..],
+            _ => {
+                out.push('<');
+                rest = tag;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Remove footnotes, written as `<ref>...</ref>` or `<ref name="x" />`.
+fn strip_references(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<ref") {
+        out.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        rest = match tag.find('>') {
+            Some(end) if tag[..end].ends_with('/') => &tag[end + 1..],
+            Some(_) => tag
+                .find("</ref>")
+                .map(|end| &tag[end + "</ref>".len()..])
+                .unwrap_or_default(),
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wikitext_to_html() {
+        let wikitext = "Intro with '''bold''' and ''italic'' text.{{Infobox|name={{x}}}}<ref>Source</ref>\n\
+            continued [[main_page|the main page]], [[Other]] and [[File:a.png|thumb|[[x]]]].\n\
+            \n\
+            == History ==\n\
+            * item [https://example.com Example]\n\
+            [[Category:Test]] <small>a &lt; b &amp;&nbsp;c</small>";
+        assert_eq!(
+            wikitext_to_html(wikitext, "example.com"),
+            "<p>Intro with <b>bold</b> and <i>italic</i> text. continued \
+            [[Main page@example.com|the main page]], [[Other@example.com]] and .</p>\n\
+            <h2>History</h2>\n\
+            <p>- item <a href=\"https://example.com\">Example</a></p>\n\
+            <p>a \u{e001} b \u{e000}\u{a0}c</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_external_link_escaped() {
+        assert_eq!(
+            render_inline(
+                "[https://example.com/\"onclick=\"x <b>label</b>]",
+                "example.com"
+            ),
+            "<a href=\"https://example.com/&quot;onclick=&quot;x\">&lt;b&gt;label&lt;/b&gt;</a>"
+        );
+    }
+}
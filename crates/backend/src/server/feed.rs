@@ -0,0 +1,377 @@
+use activitypub_federation::config::Data;
+use axum::{
+    Router,
+    extract::Path,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_macros::debug_handler;
+use chrono::{DateTime, Utc};
+use http::header::CONTENT_TYPE;
+use ibis_database::{
+    common::{
+        article::{Article, Edit, EditView},
+        comment::{Comment, CommentView},
+        instance::Instance,
+        user::Person,
+        utils::http_protocol_str,
+    },
+    error::BackendResult,
+    impls::{IbisContext, edit::RecentChangesQuery},
+};
+use ibis_frontend_components::utils::formatting::{
+    article_path,
+    comment_path,
+    edit_path,
+    person_path,
+};
+use ibis_markdown::render_comment_markdown;
+use quick_xml::{
+    Writer,
+    escape::escape,
+    events::{BytesDecl, BytesText, Event},
+};
+use serde::Deserialize;
+use std::io;
+
+/// Maximum number of entries in a feed
+const FEED_LIMIT: i64 = 50;
+
+/// Number of diff lines which are included in each edit entry
+const DIFF_EXCERPT_LINES: usize = 20;
+
+/// Atom and RSS feeds for use with feed readers, e.g. `/feed/atom/changes` or
+/// `/feed/rss/article/Main_Page`.
+pub(super) fn feed_routes() -> Router<()> {
+    Router::new()
+        .route("/feed/{format}/changes", get(recent_changes_feed))
+        .route("/feed/{format}/article/{title}", get(article_history_feed))
+        .route(
+            "/feed/{format}/article/{title}/comments",
+            get(article_comments_feed),
+        )
+        .route("/feed/{format}/user/{name}", get(user_contributions_feed))
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+impl FeedFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "atom",
+            FeedFormat::Rss => "rss",
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+        }
+    }
+}
+
+struct Feed {
+    title: String,
+    /// Page which shows the same content in the frontend
+    link: String,
+    entries: Vec<FeedEntry>,
+}
+
+struct FeedEntry {
+    id: String,
+    title: String,
+    link: String,
+    author: String,
+    author_link: String,
+    published: DateTime<Utc>,
+    summary: Option<String>,
+    /// Rendered html
+    content: String,
+}
+
+#[debug_handler]
+async fn recent_changes_feed(
+    Path(format): Path<FeedFormat>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    let instance = Instance::read_local(&context)?;
+    let edits = Edit::list_recent(RecentChangesQuery::default(), None, FEED_LIMIT, &context)?.items;
+    let feed = Feed {
+        title: format!(
+            "{} - Recent changes",
+            instance.name.unwrap_or(instance.domain)
+        ),
+        link: site_url("/", &context),
+        entries: edits.into_iter().map(|e| edit_entry(e, &context)).collect(),
+    };
+    feed_response(feed, format, "/changes", &context)
+}
+
+#[debug_handler]
+async fn article_history_feed(
+    Path((format, title)): Path<(FeedFormat, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    let article = read_article(&title, &context)?;
+    let query = RecentChangesQuery {
+        article_id: Some(article.id),
+        ..Default::default()
+    };
+    let edits = Edit::list_recent(query, None, FEED_LIMIT, &context)?.items;
+    let feed = Feed {
+        title: format!("{} - History", article.title),
+        link: site_url(&format!("{}/history", article_path(&article)), &context),
+        entries: edits.into_iter().map(|e| edit_entry(e, &context)).collect(),
+    };
+    feed_response(feed, format, &article_path(&article), &context)
+}
+
+#[debug_handler]
+async fn article_comments_feed(
+    Path((format, title)): Path<(FeedFormat, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    let article = read_article(&title, &context)?;
+    let comments = Comment::read_for_article(article.id, None, Some(FEED_LIMIT), &context)?;
+    let entries = comments
+        .items
+        .into_iter()
+        .filter(|c| !c.comment.deleted)
+        .map(|c| comment_entry(c, &article, &context))
+        .collect();
+    let feed = Feed {
+        title: format!("{} - Discussion", article.title),
+        link: site_url(&format!("{}/discussion", article_path(&article)), &context),
+        entries,
+    };
+    let path = format!("{}/comments", article_path(&article));
+    feed_response(feed, format, &path, &context)
+}
+
+#[debug_handler]
+async fn user_contributions_feed(
+    Path((format, name)): Path<(FeedFormat, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    let (username, domain) = match name.split_once('@') {
+        Some((username, domain)) => (username, Some(domain.to_string())),
+        None => (name.as_str(), None),
+    };
+    let person = Person::read_from_name(username, &domain, &context)?;
+    let query = RecentChangesQuery {
+        person_id: Some(person.id),
+        ..Default::default()
+    };
+    let edits = Edit::list_recent(query, None, FEED_LIMIT, &context)?.items;
+    let feed = Feed {
+        title: format!("{} - Contributions", person.title()),
+        link: site_url(&person_path(&person), &context),
+        entries: edits.into_iter().map(|e| edit_entry(e, &context)).collect(),
+    };
+    feed_response(feed, format, &person_path(&person), &context)
+}
+
+/// Read article from a title like `Main_Page` or `Main_Page@example.com`.
+fn read_article(title: &str, context: &IbisContext) -> BackendResult<Article> {
+    let (title, domain) = match title.split_once('@') {
+        Some((title, domain)) => (title, Some(domain.to_string())),
+        None => (title, None),
+    };
+    let title = title.replace('_', " ");
+    Ok(Article::read_view((&title, domain), None, context)?.article)
+}
+
+fn edit_entry(view: EditView, context: &IbisContext) -> FeedEntry {
+    let EditView {
+        edit,
+        article,
+        creator,
+    } = view;
+    let title = if edit.summary.is_empty() {
+        article.title.clone()
+    } else {
+        format!("{}: {}", article.title, edit.summary)
+    };
+    let content = format!(
+        "<p>{}</p><pre>{}</pre>",
+        escape(&edit.summary),
+        escape(diff_excerpt(&edit.diff))
+    );
+    let link = edit_path(&edit, &article);
+    FeedEntry {
+        id: edit.ap_id.to_string(),
+        title,
+        link: site_url(&link, context),
+        author: creator.title(),
+        author_link: site_url(&person_path(&creator), context),
+        published: edit.published,
+        summary: Some(edit.summary).filter(|s| !s.is_empty()),
+        content,
+    }
+}
+
+fn comment_entry(view: CommentView, article: &Article, context: &IbisContext) -> FeedEntry {
+    let CommentView { comment, creator } = view;
+    let link = comment_path(&comment, article);
+    FeedEntry {
+        id: comment.ap_id.to_string(),
+        title: format!("{} on {}", creator.title(), article.title),
+        link: site_url(&link, context),
+        author: creator.title(),
+        author_link: site_url(&person_path(&creator), context),
+        published: comment.published,
+        summary: None,
+        content: render_comment_markdown(&comment.content),
+    }
+}
+
+/// First lines of the unified diff, without the file headers.
+fn diff_excerpt(diff: &str) -> String {
+    let mut lines = diff
+        .lines()
+        .filter(|l| !l.starts_with("--- ") && !l.starts_with("+++ "));
+    let mut excerpt = lines
+        .by_ref()
+        .take(DIFF_EXCERPT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.next().is_some() {
+        excerpt.push_str("\n…");
+    }
+    excerpt
+}
+
+fn site_url(path: &str, context: &IbisContext) -> String {
+    format!(
+        "{}://{}{path}",
+        http_protocol_str(),
+        context.conf.federation_domain()
+    )
+}
+
+fn feed_response(
+    feed: Feed,
+    format: FeedFormat,
+    path: &str,
+    context: &IbisContext,
+) -> BackendResult<Response> {
+    let self_link = site_url(&format!("/feed/{}{path}", format.as_str()), context);
+    let body = match format {
+        FeedFormat::Atom => write_atom(&feed, &self_link)?,
+        FeedFormat::Rss => write_rss(&feed, &self_link)?,
+    };
+    Ok(([(CONTENT_TYPE, format.content_type())], body).into_response())
+}
+
+fn write_atom(feed: &Feed, self_link: &str) -> io::Result<Vec<u8>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+    let updated = feed
+        .entries
+        .first()
+        .map(|e| e.published)
+        .unwrap_or_else(Utc::now);
+    writer
+        .create_element("feed")
+        .with_attribute(("xmlns", "http://www.w3.org/2005/Atom"))
+        .write_inner_content(|w| {
+            w.create_element("title")
+                .write_text_content(BytesText::new(&feed.title))?;
+            w.create_element("link")
+                .with_attribute(("href", feed.link.as_str()))
+                .write_empty()?;
+            w.create_element("link")
+                .with_attributes([("rel", "self"), ("href", self_link)])
+                .write_empty()?;
+            w.create_element("id")
+                .write_text_content(BytesText::new(self_link))?;
+            w.create_element("updated")
+                .write_text_content(BytesText::new(&updated.to_rfc3339()))?;
+            for entry in &feed.entries {
+                w.create_element("entry").write_inner_content(|w| {
+                    w.create_element("title")
+                        .write_text_content(BytesText::new(&entry.title))?;
+                    w.create_element("link")
+                        .with_attribute(("href", entry.link.as_str()))
+                        .write_empty()?;
+                    w.create_element("id")
+                        .write_text_content(BytesText::new(&entry.id))?;
+                    w.create_element("updated")
+                        .write_text_content(BytesText::new(&entry.published.to_rfc3339()))?;
+                    w.create_element("author").write_inner_content(|w| {
+                        w.create_element("name")
+                            .write_text_content(BytesText::new(&entry.author))?;
+                        w.create_element("uri")
+                            .write_text_content(BytesText::new(&entry.author_link))?;
+                        Ok(())
+                    })?;
+                    if let Some(summary) = &entry.summary {
+                        w.create_element("summary")
+                            .write_text_content(BytesText::new(summary))?;
+                    }
+                    w.create_element("content")
+                        .with_attribute(("type", "html"))
+                        .write_text_content(BytesText::new(&entry.content))?;
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    Ok(writer.into_inner())
+}
+
+fn write_rss(feed: &Feed, self_link: &str) -> io::Result<Vec<u8>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+    writer
+        .create_element("rss")
+        .with_attributes([
+            ("version", "2.0"),
+            ("xmlns:atom", "http://www.w3.org/2005/Atom"),
+            ("xmlns:dc", "http://purl.org/dc/elements/1.1/"),
+        ])
+        .write_inner_content(|w| {
+            w.create_element("channel").write_inner_content(|w| {
+                w.create_element("title")
+                    .write_text_content(BytesText::new(&feed.title))?;
+                w.create_element("link")
+                    .write_text_content(BytesText::new(&feed.link))?;
+                w.create_element("description")
+                    .write_text_content(BytesText::new(&feed.title))?;
+                w.create_element("atom:link")
+                    .with_attributes([
+                        ("href", self_link),
+                        ("rel", "self"),
+                        ("type", "application/rss+xml"),
+                    ])
+                    .write_empty()?;
+                for entry in &feed.entries {
+                    w.create_element("item").write_inner_content(|w| {
+                        w.create_element("title")
+                            .write_text_content(BytesText::new(&entry.title))?;
+                        w.create_element("link")
+                            .write_text_content(BytesText::new(&entry.link))?;
+                        w.create_element("guid")
+                            .with_attribute(("isPermaLink", "false"))
+                            .write_text_content(BytesText::new(&entry.id))?;
+                        w.create_element("pubDate")
+                            .write_text_content(BytesText::new(&entry.published.to_rfc2822()))?;
+                        w.create_element("dc:creator")
+                            .write_text_content(BytesText::new(&entry.author))?;
+                        w.create_element("description")
+                            .write_text_content(BytesText::new(&entry.content))?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+    Ok(writer.into_inner())
+}
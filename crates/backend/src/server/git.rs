@@ -0,0 +1,148 @@
+use crate::git::GitRepository;
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{
+    Router,
+    extract::Path,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_macros::debug_handler;
+use http::StatusCode;
+use ibis_database::{common::article::Article, error::BackendResult, impls::IbisContext};
+use moka::sync::Cache;
+use std::{
+    sync::{
+        Arc,
+        LazyLock,
+        Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+use tokio::task::spawn_blocking;
+
+/// Read-only git repositories using the dumb HTTP protocol. The whole wiki can be cloned from
+/// `/git/wiki.git`, and single articles from `/git/article/<Title>.git`.
+pub(super) fn git_routes() -> Router<()> {
+    Router::new()
+        .route("/git/wiki.git/HEAD", get(get_head))
+        .route("/git/wiki.git/info/refs", get(get_wiki_refs))
+        .route("/git/wiki.git/objects/{dir}/{file}", get(get_wiki_object))
+        .route("/git/article/{repo}/HEAD", get(get_head))
+        .route("/git/article/{repo}/info/refs", get(get_article_refs))
+        .route(
+            "/git/article/{repo}/objects/{dir}/{file}",
+            get(get_article_object),
+        )
+}
+
+#[debug_handler]
+async fn get_head() -> String {
+    GitRepository::head_ref()
+}
+
+#[debug_handler]
+async fn get_wiki_refs(context: Data<IbisContext>) -> BackendResult<String> {
+    read_repository(None, true, context, GitRepository::info_refs).await
+}
+
+#[debug_handler]
+async fn get_wiki_object(
+    Path((dir, file)): Path<(String, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    read_repository(None, false, context, move |repo| {
+        object_response(repo, &dir, &file)
+    })
+    .await
+}
+
+#[debug_handler]
+async fn get_article_refs(
+    Path(repo): Path<String>,
+    context: Data<IbisContext>,
+) -> BackendResult<String> {
+    read_repository(Some(repo), true, context, GitRepository::info_refs).await
+}
+
+#[debug_handler]
+async fn get_article_object(
+    Path((repo, dir, file)): Path<(String, String, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    read_repository(Some(repo), false, context, move |repo| {
+        object_response(repo, &dir, &file)
+    })
+    .await
+}
+
+/// Git clients probe for optional files like `objects/info/alternates`, so missing objects need
+/// to return 404 instead of an error.
+fn object_response(repo: &GitRepository, dir: &str, file: &str) -> Response {
+    match repo.objects.get(&format!("{dir}{file}")) {
+        Some(data) => data.clone().into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Domain of the instance, and repo name or `None` for the whole wiki.
+type RepositoryKey = (String, Option<String>);
+
+/// Repository kept in memory, together with its size in bytes for the cache weigher.
+#[derive(Debug, Default)]
+struct CachedRepository {
+    repo: Mutex<GitRepository>,
+    size: AtomicU32,
+}
+
+/// Pass the repository for the whole wiki (if `repo` is `None`) or for a single article with
+/// a repo name like `Main_Page.git` to `f`. Repositories are kept in memory, so that later
+/// requests only need to add commits for new edits. Clients fetch `info/refs` before any
+/// objects, so only that request checks for new edits (`refresh`), while object requests
+/// use the cached repository as is. Generation runs on a blocking thread as it can take a
+/// while for large wikis.
+async fn read_repository<T: Send + 'static>(
+    repo: Option<String>,
+    refresh: bool,
+    context: Data<IbisContext>,
+    f: impl FnOnce(&GitRepository) -> T + Send + 'static,
+) -> BackendResult<T> {
+    static CACHE: LazyLock<Cache<RepositoryKey, Arc<CachedRepository>>> = LazyLock::new(|| {
+        Cache::builder()
+            // Total size of cached repositories in bytes
+            .max_capacity(256 * 1024 * 1024)
+            .weigher(|_, cached: &Arc<CachedRepository>| cached.size.load(Ordering::Relaxed))
+            .time_to_idle(Duration::from_secs(3600))
+            .build()
+    });
+    spawn_blocking(move || {
+        let key = (context.conf.domain.clone(), repo);
+        let cached = CACHE.get_with(key.clone(), Default::default);
+        let mut git = cached
+            .repo
+            .lock()
+            .map_err(|e| anyhow!("Failed to lock git repository: {e}"))?;
+        // A repository which is not cached yet, for example after it was evicted, needs to be
+        // generated even for object requests
+        if refresh || git.head.is_none() {
+            let article = match &key.1 {
+                Some(repo) => {
+                    let title = repo
+                        .strip_suffix(".git")
+                        .ok_or(anyhow!("Invalid repository name"))?
+                        .replace('_', " ");
+                    Some(Article::read_view((&title, None), None, &context)?.article)
+                }
+                None => None,
+            };
+            git.update(article.as_ref(), &context)?;
+            let size = git.size().try_into().unwrap_or(u32::MAX);
+            cached.size.store(size, Ordering::Relaxed);
+            // Insert again so that the cache picks up the new size
+            CACHE.insert(key, cached.clone());
+        }
+        Ok(f(&git))
+    })
+    .await?
+}
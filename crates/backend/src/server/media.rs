@@ -0,0 +1,80 @@
+use crate::api::{media_file_path, thumbnail_file_path};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{
+    Router,
+    extract::Path,
+    response::{IntoResponse, Redirect, Response},
+    routing::get,
+};
+use axum_macros::debug_handler;
+use http::{HeaderValue, header};
+use ibis_database::{
+    common::media::{MEDIA_PATH, Media},
+    error::BackendResult,
+    impls::IbisContext,
+};
+use tokio::fs::read;
+
+pub(super) fn media_routes() -> Router<()> {
+    Router::new()
+        .route(&format!("{MEDIA_PATH}/{{filename}}"), get(get_media))
+        .route(
+            &format!("{MEDIA_PATH}/thumbnail/{{filename}}"),
+            get(get_thumbnail),
+        )
+}
+
+#[debug_handler]
+async fn get_media(
+    Path(filename): Path<String>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    serve_media(filename, false, &context).await
+}
+
+#[debug_handler]
+async fn get_thumbnail(
+    Path(filename): Path<String>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    serve_media(filename, true, &context).await
+}
+
+/// Return the file for local media, or redirect to the instance where remote media is hosted.
+async fn serve_media(
+    filename: String,
+    thumbnail: bool,
+    context: &IbisContext,
+) -> BackendResult<Response> {
+    let media = Media::read_from_filename(&filename, context)?;
+    if !media.local {
+        // only redirect to the file on the remote instance, not to arbitrary urls
+        if !Media::is_valid_url(&media.filename, media.ap_id.inner()) {
+            return Err(anyhow!("Invalid media url").into());
+        }
+        let mut url = media.ap_id.inner().clone();
+        if thumbnail {
+            url.set_path(&media.thumbnail_path());
+        }
+        return Ok(Redirect::temporary(url.as_str()).into_response());
+    }
+    let path = if thumbnail {
+        thumbnail_file_path(&context.conf, &media.filename)
+    } else {
+        media_file_path(&context.conf, &media.filename)
+    };
+    let content = read(path).await?;
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(&media.mime_type)?,
+        ),
+        // Files are immutable because the name is derived from the content hash
+        (
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("max-age=31536000, public, immutable"),
+        ),
+    ];
+    Ok((headers, content).into_response())
+}
@@ -1,9 +1,24 @@
 use crate::api::user::validate;
-use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
 use axum_macros::debug_middleware;
-use http::{HeaderValue, header::COOKIE};
+use http::{
+    HeaderValue,
+    Method,
+    StatusCode,
+    header::{AUTHORIZATION, COOKIE},
+};
 use ibis_database::{
-    common::{AUTH_COOKIE, Auth},
+    common::{
+        AUTH_COOKIE,
+        Auth,
+        api_token::{API_TOKEN_PREFIX, ApiToken, ApiTokenScope},
+    },
     impls::IbisContext,
 };
 use std::{collections::HashSet, sync::Arc};
@@ -12,7 +27,11 @@ pub(super) const FEDERATION_ROUTES_PREFIX: &str = "/federation_routes";
 
 /// Checks all headers and cookies (including duplicates) for first valid auth token.
 /// We need to extract cookies manually because CookieJar ignores duplicates.
-/// If user is authenticated sets extensions `Auth` and `LocalUserView`.
+/// If user is authenticated sets extensions `Auth`, `LocalUserView` and `LoginSession`.
+///
+/// API tokens are accepted in the same places and as `Authorization: Bearer` header. They
+/// additionally set the `ApiToken` extension, and requests which are outside of the token
+/// scopes are rejected.
 #[debug_middleware]
 pub(super) async fn auth_middleware(
     State(context): State<Arc<IbisContext>>,
@@ -28,24 +47,69 @@ pub(super) async fn auth_middleware(
         .flat_map(|s| s.split_once('='))
         .filter(|s| s.0.trim() == AUTH_COOKIE)
         .map(|s| s.1);
+    let bearer = headers
+        .get_all(AUTHORIZATION)
+        .into_iter()
+        .filter_map(|h| h.to_str().ok()?.strip_prefix("Bearer "));
     let headers = headers
         .get_all(AUTH_COOKIE)
         .into_iter()
         .filter_map(|h| h.to_str().ok());
     let auth: HashSet<_> = headers
         .chain(cookies)
+        .chain(bearer)
         .map(std::string::ToString::to_string)
         .collect();
 
     for auth in auth {
-        if let Ok(local_user) = validate(&auth, &context).await {
+        if auth.starts_with(API_TOKEN_PREFIX) {
+            if let Ok((api_token, local_user)) = ApiToken::validate(&auth, &context) {
+                let scope = required_scope(request.method(), request.uri().path());
+                if !scope.is_some_and(|s| api_token.has_scope(s)) {
+                    return (
+                        StatusCode::FORBIDDEN,
+                        "API token does not have the required scope",
+                    )
+                        .into_response();
+                }
+                request.extensions_mut().insert(Auth(Some(auth)));
+                request.extensions_mut().insert(local_user);
+                request.extensions_mut().insert(api_token);
+            }
+        } else if let Ok((local_user, session)) = validate(&auth, &context).await {
             request.extensions_mut().insert(Auth(Some(auth)));
             request.extensions_mut().insert(local_user);
+            request.extensions_mut().insert(session);
         }
     }
     next.run(request).await
 }
 
+/// Scope which an API token needs for the request, or `None` if the request can't be made with
+/// a token at all. This includes account settings and moderator actions.
+fn required_scope(method: &Method, path: &str) -> Option<ApiTokenScope> {
+    // Sessions, API tokens and other account data need a login, even for reading
+    if path.starts_with("/api/v1/account/") {
+        return None;
+    }
+    if method == Method::GET || method == Method::HEAD {
+        return Some(ApiTokenScope::Read);
+    }
+    let path = path.strip_prefix("/api/v1")?;
+    let (post, patch, delete) = (
+        method == Method::POST,
+        method == Method::PATCH,
+        method == Method::DELETE,
+    );
+    match path {
+        "/article" if post || patch => Some(ApiTokenScope::EditArticles),
+        "/article/import" | "/article/fork" | "/media" if post => Some(ApiTokenScope::EditArticles),
+        "/conflict" if delete => Some(ApiTokenScope::EditArticles),
+        "/comment" if post || patch => Some(ApiTokenScope::Comment),
+        _ => None,
+    }
+}
+
 /// Rewrite federation routes to use `FEDERATION_ROUTES_PREFIX`, to avoid conflicts
 /// with frontend routes. If a request is an Activitypub fetch as indicated by
 /// `Accept: application/activity+json` header, use the federation routes. Otherwise
@@ -78,3 +142,35 @@ fn is_apub_request(header: &Option<&HeaderValue>) -> bool {
         .map(|h| h.starts_with("application/activity+json") || h.starts_with("application/ld+json"))
         .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_required_scope() {
+        let scope = |method, path| required_scope(&method, path);
+        assert_eq!(
+            Some(ApiTokenScope::Read),
+            scope(Method::GET, "/api/v1/media/list")
+        );
+        assert_eq!(
+            Some(ApiTokenScope::EditArticles),
+            scope(Method::PATCH, "/api/v1/article")
+        );
+        assert_eq!(
+            Some(ApiTokenScope::EditArticles),
+            scope(Method::POST, "/api/v1/media")
+        );
+        assert_eq!(None, scope(Method::DELETE, "/api/v1/media"));
+        assert_eq!(None, scope(Method::PATCH, "/api/v1/media"));
+        assert_eq!(
+            Some(ApiTokenScope::Comment),
+            scope(Method::POST, "/api/v1/comment")
+        );
+        assert_eq!(None, scope(Method::POST, "/api/v1/article/revert"));
+        assert_eq!(None, scope(Method::POST, "/media"));
+        assert_eq!(None, scope(Method::GET, "/api/v1/account/session/list"));
+        assert_eq!(None, scope(Method::GET, "/api/v1/account/api_token/list"));
+    }
+}
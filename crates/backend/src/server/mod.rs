@@ -12,12 +12,15 @@ use axum::{
     response::{IntoResponse, Response},
     routing::get,
 };
+use feed::feed_routes;
+use git::git_routes;
 use ibis_database::{common::Auth, error::BackendResult, impls::IbisContext};
 use ibis_federate::{nodeinfo, routes::federation_routes, webfinger};
 use ibis_frontend::app::{App, shell};
 use leptos::prelude::*;
 use leptos_axum::{LeptosRoutes, generate_route_list};
 use log::info;
+use media::media_routes;
 use middleware::{FEDERATION_ROUTES_PREFIX, auth_middleware, federation_routes_middleware};
 use std::{net::SocketAddr, ops::Deref, sync::Arc};
 use tokio::{net::TcpListener, sync::oneshot};
@@ -25,6 +28,9 @@ use tower_http::{compression::CompressionLayer, cors::CorsLayer};
 use tower_layer::Layer;
 
 mod assets;
+mod feed;
+mod git;
+mod media;
 mod middleware;
 pub(super) mod setup;
 
@@ -49,6 +55,9 @@ pub(super) async fn start_server(
         .with_state(leptos_options)
         .nest(FEDERATION_ROUTES_PREFIX, federation_routes())
         .nest("/api/v1", api_routes())
+        .merge(media_routes())
+        .merge(git_routes())
+        .merge(feed_routes())
         .merge(nodeinfo::config())
         .merge(webfinger::config())
         .layer(FederationMiddleware::new(context))
@@ -66,7 +75,9 @@ pub(super) async fn start_server(
     if let Some(notify_start) = notify_start {
         notify_start.send(()).expect("send oneshot");
     }
-    axum::serve(listener, app_with_middleware.into_make_service()).await?;
+    // client address is stored with login sessions
+    let service = app_with_middleware.into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(listener, service).await?;
     Ok(())
 }
 
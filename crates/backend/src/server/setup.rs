@@ -6,7 +6,7 @@ use ibis_database::{
         MAIN_PAGE_NAME,
         article::{Article, EditVersion},
         instance::Instance,
-        user::{LocalUserView, Person},
+        user::{LocalUserView, Person, Role},
         utils::http_protocol_str,
     },
     error::BackendError,
@@ -51,7 +51,7 @@ pub async fn setup(context: &Data<IbisContext>) -> Result<(), BackendError> {
     let admin = LocalUserView::create(
         context.conf.setup.admin_username.clone(),
         Some(context.conf.setup.admin_password.clone()),
-        true,
+        Role::Admin,
         None,
         context,
     )?;
@@ -79,6 +79,7 @@ pub async fn setup(context: &Data<IbisContext>) -> Result<(), BackendError> {
         &article,
         admin.person.into(),
         true,
+        false,
         context,
     )
     .await?;
@@ -1,39 +1,236 @@
 use anyhow::anyhow;
-use diffy::{Patch, apply};
+use diffy::{Line, Patch, apply};
 use ibis_database::{
     DbUrl,
     common::{
-        article::{Edit, EditVersion},
+        article::{ArticleDiff, DiffChangeKind, DiffChunk, DiffGranularity, Edit, EditVersion},
         instance::Instance,
         utils::{extract_domain, http_protocol_str},
     },
     error::BackendResult,
+    impls::{
+        IbisContext,
+        edit_snapshot::{EditSnapshot, SNAPSHOT_INTERVAL},
+    },
 };
+use ibis_markdown::render_article_markdown;
+use similar::{ChangeTag, TextDiff};
 use url::Url;
 
 /// Starting from empty string, apply edits until the specified version is reached. If no version is
 /// given, apply all edits up to latest version.
-///
-/// TODO: testing
-/// TODO: should cache all these generated versions
 pub(super) fn generate_article_version(
-    edits: &Vec<Edit>,
+    edits: &[Edit],
+    version: &EditVersion,
+) -> BackendResult<String> {
+    apply_edits_until(String::new(), edits, version)
+}
+
+/// Same as [generate_article_version], but starts from the most recent [EditSnapshot] before the
+/// version instead of replaying the whole history. Snapshots are stored when edits are created.
+pub(super) fn generate_article_version_from_snapshot(
+    edits: &[Edit],
+    version: &EditVersion,
+    context: &IbisContext,
+) -> BackendResult<String> {
+    let Some(position) = edits.iter().position(|e| &e.hash == version) else {
+        return generate_article_version(edits, version);
+    };
+    let checkpoints: Vec<_> = edits[..=position]
+        .iter()
+        .skip(SNAPSHOT_INTERVAL - 1)
+        .step_by(SNAPSHOT_INTERVAL)
+        .map(|e| e.id)
+        .collect();
+    let existing = EditSnapshot::list_existing(&checkpoints, context)?;
+    let snapshot = match checkpoints.iter().rposition(|id| existing.contains(id)) {
+        Some(i) => Some((
+            (i + 1) * SNAPSHOT_INTERVAL,
+            EditSnapshot::read(checkpoints[i], context)?.text,
+        )),
+        None => None,
+    };
+    generate_from_snapshot(edits, version, snapshot)
+}
+
+/// Generate the version starting from `snapshot`, which is the text after the given number of
+/// edits.
+fn generate_from_snapshot(
+    edits: &[Edit],
+    version: &EditVersion,
+    snapshot: Option<(usize, String)>,
+) -> BackendResult<String> {
+    let Some((applied, text)) = snapshot else {
+        return generate_article_version(edits, version);
+    };
+    if applied
+        .checked_sub(1)
+        .and_then(|i| edits.get(i))
+        .is_some_and(|e| &e.hash == version)
+    {
+        return Ok(text);
+    }
+    apply_edits_until(text, edits.get(applied..).unwrap_or_default(), version)
+}
+
+fn apply_edits_until(
+    mut text: String,
+    edits: &[Edit],
     version: &EditVersion,
 ) -> BackendResult<String> {
-    let mut generated = String::new();
     if version == &EditVersion::default() {
-        return Ok(generated);
+        return Ok(text);
     }
     for e in edits {
         let patch = Patch::from_str(&e.diff)?;
-        generated = apply(&generated, &patch)?;
+        text = apply(&text, &patch)?;
         if &e.hash == version {
-            return Ok(generated);
+            return Ok(text);
         }
     }
     Err(anyhow!("failed to generate article version").into())
 }
 
+/// Attribute each line of the text after the last edit to the edit which inserted it. Returns
+/// the line text without trailing newline, and the index of the edit in `edits`.
+pub(super) fn generate_blame(edits: &[Edit]) -> BackendResult<Vec<(String, usize)>> {
+    let mut lines: Vec<(String, usize)> = vec![];
+    for (i, edit) in edits.iter().enumerate() {
+        let patch = Patch::from_str(&edit.diff)?;
+        let mut old = lines.into_iter();
+        let mut next_line = || {
+            old.next()
+                .ok_or(anyhow!("Edit does not match article text"))
+        };
+        let mut new = vec![];
+        let mut position = 0;
+        for hunk in patch.hunks() {
+            // Hunk ranges start at 1, or at 0 for an empty file
+            let range = hunk.old_range();
+            let start = if range.is_empty() {
+                range.start()
+            } else {
+                range.start() - 1
+            };
+            while position < start {
+                new.push(next_line()?);
+                position += 1;
+            }
+            for line in hunk.lines() {
+                match line {
+                    Line::Context(_) => {
+                        new.push(next_line()?);
+                        position += 1;
+                    }
+                    Line::Delete(_) => {
+                        next_line()?;
+                        position += 1;
+                    }
+                    Line::Insert(text) => new.push((text.trim_end_matches('\n').to_string(), i)),
+                }
+            }
+        }
+        new.extend(old);
+        lines = new;
+    }
+    Ok(lines)
+}
+
+/// Compare two article versions. With [DiffGranularity::Rendered] both versions are rendered to
+/// HTML and compared word by word.
+pub(super) fn diff_versions(from: &str, to: &str, granularity: DiffGranularity) -> ArticleDiff {
+    let diff = match granularity {
+        DiffGranularity::Line => TextDiff::from_lines(from, to),
+        DiffGranularity::Word => TextDiff::from_words(from, to),
+        DiffGranularity::Rendered => {
+            let html = diff_html(&render_article_markdown(from), &render_article_markdown(to));
+            return ArticleDiff {
+                chunks: vec![],
+                html: Some(html),
+            };
+        }
+    };
+    let mut chunks: Vec<DiffChunk> = vec![];
+    for change in diff.iter_all_changes() {
+        let kind = match change.tag() {
+            ChangeTag::Equal => DiffChangeKind::Equal,
+            ChangeTag::Insert => DiffChangeKind::Insert,
+            ChangeTag::Delete => DiffChangeKind::Delete,
+        };
+        match chunks.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(change.value()),
+            _ => chunks.push(DiffChunk {
+                kind,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+    ArticleDiff { chunks, html: None }
+}
+
+/// Wrap inserted words in `<ins>` and deleted words in `<del>`. Tags are never wrapped, and
+/// deleted tags are dropped so that the result has the same structure as the new version.
+fn diff_html(from: &str, to: &str) -> String {
+    let from = tokenize_html(from);
+    let to = tokenize_html(to);
+    let diff = TextDiff::configure().diff_slices(&from, &to);
+    let mut html = String::new();
+    let mut open = None;
+    for change in diff.iter_all_changes() {
+        let value = change.value();
+        let is_tag = value.starts_with('<');
+        let mut wrapper = match change.tag() {
+            ChangeTag::Delete if is_tag => continue,
+            ChangeTag::Equal => None,
+            _ if is_tag => None,
+            ChangeTag::Insert => Some("ins"),
+            ChangeTag::Delete => Some("del"),
+        };
+        // Only wrap whitespace between changed words
+        if wrapper != open && value.trim().is_empty() {
+            if change.tag() == ChangeTag::Delete {
+                continue;
+            }
+            wrapper = None;
+        }
+        if open != wrapper {
+            if let Some(open) = open {
+                html.push_str(&format!("</{open}>"));
+            }
+            if let Some(wrapper) = wrapper {
+                html.push_str(&format!("<{wrapper}>"));
+            }
+            open = wrapper;
+        }
+        html.push_str(value);
+    }
+    if let Some(open) = open {
+        html.push_str(&format!("</{open}>"));
+    }
+    html
+}
+
+/// Split HTML into tags, words and whitespace.
+fn tokenize_html(html: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while let Some(first) = rest.chars().next() {
+        let len = if first == '<' {
+            rest.find('>').map_or(rest.len(), |i| i + 1)
+        } else if first.is_whitespace() {
+            rest.find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len())
+        } else {
+            rest.find(|c: char| c.is_whitespace() || c == '<')
+                .unwrap_or(rest.len())
+        };
+        let (token, remaining) = rest.split_at(len);
+        tokens.push(token);
+        rest = remaining;
+    }
+    tokens
+}
+
 pub(crate) fn generate_article_ap_id(title: &str, instance: &Instance) -> BackendResult<DbUrl> {
     Ok(Url::parse(&format!(
         "{}://{}/article/{}",
@@ -66,6 +263,8 @@ mod test {
                 previous_version_id: Default::default(),
                 published: Utc::now(),
                 pending: false,
+                awaiting_review: false,
+                bot: false,
             })
         };
         Ok([
@@ -99,4 +298,91 @@ mod test {
         assert_eq!("", generated);
         Ok(())
     }
+
+    #[test]
+    fn test_generate_from_snapshot() -> BackendResult<()> {
+        let mut edits = create_edits()?;
+        // edits covered by the snapshot are not applied again
+        edits[0].diff = "invalid".to_string();
+        let snapshot = || Some((2, "sda\n".to_string()));
+        assert_eq!(
+            "123\n",
+            generate_from_snapshot(&edits, &edits[2].hash, snapshot())?
+        );
+        assert_eq!(
+            "sda\n",
+            generate_from_snapshot(&edits, &edits[1].hash, snapshot())?
+        );
+        assert!(generate_from_snapshot(&edits, &EditVersion::new("invalid"), snapshot()).is_err());
+        // without snapshot all edits are applied
+        assert!(generate_from_snapshot(&edits, &edits[2].hash, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_versions() {
+        let diff = diff_versions("a\nb\nc\n", "a\nx\nc\n", DiffGranularity::Line);
+        let kinds: Vec<_> = diff
+            .chunks
+            .iter()
+            .map(|c| (c.kind, c.text.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (DiffChangeKind::Equal, "a\n"),
+                (DiffChangeKind::Delete, "b\n"),
+                (DiffChangeKind::Insert, "x\n"),
+                (DiffChangeKind::Equal, "c\n"),
+            ],
+            kinds
+        );
+
+        let diff = diff_versions("one two three", "one 2 three", DiffGranularity::Word);
+        assert_eq!(4, diff.chunks.len());
+        assert_eq!("two", diff.chunks[1].text);
+        assert_eq!("2", diff.chunks[2].text);
+
+        let diff = diff_versions(
+            "Some **bold** text\n",
+            "Some **strong** text\n\nNew paragraph\n",
+            DiffGranularity::Rendered,
+        );
+        assert!(diff.chunks.is_empty());
+        assert_eq!(
+            Some(
+                "<p>Some <strong><del>bold</del><ins>strong</ins></strong> text</p>\n<p><ins>New paragraph</ins></p>\n"
+                    .to_string()
+            ),
+            diff.html
+        );
+    }
+
+    #[test]
+    fn test_generate_blame() -> BackendResult<()> {
+        let texts = ["", "a\nb\nc\n", "a\nB\nc\nd\n", "a\nB\nd\n"];
+        let edits = texts
+            .windows(2)
+            .map(|t| {
+                let diff = create_patch(t[0], t[1]).to_string();
+                Ok(Edit {
+                    id: EditId(0),
+                    creator_id: PersonId(0),
+                    hash: EditVersion::new(&diff),
+                    ap_id: Url::parse("http://example.com")?.into(),
+                    diff,
+                    summary: String::new(),
+                    article_id: ArticleId(0),
+                    previous_version_id: Default::default(),
+                    published: Utc::now(),
+                    pending: false,
+                    awaiting_review: false,
+                    bot: false,
+                })
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+        let blame = generate_blame(&edits)?;
+        let expected = [("a", 0), ("B", 1), ("d", 1)].map(|(t, i)| (t.to_string(), i));
+        assert_eq!(expected.to_vec(), blame);
+        Ok(())
+    }
 }
@@ -4,7 +4,7 @@ use ibis::start;
 use ibis_api_client::{ApiClient, user::RegisterUserParams};
 use ibis_database::{
     common::instance::Options,
-    config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation},
+    config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation, IbisConfigMedia},
 };
 use log::LevelFilter;
 use std::{
@@ -83,6 +83,7 @@ impl AsyncTestContext for TestData {
 
 pub struct IbisInstance {
     pub api_client: ApiClient,
+    pub config: IbisConfig,
     db_path: String,
     db_handle: JoinHandle<()>,
     pub hostname: String,
@@ -92,7 +93,15 @@ impl IbisInstance {
     async fn new(name: &'static str, port: i32) -> Self {
         let db_path = Self::generate_db_path(name, port);
         Self::prepare_db(db_path.clone()).await;
-        Self::start(db_path, port, name).await
+        Self::start(db_path, port, name, None).await
+    }
+
+    /// Start an additional instance with its own database, which uses the federation domain
+    /// of an existing instance. Needs to be stopped manually.
+    pub async fn new_with_domain(name: &'static str, port: i32, domain: &str) -> Self {
+        let db_path = Self::generate_db_path(name, port);
+        Self::prepare_db(db_path.clone()).await;
+        Self::start(db_path, port, name, Some(domain.to_string())).await
     }
 
     /// Generate a unique db path for each postgres so that tests can run in parallel.
@@ -120,7 +129,7 @@ impl IbisInstance {
         .unwrap();
     }
 
-    async fn start(db_path: String, port: i32, username: &str) -> Self {
+    async fn start(db_path: String, port: i32, username: &str, domain: Option<String>) -> Self {
         let connection_url = format!("postgresql://ibis:password@/ibis?host={db_path}");
 
         let hostname = format!("localhost:{port}");
@@ -130,7 +139,11 @@ impl IbisInstance {
                 ..Default::default()
             },
             federation: IbisConfigFederation {
-                domain: Some(hostname.clone()),
+                domain: Some(domain.unwrap_or(hostname.clone())),
+                ..Default::default()
+            },
+            media: IbisConfigMedia {
+                upload_dir: format!("{db_path}/media"),
                 ..Default::default()
             },
             options: Options {
@@ -141,9 +154,10 @@ impl IbisInstance {
         };
         let api_client = ApiClient::new(Some(hostname.clone()));
         let (tx, rx) = oneshot::channel::<()>();
+        let server_config = config.clone();
         let db_handle = tokio::task::spawn(async move {
             let hostname = format!("127.0.0.1:{port}");
-            start(config, Some(hostname.parse().unwrap()), Some(tx))
+            start(server_config, Some(hostname.parse().unwrap()), Some(tx))
                 .await
                 .unwrap();
         });
@@ -158,13 +172,14 @@ impl IbisInstance {
         api_client.register(params).await.unwrap();
         Self {
             api_client,
+            config,
             db_path,
             db_handle,
             hostname,
         }
     }
 
-    async fn stop(self) {
+    pub async fn stop(self) {
         self.db_handle.abort();
         self.db_handle.await.ok();
         Self::stop_internal(self.db_path).await;
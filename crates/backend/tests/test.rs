@@ -2,31 +2,82 @@
 
 mod common;
 
-use crate::common::{TEST_ARTICLE_DEFAULT_TEXT, TestData};
+use crate::common::{IbisInstance, TEST_ARTICLE_DEFAULT_TEXT, TestData};
 use anyhow::Result;
+use chrono::{TimeDelta, Utc};
+use flate2::read::ZlibDecoder;
+use http::StatusCode;
+use ibis::{
+    archive::{ArchiveStats, export_archive, import_archive},
+    mediawiki::{MediaWikiImportOptions, MediaWikiImportStats, import_mediawiki_dump},
+};
 use ibis_api_client::{
+    ApiClient,
     article::{
+        CompareVersionsParams,
         CreateArticleParams,
         EditArticleParams,
         ForkArticleParams,
         GetArticleParams,
+        GetBacklinksParams,
+        GetEditList,
         ListArticlesParams,
+        ListRecentChangesParams,
+        ListWantedArticlesParams,
+        MoveArticleParams,
         ProtectArticleParams,
+        RemoveArticleParams,
+        RequireReviewParams,
+        RevertArticleParams,
+        ReviewEditParams,
+    },
+    comment::{CreateCommentParams, EditCommentParams, ListCommentsParams},
+    instance::{
+        BlockedContent,
+        RemoveFederationListParams,
+        SearchArticleParams,
+        UpdateFederationListParams,
+        UpdateInstanceParams,
+    },
+    modlog::ListModlogParams,
+    report::{CreateReportParams, ListReportsParams, ResolveReportParams},
+    user::{
+        BanPersonParams,
+        ChangePasswordParams,
+        CreateApiTokenParams,
+        GetUserParams,
+        LoginUserParams,
+        RegisterUserParams,
+        SetUserRoleParams,
+        UnbanPersonParams,
     },
-    comment::{CreateCommentParams, EditCommentParams},
-    instance::SearchArticleParams,
-    user::{GetUserParams, LoginUserParams, RegisterUserParams},
 };
-use ibis_database::common::{
-    article::ArticleView,
-    notifications::ApiNotificationData,
-    utils::extract_domain,
+use ibis_database::{
+    common::{
+        api_token::ApiTokenScope,
+        article::{ArticleView, DiffChangeKind, DiffGranularity},
+        federation_list::FederationMode,
+        instance::SiteView,
+        modlog::ModAction,
+        notifications::ApiNotificationData,
+        pagination::PaginationCursor,
+        report::ReportStatus,
+        totp::TOTP_REQUIRED_ERROR,
+        user::Role,
+        utils::extract_domain,
+    },
+    impls::{IbisContext, edit_snapshot::EditSnapshot},
 };
+use image::{GenericImageView, ImageFormat, RgbImage};
 use pretty_assertions::assert_eq;
 use retry_future::{LinearRetryStrategy, RetryFuture, RetryPolicy};
-use std::time::Duration;
+use std::{
+    io::{Cursor, Read},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use test_context::test_context;
 use tokio::time::sleep;
+use totp_rs::TOTP;
 use url::Url;
 
 fn create_test_article_params() -> CreateArticleParams {
@@ -83,19 +134,27 @@ async fn api_test_create_read_and_edit_local_article(
 
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        instance_id: None,
+        only_local: None,
+        cursor: None,
+        limit: None,
+        federated: None,
     };
-    let search_res = alpha.search(&search_params).await.unwrap();
+    let search_res = alpha.search(&search_params).await.unwrap().results;
     assert_eq!(1, search_res.len());
-    assert_eq!(edit_res.article, search_res[0]);
+    assert_eq!(Some(edit_res.article.clone()), search_res[0].article);
 
     let list_articles = alpha
         .list_articles(ListArticlesParams {
             only_local: Some(false),
             instance_id: None,
             include_removed: None,
+            cursor: None,
+            limit: None,
         })
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(2, list_articles.len());
     assert_eq!(edit_res.article, list_articles[0]);
 
@@ -364,6 +423,74 @@ async fn api_test_edit_remote_article(TestData(alpha, beta, gamma): &mut TestDat
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_revert_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let beta_instance = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create and edit article on beta
+    let create_params = create_test_article_params();
+    let create_res = beta.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Vandalism\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    beta.edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // revert remote article from alpha
+    let get_article_data = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        domain: Some(beta_instance.domain),
+        id: None,
+    };
+    let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
+    assert_eq!(edit_params.new_text, get_res.article.text);
+    let revert_params = RevertArticleParams {
+        article_id: get_res.article.id,
+        version: create_res.latest_version.clone(),
+        reason: None,
+    };
+    // only moderators can revert
+    assert!(alpha.revert_article(&revert_params).await.is_err());
+    let admin_login = || LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    alpha.login(admin_login()).await.unwrap();
+    let revert_res = alpha.revert_article(&revert_params).await.unwrap();
+    assert_eq!(None, revert_res);
+
+    // revert is federated to beta
+    let get_res = beta.get_article(get_article_data).await.unwrap();
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT, get_res.article.text);
+    let edits = beta.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(3, edits.len());
+    assert_eq!(
+        format!("Revert to version {}", create_res.latest_version.0),
+        edits[2].edit.summary
+    );
+
+    // reverting to the current version fails
+    beta.login(admin_login()).await.unwrap();
+    let revert_params = RevertArticleParams {
+        article_id: get_res.article.id,
+        version: create_res.latest_version,
+        reason: None,
+    };
+    assert!(beta.revert_article(&revert_params).await.is_err());
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> Result<()> {
@@ -403,7 +530,11 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
         edit_res.three_way_merge
     );
 
-    let notifications = alpha.notifications_list().await.unwrap();
+    let notifications = alpha
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(1, notifications.len());
     let ApiNotificationData::EditConflict {
         conflict_id,
@@ -433,6 +564,94 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_conflict_long_history(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+
+    // create enough edits so that a snapshot is stored
+    let mut text = TEST_ARTICLE_DEFAULT_TEXT.to_string();
+    let mut previous_version_id = create_res.latest_version;
+    for i in 0..120 {
+        text.push_str(&format!("line {i}\n"));
+        let edit_params = EditArticleParams {
+            article_id: create_res.article.id,
+            new_text: text.clone(),
+            summary: format!("edit {i}"),
+            previous_version_id,
+            resolve_conflict_id: None,
+        };
+        let edit_res = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        previous_version_id = edit_res.latest_version;
+    }
+    let edits = alpha
+        .get_article_edits(create_res.article.id)
+        .await
+        .unwrap();
+    assert_eq!(121, edits.len());
+    let old_text = |until: usize| {
+        let lines: String = (0..until).map(|i| format!("line {i}\n")).collect();
+        format!("{TEST_ARTICLE_DEFAULT_TEXT}{lines}")
+    };
+
+    // snapshot is stored when the checkpoint edit is created
+    let context = IbisContext::init(alpha.config.clone(), true).map_err(|e| e.0)?;
+    let checkpoint = edits[99].edit.id;
+    let snapshot = EditSnapshot::read(checkpoint, &context).map_err(|e| e.0)?;
+    assert_eq!(old_text(99), snapshot.text);
+
+    // only edits published before the checkpoint invalidate it
+    let invalidate =
+        |published| EditSnapshot::invalidate(create_res.article.id, published, &context);
+    assert_eq!(0, invalidate(edits[110].edit.published).map_err(|e| e.0)?);
+    assert_eq!(1, invalidate(edits[50].edit.published).map_err(|e| e.0)?);
+    assert!(EditSnapshot::read(checkpoint, &context).is_err());
+    EditSnapshot::create_missing(create_res.article.id, &context).map_err(|e| e.0)?;
+    let snapshot = EditSnapshot::read(checkpoint, &context).map_err(|e| e.0)?;
+    assert_eq!(old_text(99), snapshot.text);
+
+    // edit based on an old version is merged using the generated ancestor text
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: format!("header\n{}", old_text(110)),
+        summary: "header".to_string(),
+        previous_version_id: edits[110].edit.hash.clone(),
+        resolve_conflict_id: None,
+    };
+    assert_eq!(None, alpha.edit_article(&edit_params).await.unwrap());
+    let get_params = GetArticleParams {
+        title: None,
+        domain: None,
+        id: Some(create_res.article.id),
+    };
+    let get_res = alpha.get_article(get_params.clone()).await.unwrap();
+    assert_eq!(format!("header\n{text}"), get_res.article.text);
+
+    // revert also generates old versions, login as admin for permission
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    alpha.login(params).await.unwrap();
+    let revert_params = RevertArticleParams {
+        article_id: create_res.article.id,
+        version: edits[105].edit.hash.clone(),
+        reason: None,
+    };
+    assert_eq!(None, alpha.revert_article(&revert_params).await.unwrap());
+    let get_res = alpha.get_article(get_params).await.unwrap();
+    assert_eq!(old_text(105), get_res.article.text);
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 #[ignore]
@@ -511,7 +730,11 @@ async fn api_test_federated_edit_conflict(
     assert!(!edit_res.article.local);
 
     assert_eq!(1, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(1, notifications.len());
     let ApiNotificationData::EditConflict {
         conflict_id,
@@ -541,7 +764,11 @@ async fn api_test_federated_edit_conflict(
     assert!(gamma_edits.iter().all(|e| !e.edit.pending));
 
     assert_eq!(0, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(0, notifications.len());
 
     Ok(())
@@ -669,9 +896,462 @@ async fn api_test_fork_article(TestData(alpha, beta, _): &mut TestData) -> Resul
     // now search returns two articles for this title (original and forked)
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        instance_id: None,
+        only_local: None,
+        cursor: None,
+        limit: None,
+        federated: None,
+    };
+    let search_res = beta.search(&search_params).await.unwrap().results;
+    assert_eq!(2, search_res.len());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_move_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let beta_instance = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create article on beta, it gets federated to alpha
+    let create_params = create_test_article_params();
+    let create_res = beta.create_article(&create_params).await.unwrap();
+    let get_params = |title: &str| GetArticleParams {
+        title: Some(title.to_string()),
+        domain: Some(beta_instance.domain.clone()),
+        id: None,
+    };
+    let alpha_article = alpha
+        .get_article(get_params(&create_params.title))
+        .await
+        .unwrap();
+    let list_params = ListArticlesParams {
+        only_local: Some(false),
+        ..Default::default()
+    };
+    let alpha_articles = alpha
+        .list_articles(list_params.clone())
+        .await
+        .unwrap()
+        .items;
+
+    // move the article
+    let new_title = "Manu Chao (musician)".to_string();
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: new_title.clone(),
+    };
+    let move_res = beta.move_article(&move_params).await.unwrap();
+    assert_eq!(new_title, move_res.article.title);
+    assert_eq!(create_res.article.id, move_res.article.id);
+    assert_ne!(create_res.article.ap_id, move_res.article.ap_id);
+
+    // old title redirects to the new one
+    let get_res = beta
+        .get_article(get_params(&create_params.title))
+        .await
+        .unwrap();
+    assert_eq!(move_res.article, get_res.article);
+
+    // alpha renamed its copy instead of creating a new article
+    let get_res = alpha.get_article(get_params(&new_title)).await.unwrap();
+    assert_eq!(alpha_article.article.id, get_res.article.id);
+    assert_eq!(move_res.article.ap_id, get_res.article.ap_id);
+    let get_res = alpha
+        .get_article(get_params(&create_params.title))
+        .await
+        .unwrap();
+    assert_eq!(alpha_article.article.id, get_res.article.id);
+    let list_res = alpha.list_articles(list_params).await.unwrap().items;
+    assert_eq!(alpha_articles.len(), list_res.len());
+
+    // old ap_id can still be resolved
+    let resolve_res = alpha
+        .resolve_article(create_res.article.ap_id.clone().into())
+        .await
+        .unwrap();
+    assert_eq!(alpha_article.article.id, resolve_res.article.id);
+
+    // move back to the original title
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: create_params.title.clone(),
     };
-    let search_res = beta.search(&search_params).await.unwrap();
+    let move_res = beta.move_article(&move_params).await.unwrap();
+    assert_eq!(create_res.article.ap_id, move_res.article.ap_id);
+    let get_res = alpha
+        .get_article(get_params(&create_params.title))
+        .await
+        .unwrap();
+    assert_eq!(alpha_article.article.id, get_res.article.id);
+    assert_eq!(create_params.title, get_res.article.title);
+
+    // cannot move to the title of a removed article
+    let removed_params = CreateArticleParams {
+        title: "Mano Negra".to_string(),
+        ..create_test_article_params()
+    };
+    let removed = beta.create_article(&removed_params).await.unwrap();
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    beta.login(params).await.unwrap();
+    let remove_params = RemoveArticleParams {
+        article_id: removed.article.id,
+        remove: true,
+        reason: None,
+    };
+    beta.remove_article(&remove_params).await.unwrap();
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: removed_params.title,
+    };
+    let err = beta.move_article(&move_params).await.unwrap_err();
+    assert_eq!(
+        "A removed article with this title already exists",
+        err.message()
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_backlinks(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let target = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let backlinks_params = GetBacklinksParams {
+        article_id: target.article.id,
+        ..Default::default()
+    };
+    assert!(
+        alpha
+            .get_backlinks(&backlinks_params)
+            .await?
+            .items
+            .is_empty()
+    );
+    let orphaned = alpha
+        .list_orphaned_articles(&Default::default())
+        .await?
+        .items;
+    assert!(orphaned.iter().any(|a| a.id == target.article.id));
+
+    // create an article which links to the existing one, and to a missing one
+    let source_params = CreateArticleParams {
+        title: "Clandestino".to_string(),
+        text: format!(
+            "[[Manu Chao@{host}]] and [[Mano Negra@{host}]]\n",
+            host = alpha.hostname
+        ),
+        ..create_test_article_params()
+    };
+    let source = alpha.create_article(&source_params).await.unwrap();
+    let backlinks = alpha.get_backlinks(&backlinks_params).await?.items;
+    assert_eq!(1, backlinks.len());
+    assert_eq!(source.article.id, backlinks[0].id);
+    assert_eq!(source.article.title, backlinks[0].title);
+    let orphaned = alpha
+        .list_orphaned_articles(&Default::default())
+        .await?
+        .items;
+    assert!(!orphaned.iter().any(|a| a.id == target.article.id));
+    assert!(orphaned.iter().any(|a| a.id == source.article.id));
+    let wanted = alpha.list_wanted_articles(&Default::default()).await?.items;
+    assert_eq!(1, wanted.len());
+    assert_eq!("Mano Negra", wanted[0].title);
+    assert_eq!(1, wanted[0].link_count);
+
+    // links are updated after edit
+    let edit_params = EditArticleParams {
+        article_id: source.article.id,
+        new_text: "no more links\n".to_string(),
+        summary: "remove links".to_string(),
+        previous_version_id: source.latest_version,
+        resolve_conflict_id: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    assert!(
+        alpha
+            .get_backlinks(&backlinks_params)
+            .await?
+            .items
+            .is_empty()
+    );
+    assert!(
+        alpha
+            .list_wanted_articles(&Default::default())
+            .await?
+            .items
+            .is_empty()
+    );
+
+    // links to the old title count for the moved article
+    let move_params = MoveArticleParams {
+        article_id: target.article.id,
+        new_title: "Manu Chao (singer)".to_string(),
+    };
+    alpha.move_article(&move_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        new_text: format!(
+            "[[Manu Chao@{host}]], [[Mano Negra@{host}]] and [[Radio Bemba@{host}]]\n",
+            host = alpha.hostname
+        ),
+        summary: "link old title".to_string(),
+        previous_version_id: edit_res.latest_version,
+        ..edit_params
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let orphaned = alpha
+        .list_orphaned_articles(&Default::default())
+        .await?
+        .items;
+    assert!(!orphaned.iter().any(|a| a.id == target.article.id));
+
+    // wanted articles are paginated
+    let mut params = ListWantedArticlesParams {
+        limit: Some(1),
+        ..Default::default()
+    };
+    let wanted = alpha.list_wanted_articles(&params).await?;
+    assert_eq!(1, wanted.items.len());
+    assert_eq!("Mano Negra", wanted.items[0].title);
+    params.cursor = wanted.next_page;
+    assert!(params.cursor.is_some());
+    let wanted = alpha.list_wanted_articles(&params).await?;
+    assert_eq!(1, wanted.items.len());
+    assert_eq!("Radio Bemba", wanted.items[0].title);
+    assert!(wanted.next_page.is_none());
+
+    // but not if the linking article is removed
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    alpha.login(params).await.unwrap();
+    let remove_params = RemoveArticleParams {
+        article_id: source.article.id,
+        remove: true,
+        reason: None,
+    };
+    alpha.remove_article(&remove_params).await.unwrap();
+    let orphaned = alpha
+        .list_orphaned_articles(&Default::default())
+        .await?
+        .items;
+    assert!(orphaned.iter().any(|a| a.id == target.article.id));
+    assert!(
+        alpha
+            .list_wanted_articles(&Default::default())
+            .await?
+            .items
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_search(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let beta_instance = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // one article mentions the term in the text, the other in the title
+    let text_params = CreateArticleParams {
+        title: "Busking".to_string(),
+        text: "Street musicians <often> play guitars for money\n".to_string(),
+        ..create_test_article_params()
+    };
+    let text_res = alpha.create_article(&text_params).await.unwrap();
+    let title_params = CreateArticleParams {
+        title: "Guitar".to_string(),
+        text: "A stringed instrument\n".to_string(),
+        ..create_test_article_params()
+    };
+    let title_res = alpha.create_article(&title_params).await.unwrap();
+    let remote_params = CreateArticleParams {
+        title: "Electric guitar".to_string(),
+        text: "Needs an amplifier\n".to_string(),
+        ..create_test_article_params()
+    };
+    beta.create_article(&remote_params).await.unwrap();
+
+    // search is stemmed, and title matches are ranked first
+    let mut search_params = SearchArticleParams {
+        query: "guitar".to_string(),
+        instance_id: None,
+        only_local: Some(true),
+        cursor: None,
+        limit: None,
+        federated: None,
+    };
+    let search_res = alpha.search(&search_params).await.unwrap().results;
     assert_eq!(2, search_res.len());
+    assert_eq!(Some(title_res.article.clone()), search_res[0].article);
+    assert_eq!(Some(text_res.article.clone()), search_res[1].article);
+    assert_eq!(
+        "Street musicians &lt;often&gt; play <b>guitars</b> for money",
+        search_res[1].snippet
+    );
+
+    // pagination
+    search_params.limit = Some(1);
+    let first_page = alpha.search(&search_params).await.unwrap();
+    assert_eq!(1, first_page.results.len());
+    assert_eq!(
+        Some(title_res.article.clone()),
+        first_page.results[0].article
+    );
+    search_params.cursor = first_page.next_page;
+    assert!(search_params.cursor.is_some());
+    let second_page = alpha.search(&search_params).await.unwrap();
+    assert_eq!(1, second_page.results.len());
+    assert_eq!(Some(text_res.article), second_page.results[0].article);
+    assert!(second_page.next_page.is_none());
+
+    // filter by instance, remote article gets federated after following
+    search_params.limit = None;
+    search_params.cursor = None;
+    search_params.only_local = None;
+    search_params.instance_id = Some(beta_instance.id);
+    let search_res = alpha.search(&search_params).await.unwrap().results;
+    assert_eq!(1, search_res.len());
+    assert_eq!(remote_params.title, search_res[0].title);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_federated_search(TestData(alpha, _, gamma): &mut TestData) -> Result<()> {
+    // alpha knows about gamma, but doesn't receive its new articles
+    let gamma_instance = alpha
+        .resolve_instance(Url::parse(&format!("http://{}", &gamma.hostname))?)
+        .await
+        .unwrap();
+    // wait until the articles of gamma are fetched in the background
+    RetryFuture::new(
+        || async {
+            let params = ListArticlesParams {
+                instance_id: Some(gamma_instance.id),
+                ..Default::default()
+            };
+            match alpha.list_articles(params).await {
+                Ok(a) if !a.items.is_empty() => Ok(()),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    let create_res = gamma
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+
+    let mut search_params = SearchArticleParams {
+        query: "example".to_string(),
+        instance_id: None,
+        only_local: None,
+        cursor: None,
+        limit: None,
+        federated: None,
+    };
+    let search_res = alpha.search(&search_params).await.unwrap();
+    assert!(search_res.results.is_empty());
+
+    // federated search finds the article on gamma, without storing it locally
+    search_params.federated = Some(true);
+    let search_res = alpha.search(&search_params).await.unwrap();
+    assert!(search_res.timed_out.is_empty());
+    assert_eq!(1, search_res.results.len());
+    let result = &search_res.results[0];
+    assert_eq!(create_res.article.ap_id, result.ap_id);
+    assert_eq!(create_res.article.title, result.title);
+    assert_eq!(gamma.hostname, result.origin);
+    assert_eq!(None, result.article);
+
+    // after resolving the article it is returned from the local database instead
+    let resolve_res = alpha.resolve_article(result.ap_id.clone().into()).await?;
+    let search_res = alpha.search(&search_params).await.unwrap();
+    assert_eq!(1, search_res.results.len());
+    assert_eq!(Some(resolve_res.article), search_res.results[0].article);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_media_upload(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let mut png = Cursor::new(vec![]);
+    RgbImage::new(400, 300).write_to(&mut png, ImageFormat::Png)?;
+    let png = png.into_inner();
+    let fetch = |hostname: String, path: String| async move {
+        let res = reqwest::get(format!("http://{hostname}{path}")).await?;
+        res.error_for_status()?.bytes().await
+    };
+
+    let media = alpha.upload_media(png.clone()).await.unwrap();
+    assert_eq!("image/png", media.mime_type);
+    assert!(media.filename.ends_with(".png"));
+    assert!(media.local);
+
+    // same file is only stored once
+    let media_again = alpha.upload_media(png.clone()).await.unwrap();
+    assert_eq!(media.id, media_again.id);
+    assert!(alpha.upload_media(b"plain text".to_vec()).await.is_err());
+
+    // uploaded file and thumbnail are served
+    let file = fetch(alpha.hostname.clone(), media.path()).await?;
+    assert_eq!(png, file);
+    let thumbnail = fetch(alpha.hostname.clone(), media.thumbnail_path()).await?;
+    let thumbnail = image::load_from_memory(&thumbnail)?;
+    assert_eq!((256, 192), thumbnail.dimensions());
+
+    // embed in article, remote copy on beta can display it
+    let create_params = CreateArticleParams {
+        text: format!("![test image]({})\n", media.path()),
+        ..create_test_article_params()
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    beta.resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let file = fetch(beta.hostname.clone(), media.path()).await?;
+    assert_eq!(png, file);
+
+    // only admin can delete media
+    assert!(alpha.delete_media(media.id).await.is_err());
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    alpha.login(params).await.unwrap();
+    alpha.delete_media(media.id).await.unwrap();
+    assert!(
+        alpha
+            .list_media(&Default::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+    assert!(fetch(alpha.hostname.clone(), media.path()).await.is_err());
 
     Ok(())
 }
@@ -692,6 +1372,7 @@ async fn api_test_user_registration_login(TestData(alpha, _, _): &mut TestData)
     let login_data = LoginUserParams {
         username_or_email: username.to_string(),
         password: "asd123".to_string(),
+        totp_code: None,
     };
     let invalid_login = alpha.login(login_data).await;
     assert!(invalid_login.is_err());
@@ -699,6 +1380,7 @@ async fn api_test_user_registration_login(TestData(alpha, _, _): &mut TestData)
     let login_data = LoginUserParams {
         username_or_email: username.to_string(),
         password: password.to_string(),
+        totp_code: None,
     };
     alpha.login(login_data).await.unwrap();
 
@@ -740,8 +1422,9 @@ async fn api_test_user_profile(TestData(alpha, beta, _): &mut TestData) -> Resul
         domain: Some(domain),
     };
     let user = beta.get_user(params).await.unwrap();
-    assert_eq!("alpha", user.username);
-    assert!(!user.local);
+    assert_eq!("alpha", user.person.username);
+    assert!(!user.person.local);
+    assert_eq!(None, user.role);
 
     Ok(())
 }
@@ -758,6 +1441,7 @@ async fn api_test_lock_article(TestData(alpha, _, gamma): &mut TestData) -> Resu
     let lock_params = ProtectArticleParams {
         article_id: create_res.article.id,
         protected: true,
+        reason: None,
     };
     let lock_res = alpha.protect_article(&lock_params).await;
     assert!(lock_res.is_err());
@@ -766,6 +1450,7 @@ async fn api_test_lock_article(TestData(alpha, _, gamma): &mut TestData) -> Resu
     let params = LoginUserParams {
         username_or_email: "ibis".to_string(),
         password: "ibis".to_string(),
+        totp_code: None,
     };
     alpha.login(params).await.unwrap();
     let lock_res = alpha.protect_article(&lock_params).await.unwrap();
@@ -795,7 +1480,11 @@ async fn api_test_synchronize_instances(TestData(alpha, beta, gamma): &mut TestD
     beta.resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
         .await
         .unwrap();
-    let beta_instances = beta.list_instances().await.unwrap();
+    let beta_instances = beta
+        .list_instances(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(2, beta_instances.len());
 
     // fetch beta instance on gamma
@@ -807,10 +1496,10 @@ async fn api_test_synchronize_instances(TestData(alpha, beta, gamma): &mut TestD
     // wait until instance collection is fetched
     let gamma_instances = RetryFuture::new(
         || async {
-            match gamma.list_instances().await {
+            match gamma.list_instances(&Default::default()).await {
                 Err(_) => Err(RetryPolicy::<String>::Retry(None)),
-                Ok(i) if i.len() < 3 => Err(RetryPolicy::Retry(None)),
-                Ok(i) => Ok(i),
+                Ok(i) if i.items.len() < 3 => Err(RetryPolicy::Retry(None)),
+                Ok(i) => Ok(i.items),
             }
         },
         LinearRetryStrategy::new(),
@@ -839,12 +1528,12 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
     let create_params = create_test_article_params();
     let create_res = alpha.create_article(&create_params).await.unwrap();
 
-    let list_alpha = alpha.list_articles(Default::default()).await.unwrap();
+    let list_alpha = alpha.list_articles(Default::default()).await.unwrap().items;
     let article_to_remove_id = list_alpha[0].id;
     // count also includes auto-created main page
     assert_eq!(2, list_alpha.len());
     assert_eq!(article_to_remove_id, create_res.article.id);
-    let list_beta = beta.list_articles(Default::default()).await.unwrap();
+    let list_beta = beta.list_articles(Default::default()).await.unwrap().items;
     // count also includes main pages from alpha and beta
     assert_eq!(3, list_beta.len());
     assert_eq!(create_res.article.ap_id, list_beta[0].ap_id);
@@ -853,13 +1542,16 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
     let params = LoginUserParams {
         username_or_email: "ibis".to_string(),
         password: "ibis".to_string(),
+        totp_code: None,
     };
     alpha.login(params).await.unwrap();
 
-    alpha
-        .remove_article(article_to_remove_id, true)
-        .await
-        .unwrap();
+    let mut remove_params = RemoveArticleParams {
+        article_id: article_to_remove_id,
+        remove: true,
+        reason: None,
+    };
+    alpha.remove_article(&remove_params).await.unwrap();
 
     let params = GetArticleParams {
         title: Some(create_res.article.title),
@@ -870,7 +1562,7 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
     // cannot get the article
     sleep(Duration::from_secs(1)).await;
     assert!(beta.get_article(params.clone()).await.is_err());
-    let list_beta = beta.list_articles(Default::default()).await?;
+    let list_beta = beta.list_articles(Default::default()).await?.items;
     assert_eq!(2, list_beta.len());
 
     // except as admin with include_removed
@@ -880,18 +1572,17 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
             ..Default::default()
         })
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(2, list_all.len());
 
     // restore article
-    alpha
-        .remove_article(article_to_remove_id, false)
-        .await
-        .unwrap();
+    remove_params.remove = false;
+    alpha.remove_article(&remove_params).await.unwrap();
 
     // now it can be viewed again
     assert!(alpha.get_article(params).await.is_ok());
-    let list_beta = beta.list_articles(Default::default()).await?;
+    let list_beta = beta.list_articles(Default::default()).await?.items;
     assert_eq!(3, list_beta.len());
 
     Ok(())
@@ -1089,3 +1780,2128 @@ async fn api_test_create_remote_article(TestData(alpha, beta, _): &mut TestData)
 
     Ok(())
 }
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_mediawiki_import(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let dump = r#"<mediawiki xmlns="http://www.mediawiki.org/xml/export-0.11/" version="0.11">
+  <siteinfo>
+    <sitename>Test Wiki</sitename>
+  </siteinfo>
+  <page>
+    <title>Home_page</title>
+    <ns>0</ns>
+    <revision>
+      <timestamp>2020-01-01T10:00:00Z</timestamp>
+      <contributor><username>Alice</username></contributor>
+      <comment>first version</comment>
+      <text xml:space="preserve">Welcome &amp; hello.</text>
+    </revision>
+    <revision>
+      <timestamp>2021-06-15T12:30:00Z</timestamp>
+      <contributor><username>Bob</username></contributor>
+      <comment>add section</comment>
+      <text xml:space="preserve">Welcome &amp; hello.{{Stub}}
+
+== History ==
+Read about the '''other''' [[other_page|page]].</text>
+    </revision>
+  </page>
+  <page>
+    <title>Broken page</title>
+    <ns>0</ns>
+    <revision>
+      <timestamp>yesterday</timestamp>
+      <text xml:space="preserve">Invalid timestamp</text>
+    </revision>
+  </page>
+  <page>
+    <title>Other page</title>
+    <ns>0</ns>
+    <revision>
+      <timestamp>2022-03-01T08:00:00Z</timestamp>
+      <text xml:space="preserve">Back to [[Home page]].</text>
+    </revision>
+  </page>
+  <page>
+    <title>Main Page</title>
+    <ns>0</ns>
+    <revision>
+      <timestamp>2022-03-01T08:00:00Z</timestamp>
+      <text xml:space="preserve">Exists already</text>
+    </revision>
+  </page>
+  <page>
+    <title>Talk:Home page</title>
+    <ns>1</ns>
+    <revision>
+      <timestamp>2022-03-01T08:00:00Z</timestamp>
+      <text xml:space="preserve">Discussion</text>
+    </revision>
+  </page>
+  <page>
+    <title>Home</title>
+    <ns>0</ns>
+    <redirect title="Home page" />
+    <revision>
+      <timestamp>2022-03-01T08:00:00Z</timestamp>
+      <text xml:space="preserve">#REDIRECT [[Home page]]</text>
+    </revision>
+  </page>
+</mediawiki>"#;
+    let path = std::env::temp_dir().join(format!(
+        "ibis-mediawiki-{}.xml",
+        alpha.hostname.replace(':', "-")
+    ));
+    std::fs::write(&path, dump)?;
+
+    let context = IbisContext::init(alpha.config.clone(), true).map_err(|e| e.0)?;
+    let options = MediaWikiImportOptions {
+        all_revisions: true,
+        username: "ibis".to_string(),
+    };
+    let stats = import_mediawiki_dump(&path, &options, &context)
+        .await
+        .map_err(|e| e.0)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(
+        MediaWikiImportStats {
+            articles: 2,
+            edits: 3,
+            redirects: 1,
+            skipped: 2,
+        },
+        stats
+    );
+
+    // all revisions are stored with original timestamp and summary, and links are converted
+    let home_page = alpha
+        .get_article(GetArticleParams {
+            title: Some("Home page".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(
+        format!(
+            "Welcome & hello.\n\n## History\n\nRead about the **other** [[Other page@{}|page]].\n",
+            alpha.hostname
+        ),
+        home_page.article.text
+    );
+    let edits = alpha.get_article_edits(home_page.article.id).await?;
+    assert_eq!(2, edits.len());
+    assert_eq!("first version", edits[0].edit.summary);
+    assert_eq!("add section", edits[1].edit.summary);
+    assert_eq!(
+        "2021-06-15T12:30:00+00:00",
+        edits[1].edit.published.to_rfc3339()
+    );
+    assert_eq!("ibis", edits[1].creator.username);
+
+    // existing article is not overwritten
+    let main_page = alpha
+        .get_article(GetArticleParams {
+            title: Some("Main Page".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert_ne!("Exists already\n", main_page.article.text);
+
+    // links between imported articles are tracked as backlinks
+    let backlinks = alpha
+        .get_backlinks(&GetBacklinksParams {
+            article_id: home_page.article.id,
+            ..Default::default()
+        })
+        .await?
+        .items;
+    assert_eq!(1, backlinks.len());
+    assert_eq!("Other page", backlinks[0].title);
+
+    // redirect page leads to the target article
+    let redirect = alpha
+        .get_article(GetArticleParams {
+            title: Some("Home".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(home_page.article.id, redirect.article.id);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_archive_export_import(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // create article with multiple edits and comments
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Manu Chao is a French musician.\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let comment = alpha
+        .create_comment(&CreateCommentParams {
+            content: "first comment".to_string(),
+            article_id: create_res.article.id,
+            parent_id: None,
+        })
+        .await?;
+    alpha
+        .create_comment(&CreateCommentParams {
+            content: "reply".to_string(),
+            article_id: create_res.article.id,
+            parent_id: Some(comment.comment.id),
+        })
+        .await?;
+
+    let path = std::env::temp_dir().join(format!(
+        "ibis-archive-{}.tar",
+        alpha.hostname.replace(':', "-")
+    ));
+    let alpha_context = IbisContext::init(alpha.config.clone(), true).map_err(|e| e.0)?;
+    let stats = export_archive(&path, &alpha_context).map_err(|e| e.0)?;
+    assert_eq!(
+        ArchiveStats {
+            articles: 2,
+            edits: 3,
+            comments: 2,
+            skipped: 0,
+        },
+        stats
+    );
+
+    // restore on a new instance with the same domain
+    let port: i32 = extract_port(&alpha.hostname) + 1000;
+    let restored = IbisInstance::new_with_domain("restored", port, &alpha.hostname).await;
+    let restored_context = IbisContext::init(restored.config.clone(), true).map_err(|e| e.0)?;
+    let stats = import_archive(&path, &restored_context)
+        .await
+        .map_err(|e| e.0)?;
+    std::fs::remove_file(&path)?;
+    // main page exists already with the same edit, so only article is restored
+    assert_eq!(
+        ArchiveStats {
+            articles: 2,
+            edits: 2,
+            comments: 2,
+            skipped: 0,
+        },
+        stats
+    );
+
+    // edits have identical hashes and ap_ids
+    let get_params = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        ..Default::default()
+    };
+    let article = alpha.get_article(get_params.clone()).await?;
+    let restored_article = restored.get_article(get_params).await?;
+    assert_eq!(article.article.ap_id, restored_article.article.ap_id);
+    assert_eq!(article.article.text, restored_article.article.text);
+    assert_eq!(article.latest_version, restored_article.latest_version);
+    let edits = alpha.get_article_edits(article.article.id).await?;
+    let restored_edits = restored
+        .get_article_edits(restored_article.article.id)
+        .await?;
+    assert_eq!(2, restored_edits.len());
+    for (a, b) in edits.iter().zip(&restored_edits) {
+        assert_eq!(a.edit.hash, b.edit.hash);
+        assert_eq!(a.edit.ap_id, b.edit.ap_id);
+        assert_eq!(a.edit.summary, b.edit.summary);
+        assert_eq!(a.creator.ap_id, b.creator.ap_id);
+    }
+
+    // comment thread is restored
+    let comments = restored_article.comments;
+    assert_eq!(2, comments.len());
+    let parent = comments
+        .iter()
+        .find(|c| c.comment.content == "first comment")
+        .unwrap();
+    let reply = comments
+        .iter()
+        .find(|c| c.comment.content == "reply")
+        .unwrap();
+    assert_eq!(comment.comment.ap_id, parent.comment.ap_id);
+    assert_eq!(Some(parent.comment.id), reply.comment.parent_id);
+    assert_eq!(1, reply.comment.depth);
+
+    restored.stop().await;
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_blame(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: format!("{TEST_ARTICLE_DEFAULT_TEXT}second line\n"),
+        summary: "add line".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // each line is attributed to the edit which inserted it
+    let blame = alpha.get_article_blame(create_res.article.id).await?;
+    assert_eq!(2, blame.lines.len());
+    assert_eq!("some example text", blame.lines[0].text);
+    assert_eq!("second line", blame.lines[1].text);
+    assert_eq!(edit_res.latest_version, blame.lines[1].edit);
+    assert_eq!(2, blame.edits.len());
+    assert_eq!("create article", blame.edits[0].edit.summary);
+    assert_eq!(blame.edits[0].edit.hash, blame.lines[0].edit);
+    assert_eq!("alpha", blame.edits[1].creator.username);
+
+    // edits which are completely overwritten are not included
+    let edit_params = EditArticleParams {
+        new_text: "rewritten\n".to_string(),
+        summary: "rewrite".to_string(),
+        previous_version_id: edit_res.latest_version,
+        ..edit_params
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let blame = alpha.get_article_blame(create_res.article.id).await?;
+    assert_eq!(1, blame.lines.len());
+    assert_eq!(1, blame.edits.len());
+    assert_eq!("rewrite", blame.edits[0].edit.summary);
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_compare_versions(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let first_version = create_res.latest_version.clone();
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "some **changed** text\n\nnew paragraph\n".to_string(),
+        summary: "change".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    edit_params.new_text = "unrelated\n".to_string();
+    edit_params.previous_version_id = edit_res.latest_version.clone();
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // compare first and second version, ignoring the latest one
+    let mut params = CompareVersionsParams {
+        article_id: create_res.article.id,
+        from: first_version,
+        to: edit_res.latest_version,
+        granularity: DiffGranularity::Line,
+    };
+    let diff = alpha.compare_versions(&params).await?;
+    let changes: Vec<_> = diff
+        .chunks
+        .iter()
+        .map(|c| (c.kind, c.text.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (DiffChangeKind::Delete, "some example text\n"),
+            (
+                DiffChangeKind::Insert,
+                "some **changed** text\n\nnew paragraph\n"
+            ),
+        ],
+        changes
+    );
+    assert!(diff.html.is_none());
+
+    params.granularity = DiffGranularity::Word;
+    let diff = alpha.compare_versions(&params).await?;
+    assert_eq!(DiffChangeKind::Equal, diff.chunks[0].kind);
+    assert_eq!("some ", diff.chunks[0].text);
+    assert_eq!("example", diff.chunks[1].text);
+    assert_eq!(DiffChangeKind::Delete, diff.chunks[1].kind);
+
+    params.granularity = DiffGranularity::Rendered;
+    let diff = alpha.compare_versions(&params).await?;
+    assert!(diff.chunks.is_empty());
+    let html = diff.html.unwrap();
+    assert!(html.contains("<del>example</del>"));
+    assert!(html.contains("<strong><ins>changed</ins></strong>"));
+    assert!(html.contains("<p><ins>new paragraph</ins></p>"));
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_git_history(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Manu Chao is a French musician.\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    let hostname = alpha.hostname.clone();
+    let fetch = |path: String| {
+        let hostname = hostname.clone();
+        async move {
+            let res = reqwest::get(format!("http://{hostname}/git/{path}")).await?;
+            res.error_for_status()?.bytes().await
+        }
+    };
+    let read_object = |data: &[u8]| -> Result<String> {
+        let mut object = String::new();
+        ZlibDecoder::new(data).read_to_string(&mut object)?;
+        Ok(object)
+    };
+
+    let head = fetch("article/Manu_Chao.git/HEAD".to_string()).await?;
+    assert_eq!(&b"ref: refs/heads/main\n"[..], &head[..]);
+    let refs = fetch("article/Manu_Chao.git/info/refs".to_string()).await?;
+    let refs = String::from_utf8(refs.to_vec())?;
+    let (commit_id, branch) = refs.trim_end().split_once('\t').unwrap();
+    assert_eq!("refs/heads/main", branch);
+
+    // latest commit has edit summary, author and previous edit as parent
+    let path = format!(
+        "article/Manu_Chao.git/objects/{}/{}",
+        &commit_id[..2],
+        &commit_id[2..]
+    );
+    let commit = read_object(&fetch(path).await?)?;
+    let domain = extract_domain(&Url::parse(&format!("http://{}", alpha.hostname))?);
+    assert!(commit.contains(&format!("author alpha <alpha@{domain}>")));
+    assert!(commit.contains("\n\nshorten\n"));
+    let parent = commit
+        .lines()
+        .find_map(|l| l.strip_prefix("parent "))
+        .unwrap();
+    let path = format!(
+        "article/Manu_Chao.git/objects/{}/{}",
+        &parent[..2],
+        &parent[2..]
+    );
+    let parent = read_object(&fetch(path).await?)?;
+    assert!(parent.contains("\n\ncreate article\n"));
+    assert!(!parent.contains("\nparent "));
+
+    // new edits are added on top of the existing commits
+    let edit_params = EditArticleParams {
+        new_text: "Manu Chao is a French singer.\n".to_string(),
+        summary: "singer".to_string(),
+        previous_version_id: edit_res.latest_version,
+        ..edit_params
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let refs = fetch("article/Manu_Chao.git/info/refs".to_string()).await?;
+    let refs = String::from_utf8(refs.to_vec())?;
+    let (new_commit_id, _) = refs.trim_end().split_once('\t').unwrap();
+    let path = format!(
+        "article/Manu_Chao.git/objects/{}/{}",
+        &new_commit_id[..2],
+        &new_commit_id[2..]
+    );
+    let commit = read_object(&fetch(path).await?)?;
+    assert!(commit.contains(&format!("\nparent {commit_id}\n")));
+    assert!(commit.contains("\n\nsinger\n"));
+
+    // wiki repository also contains the main page
+    let refs = fetch("wiki.git/info/refs".to_string()).await?;
+    assert_ne!(refs, commit_id.as_bytes());
+    let missing = reqwest::get(format!(
+        "http://{}/git/wiki.git/objects/info/alternates",
+        alpha.hostname
+    ))
+    .await?;
+    assert_eq!(StatusCode::NOT_FOUND, missing.status());
+    Ok(())
+}
+
+fn extract_port(hostname: &str) -> i32 {
+    hostname
+        .split_once(':')
+        .and_then(|(_, port)| port.parse().ok())
+        .unwrap()
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_update_instance(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = UpdateInstanceParams {
+        name: Some("Alpha Wiki".to_string()),
+        topic: Some("Music".to_string()),
+        review_all_edits: None,
+        review_new_user_edits: None,
+        require_totp_for_moderators: None,
+        reason: None,
+    };
+
+    // only admins can change instance settings
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    assert!(anonymous.update_local_instance(&params).await.is_err());
+    assert!(alpha.update_local_instance(&params).await.is_err());
+
+    let admin_login = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    alpha.login(admin_login).await.unwrap();
+    let instance = alpha.update_local_instance(&params).await.unwrap();
+    assert_eq!(params.name, instance.name);
+    assert_eq!(params.topic, instance.topic);
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_review(TestData(alpha, _, gamma): &mut TestData) -> Result<()> {
+    let admin_login = || LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    let alpha_login = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+
+    // only admins can change review settings
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let review_params = RequireReviewParams {
+        article_id: create_res.article.id,
+        require_review: true,
+        reason: None,
+    };
+    assert!(alpha.require_review(&review_params).await.is_err());
+    let instance_params = UpdateInstanceParams {
+        name: None,
+        topic: None,
+        review_all_edits: Some(true),
+        review_new_user_edits: None,
+        require_totp_for_moderators: None,
+        reason: None,
+    };
+    assert!(alpha.update_local_instance(&instance_params).await.is_err());
+    assert!(
+        alpha
+            .list_edits_awaiting_review(&Default::default())
+            .await
+            .is_err()
+    );
+
+    alpha.login(admin_login()).await.unwrap();
+    let article = alpha.require_review(&review_params).await.unwrap();
+    assert!(article.review_edits);
+
+    // local edit is not applied until reviewed
+    alpha.login(alpha_login).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Local edit\n".to_string(),
+        summary: "local".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    assert!(alpha.edit_article(&edit_params).await.unwrap().is_none());
+    let article = alpha
+        .get_article(GetArticleParams {
+            id: Some(create_res.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT, article.article.text);
+    assert_eq!(create_res.latest_version, article.latest_version);
+
+    // remote edit also needs review
+    let gamma_article = gamma
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: gamma_article.article.id,
+        new_text: "Remote edit\n".to_string(),
+        summary: "remote".to_string(),
+        previous_version_id: gamma_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    assert!(gamma.edit_article(&edit_params).await.unwrap().is_none());
+
+    alpha.login(admin_login()).await.unwrap();
+    let queue = RetryFuture::new(
+        || async {
+            match alpha.list_edits_awaiting_review(&Default::default()).await {
+                Ok(e) if e.items.len() == 2 => Ok(e.items),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    assert_eq!("local", queue[0].edit.summary);
+    assert_eq!("remote", queue[1].edit.summary);
+
+    // approve local edit, now the remote edit is outdated and can only be rejected
+    let review_params = |i: usize, approve| ReviewEditParams {
+        edit_id: queue[i].edit.id,
+        approve,
+        reason: None,
+    };
+    alpha.review_edit(&review_params(0, true)).await.unwrap();
+    assert!(alpha.review_edit(&review_params(1, true)).await.is_err());
+    alpha.review_edit(&review_params(1, false)).await.unwrap();
+    assert!(
+        alpha
+            .list_edits_awaiting_review(&Default::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+
+    let article = alpha
+        .get_article(GetArticleParams {
+            id: Some(create_res.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!("Local edit\n", article.article.text);
+    assert_eq!(queue[0].edit.hash, article.latest_version);
+
+    // approved edit is federated
+    let gamma_article = RetryFuture::new(
+        || async {
+            match gamma
+                .get_article(GetArticleParams {
+                    id: Some(gamma_article.article.id),
+                    ..Default::default()
+                })
+                .await
+            {
+                Ok(a) if a.article.text == "Local edit\n" => Ok(a),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    assert_eq!(queue[0].edit.hash, gamma_article.latest_version);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_review_snapshot(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let mut text = TEST_ARTICLE_DEFAULT_TEXT.to_string();
+    let mut previous_version_id = create_res.latest_version;
+    let mut edit_params = |i: usize, previous_version_id| {
+        text.push_str(&format!("line {i}\n"));
+        EditArticleParams {
+            article_id: create_res.article.id,
+            new_text: text.clone(),
+            summary: format!("edit {i}"),
+            previous_version_id,
+            resolve_conflict_id: None,
+        }
+    };
+    for i in 0..98 {
+        previous_version_id = alpha
+            .edit_article_without_conflict(&edit_params(i, previous_version_id))
+            .await
+            .unwrap()
+            .latest_version;
+    }
+
+    let admin_login = || LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: None,
+    };
+    let alpha_login = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+    alpha.login(admin_login()).await.unwrap();
+    let review_params = RequireReviewParams {
+        article_id: create_res.article.id,
+        require_review: true,
+        reason: None,
+    };
+    alpha.require_review(&review_params).await.unwrap();
+    alpha.login(alpha_login).await.unwrap();
+    let last_edit = edit_params(98, previous_version_id);
+    assert!(alpha.edit_article(&last_edit).await.unwrap().is_none());
+
+    // approved edit is the checkpoint, so a snapshot is stored for it
+    alpha.login(admin_login()).await.unwrap();
+    let queue = alpha
+        .list_edits_awaiting_review(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(1, queue.len());
+    let review_params = ReviewEditParams {
+        edit_id: queue[0].edit.id,
+        approve: true,
+        reason: None,
+    };
+    alpha.review_edit(&review_params).await.unwrap();
+    let context = IbisContext::init(alpha.config.clone(), true).map_err(|e| e.0)?;
+    let snapshot = EditSnapshot::read(queue[0].edit.id, &context).map_err(|e| e.0)?;
+    assert_eq!(last_edit.new_text, snapshot.text);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_user_roles(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let user_params = || GetUserParams {
+        name: "alpha".to_string(),
+        domain: None,
+    };
+    let alpha_user = alpha.get_user(user_params()).await.unwrap();
+    assert_eq!(Some(Role::User), alpha_user.role);
+    let role_params = SetUserRoleParams {
+        person_id: alpha_user.person.id,
+        role: Role::Moderator,
+        reason: None,
+    };
+
+    // normal users cannot assign roles or protect articles
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let protect_params = ProtectArticleParams {
+        article_id: create_res.article.id,
+        protected: true,
+        reason: None,
+    };
+    assert!(alpha.set_user_role(&role_params).await.is_err());
+    assert!(alpha.protect_article(&protect_params).await.is_err());
+
+    // admin makes alpha a moderator, but can't change own role
+    let admin = alpha
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(Role::Admin, admin.local_user.role);
+    let own_role_params = SetUserRoleParams {
+        person_id: admin.person.id,
+        role: Role::User,
+        reason: None,
+    };
+    assert!(alpha.set_user_role(&own_role_params).await.is_err());
+    let local_user = alpha.set_user_role(&role_params).await.unwrap();
+    assert_eq!(Role::Moderator, local_user.role);
+    let alpha_user = alpha.get_user(user_params()).await.unwrap();
+    assert_eq!(Some(Role::Moderator), alpha_user.role);
+
+    // moderator can protect articles, but not change instance settings
+    let alpha_login = alpha
+        .login(LoginUserParams {
+            username_or_email: "alpha".to_string(),
+            password: "hunter22".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(Role::Moderator, alpha_login.local_user.role);
+    let article = alpha.protect_article(&protect_params).await.unwrap();
+    assert!(article.protected);
+    let instance_params = UpdateInstanceParams {
+        name: Some("test".to_string()),
+        topic: None,
+        review_all_edits: None,
+        review_new_user_edits: None,
+        require_totp_for_moderators: None,
+        reason: None,
+    };
+    assert!(alpha.update_local_instance(&instance_params).await.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_ban_user(TestData(alpha, _, gamma): &mut TestData) -> Result<()> {
+    let alpha_login = || LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+    let moderator = ApiClient::new(Some(alpha.hostname.clone()));
+    let admin = moderator
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+
+    // article is created by admin, then edited and commented by alpha
+    let create_res = moderator
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Spam\n".to_string(),
+        summary: "spam".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let comment_params = CreateCommentParams {
+        content: "Spam".to_string(),
+        article_id: create_res.article.id,
+        parent_id: None,
+    };
+    let comment = alpha.create_comment(&comment_params).await.unwrap();
+
+    // alpha's edits to another article are interleaved with edits by admin
+    let interleaved_params = CreateArticleParams {
+        title: "Mano Negra".to_string(),
+        text: "1\n2\n3\n4\n5\n6\n7\n".to_string(),
+        ..create_test_article_params()
+    };
+    let interleaved = moderator.create_article(&interleaved_params).await.unwrap();
+    let edit_interleaved = |client: &ApiClient, text: &str, previous_version_id| {
+        let params = EditArticleParams {
+            article_id: interleaved.article.id,
+            new_text: text.to_string(),
+            summary: "edit".to_string(),
+            previous_version_id,
+            resolve_conflict_id: None,
+        };
+        let client = client.clone();
+        async move { client.edit_article_without_conflict(&params).await.unwrap() }
+    };
+    let res = edit_interleaved(
+        alpha,
+        "spam\n2\n3\n4\n5\n6\n7\n",
+        interleaved.latest_version,
+    )
+    .await;
+    let res = edit_interleaved(
+        &moderator,
+        "spam\n2\n3\nfour\n5\n6\n7\n",
+        res.latest_version,
+    )
+    .await;
+    edit_interleaved(alpha, "spam\n2\n3\nfour\n5\n6\nspam\n", res.latest_version).await;
+
+    // alpha's edit to an article which requires review is waiting in the queue
+    let reviewed = moderator
+        .create_article(&CreateArticleParams {
+            title: "Clandestino".to_string(),
+            ..create_test_article_params()
+        })
+        .await
+        .unwrap();
+    let review_params = RequireReviewParams {
+        article_id: reviewed.article.id,
+        require_review: true,
+        reason: None,
+    };
+    moderator.require_review(&review_params).await.unwrap();
+    let reviewed_edit = EditArticleParams {
+        article_id: reviewed.article.id,
+        new_text: "Spam\n".to_string(),
+        summary: "spam".to_string(),
+        previous_version_id: reviewed.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&reviewed_edit).await.unwrap();
+    let queue = moderator
+        .list_edits_awaiting_review(&Default::default())
+        .await
+        .unwrap();
+    assert_eq!(1, queue.items.len());
+
+    // ban requires a moderator and a reason, and cannot target yourself
+    let mut ban_params = BanPersonParams {
+        person_id: comment.creator.id,
+        reason: " ".to_string(),
+        expires: None,
+        remove_content_days: Some(1),
+    };
+    assert!(alpha.ban_person(&ban_params).await.is_err());
+    assert!(moderator.ban_person(&ban_params).await.is_err());
+    ban_params.reason = "spam".to_string();
+    ban_params.person_id = admin.person.id;
+    assert!(moderator.ban_person(&ban_params).await.is_err());
+    ban_params.person_id = comment.creator.id;
+    let ban = moderator.ban_person(&ban_params).await.unwrap();
+    assert_eq!("spam", ban.reason);
+
+    // alpha is logged out and cannot login again
+    assert!(alpha.edit_article(&edit_params).await.is_err());
+    assert!(alpha.login(alpha_login()).await.is_err());
+    let alpha_user = moderator
+        .get_user(GetUserParams {
+            name: "alpha".to_string(),
+            domain: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(Some(ban.id), alpha_user.ban.map(|b| b.id));
+
+    // content by alpha is removed
+    let article = moderator
+        .get_article(GetArticleParams {
+            id: Some(create_res.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT, article.article.text);
+    assert!(article.comments[0].comment.deleted);
+    let article = moderator
+        .get_article(GetArticleParams {
+            id: Some(interleaved.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!("1\n2\n3\nfour\n5\n6\n7\n", article.article.text);
+    let queue = moderator
+        .list_edits_awaiting_review(&Default::default())
+        .await
+        .unwrap();
+    assert!(queue.items.is_empty());
+
+    // remote user is banned by alpha instance
+    let gamma_article = gamma
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let comment_params = CreateCommentParams {
+        content: "Remote spam".to_string(),
+        article_id: gamma_article.article.id,
+        parent_id: None,
+    };
+    gamma.create_comment(&comment_params).await.unwrap();
+    let article = RetryFuture::new(
+        || async {
+            match moderator
+                .get_article(GetArticleParams {
+                    id: Some(create_res.article.id),
+                    ..Default::default()
+                })
+                .await
+            {
+                Ok(a) if a.comments.len() == 2 => Ok(a),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    let gamma_person = article
+        .comments
+        .into_iter()
+        .map(|c| c.creator)
+        .find(|p| !p.local)
+        .unwrap();
+    ban_params.person_id = gamma_person.id;
+    ban_params.remove_content_days = None;
+    moderator.ban_person(&ban_params).await.unwrap();
+    let bans = moderator
+        .list_bans(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(2, bans.len());
+
+    // edits and comments by banned remote user are rejected
+    let edit_params = EditArticleParams {
+        article_id: gamma_article.article.id,
+        new_text: "Remote spam\n".to_string(),
+        summary: "spam".to_string(),
+        previous_version_id: gamma_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    gamma.edit_article(&edit_params).await.unwrap();
+    gamma.create_comment(&comment_params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let article = moderator
+        .get_article(GetArticleParams {
+            id: Some(create_res.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT, article.article.text);
+    assert_eq!(2, article.comments.len());
+
+    // after unban alpha can login again
+    let unban_params = UnbanPersonParams {
+        person_id: alpha_user.person.id,
+        reason: None,
+    };
+    moderator.unban_person(&unban_params).await.unwrap();
+    alpha.login(alpha_login()).await.unwrap();
+    let bans = moderator
+        .list_bans(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(1, bans.len());
+    assert_eq!(gamma_person.id, bans[0].person.id);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_modlog(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let admin = alpha
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let protect_params = ProtectArticleParams {
+        article_id: create_res.article.id,
+        protected: true,
+        reason: Some("vandalism".to_string()),
+    };
+    alpha.protect_article(&protect_params).await.unwrap();
+    let remove_params = RemoveArticleParams {
+        article_id: create_res.article.id,
+        remove: true,
+        reason: Some(" ".to_string()),
+    };
+    alpha.remove_article(&remove_params).await.unwrap();
+
+    // modlog is public, newest entries first
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    let modlog = anonymous
+        .list_modlog(&ListModlogParams::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(2, modlog.len());
+    assert_eq!(ModAction::RemoveArticle, modlog[0].modlog.action);
+    assert_eq!(None, modlog[0].modlog.reason);
+    assert_eq!(ModAction::ProtectArticle, modlog[1].modlog.action);
+    assert_eq!(Some("vandalism".to_string()), modlog[1].modlog.reason);
+    assert_eq!(admin.person.id, modlog[1].moderator.id);
+    assert_eq!(
+        Some(create_res.article.id),
+        modlog[1].article.as_ref().map(|a| a.id)
+    );
+
+    // filters and pagination
+    let params = ListModlogParams {
+        action: Some(ModAction::ProtectArticle),
+        ..Default::default()
+    };
+    let modlog = anonymous.list_modlog(&params).await.unwrap();
+    assert_eq!(1, modlog.items.len());
+    let mut params = ListModlogParams {
+        article_id: Some(create_res.article.id),
+        moderator_id: Some(admin.person.id),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let first_page = anonymous.list_modlog(&params).await.unwrap();
+    assert_eq!(1, first_page.items.len());
+    assert_eq!(ModAction::RemoveArticle, first_page.items[0].modlog.action);
+    params.cursor = first_page.next_page;
+    let modlog = anonymous.list_modlog(&params).await.unwrap();
+    assert_eq!(1, modlog.items.len());
+    assert_eq!(ModAction::ProtectArticle, modlog.items[0].modlog.action);
+    assert!(modlog.next_page.is_none());
+    let alpha_user = anonymous
+        .get_user(GetUserParams {
+            name: "alpha".to_string(),
+            domain: None,
+        })
+        .await
+        .unwrap();
+    let params = ListModlogParams {
+        moderator_id: Some(alpha_user.person.id),
+        ..Default::default()
+    };
+    assert!(
+        anonymous
+            .list_modlog(&params)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_report(TestData(alpha, _, gamma): &mut TestData) -> Result<()> {
+    let moderator = ApiClient::new(Some(alpha.hostname.clone()));
+    moderator
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    let create_res = moderator
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+
+    // local user reports the article, moderators are notified
+    let mut report_params = CreateReportParams {
+        article_id: create_res.article.id,
+        edit_id: None,
+        comment_id: None,
+        reason: " ".to_string(),
+    };
+    assert!(alpha.create_report(&report_params).await.is_err());
+    report_params.reason = "Spam".to_string();
+    let report = alpha.create_report(&report_params).await.unwrap();
+    assert_eq!(ReportStatus::Open, report.status);
+    let notifications = moderator
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(1, notifications.len());
+    assert!(matches!(
+        &notifications[0].data,
+        ApiNotificationData::Report(r) if r.id == report.id
+    ));
+    assert!(
+        alpha
+            .list_reports(&ListReportsParams::default())
+            .await
+            .is_err()
+    );
+    let reports = moderator
+        .list_reports(&ListReportsParams::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(1, reports.len());
+    assert_eq!("alpha", reports[0].creator.username);
+    assert_eq!(create_res.article.id, reports[0].article.id);
+
+    // resolving the report is logged
+    let resolve_params = ResolveReportParams {
+        report_id: report.id,
+        dismiss: false,
+        reason: Some("Removed spam".to_string()),
+    };
+    assert!(alpha.resolve_report(&resolve_params).await.is_err());
+    let report = moderator.resolve_report(&resolve_params).await.unwrap();
+    assert_eq!(ReportStatus::Resolved, report.status);
+    assert!(moderator.resolve_report(&resolve_params).await.is_err());
+    assert!(
+        moderator
+            .list_reports(&ListReportsParams::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+    let params = ListReportsParams {
+        status: Some(ReportStatus::Resolved),
+        ..Default::default()
+    };
+    assert_eq!(
+        1,
+        moderator.list_reports(&params).await.unwrap().items.len()
+    );
+    let modlog = moderator
+        .list_modlog(&ListModlogParams::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(ModAction::ResolveReport, modlog[0].modlog.action);
+    assert_eq!(Some("Removed spam".to_string()), modlog[0].modlog.reason);
+    assert!(modlog[0].target_person.is_none());
+
+    // modlog references the author of a reported comment, but never the reporter
+    let comment_params = CreateCommentParams {
+        content: "Off topic".to_string(),
+        article_id: create_res.article.id,
+        parent_id: None,
+    };
+    let comment = moderator.create_comment(&comment_params).await.unwrap();
+    let report = alpha
+        .create_report(&CreateReportParams {
+            article_id: create_res.article.id,
+            edit_id: None,
+            comment_id: Some(comment.comment.id),
+            reason: "Off topic".to_string(),
+        })
+        .await
+        .unwrap();
+    let resolve_params = ResolveReportParams {
+        report_id: report.id,
+        dismiss: true,
+        reason: None,
+    };
+    moderator.resolve_report(&resolve_params).await.unwrap();
+    let modlog = moderator
+        .list_modlog(&ListModlogParams::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(ModAction::DismissReport, modlog[0].modlog.action);
+    assert_eq!(
+        Some(comment.creator.id),
+        modlog[0].target_person.as_ref().map(|p| p.id)
+    );
+    assert!(
+        modlog
+            .iter()
+            .all(|m| m.target_person.as_ref().map(|p| p.id) != Some(report.creator_id))
+    );
+
+    // remote user reports a comment, which is sent to the origin instance as flag
+    let gamma_article = gamma
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let comment_params = CreateCommentParams {
+        content: "Hello".to_string(),
+        article_id: gamma_article.article.id,
+        parent_id: None,
+    };
+    let comment = gamma.create_comment(&comment_params).await.unwrap();
+    let report_params = CreateReportParams {
+        article_id: gamma_article.article.id,
+        edit_id: None,
+        comment_id: Some(comment.comment.id),
+        reason: "Remote spam".to_string(),
+    };
+    gamma.create_report(&report_params).await.unwrap();
+    let reports = RetryFuture::new(
+        || async {
+            match moderator.list_reports(&ListReportsParams::default()).await {
+                Ok(r) if !r.items.is_empty() => Ok(r.items),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    assert_eq!(1, reports.len());
+    assert_eq!("Remote spam", reports[0].report.reason);
+    assert!(!reports[0].creator.local);
+    assert_eq!(
+        Some("Hello".to_string()),
+        reports[0].comment.as_ref().map(|c| c.content.clone())
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_federation_list(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    let gamma_article = gamma
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let alpha_article = alpha
+        .resolve_article(gamma_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    // only admin can block, and the local instance cannot be blocked
+    let mut block_params = UpdateFederationListParams {
+        domain: gamma.hostname.clone(),
+        mode: FederationMode::Block,
+        reason: "Spam".to_string(),
+        expires: None,
+        content: Some(BlockedContent::Purge),
+    };
+    assert!(alpha.update_federation_list(&block_params).await.is_err());
+    block_params.domain = alpha.hostname.clone();
+    assert!(admin.update_federation_list(&block_params).await.is_err());
+    block_params.domain = gamma.hostname.clone();
+    let entry = admin.update_federation_list(&block_params).await.unwrap();
+    assert_eq!(gamma.hostname, entry.domain);
+
+    // list is public
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    let list = anonymous
+        .list_federation(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(vec![entry], list);
+
+    // content from gamma is purged and cannot be fetched again
+    let get_params = GetArticleParams {
+        id: Some(alpha_article.article.id),
+        ..Default::default()
+    };
+    assert!(alpha.get_article(get_params.clone()).await.is_err());
+    assert!(
+        alpha
+            .resolve_article(gamma_article.article.ap_id.inner().clone())
+            .await
+            .is_err()
+    );
+
+    // after removing the block, fetching works again
+    let remove_params = RemoveFederationListParams {
+        domain: format!(" {} ", gamma.hostname.to_uppercase()),
+    };
+    assert!(alpha.remove_federation_list(&remove_params).await.is_err());
+    admin.remove_federation_list(&remove_params).await.unwrap();
+    assert!(
+        anonymous
+            .list_federation(&Default::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+
+    // changes are shown in modlog
+    let modlog = anonymous
+        .list_modlog(&ListModlogParams::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(
+        ModAction::RemoveFederationListEntry,
+        modlog[0].modlog.action
+    );
+    assert_eq!(ModAction::BlockDomain, modlog[1].modlog.action);
+    assert_eq!(
+        Some(&gamma.hostname),
+        modlog[0].modlog.target_domain.as_ref()
+    );
+    assert_eq!(Some("Spam"), modlog[1].modlog.reason.as_deref());
+    alpha
+        .resolve_article(gamma_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    // if any domain is allowed, all others are blocked
+    let allow_params = UpdateFederationListParams {
+        domain: beta.hostname.clone(),
+        mode: FederationMode::Allow,
+        reason: "Friends".to_string(),
+        expires: None,
+        content: None,
+    };
+    admin.update_federation_list(&allow_params).await.unwrap();
+    let mut article_params = create_test_article_params();
+    article_params.title = "Another article".to_string();
+    let gamma_article = gamma.create_article(&article_params).await.unwrap();
+    assert!(
+        alpha
+            .resolve_article(gamma_article.article.ap_id.inner().clone())
+            .await
+            .is_err()
+    );
+    let beta_article = beta.create_article(&article_params).await.unwrap();
+    alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_delivery_status(TestData(alpha, beta, _gamma): &mut TestData) -> Result<()> {
+    let admin = ApiClient::new(Some(beta.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await
+        .unwrap();
+    assert!(
+        admin
+            .list_delivery_status(&Default::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+
+    // follow is delivered to alpha right away
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+    assert!(
+        beta.list_delivery_status(&Default::default())
+            .await
+            .is_err()
+    );
+    let statuses = admin
+        .list_delivery_status(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(1, statuses.len());
+    assert_eq!(alpha.hostname, statuses[0].status.domain);
+    assert!(statuses[0].status.last_success.is_some());
+    assert_eq!(0, statuses[0].status.fail_count);
+    assert_eq!(0, statuses[0].queue_depth);
+    assert!(!statuses[0].dead);
+
+    // additional instance follows beta and goes offline, so delivery of a new article fails
+    let port = extract_port(&beta.hostname) + 1000;
+    let domain = format!("localhost:{port}");
+    let delta = IbisInstance::new_with_domain("delta", port, &domain).await;
+    delta.follow_instance_with_resolve(&beta.hostname).await?;
+    delta.stop().await;
+    beta.create_article(&create_test_article_params()).await?;
+    let read_status = || async {
+        let statuses = admin.list_delivery_status(&Default::default()).await?.items;
+        Ok::<_, anyhow::Error>(statuses.into_iter().find(|s| s.status.domain == domain))
+    };
+    let status = read_status().await?.unwrap();
+    assert!(status.status.fail_count > 0);
+    assert!(status.status.failing_since.is_some());
+    assert!(status.status.last_error.is_some());
+    assert_eq!(i64::from(status.status.fail_count), status.queue_depth);
+    assert!(!status.dead);
+
+    // instance is back, fetching a user from it resets the failure state
+    let delta = IbisInstance::new_with_domain("delta2", port, &domain).await;
+    let delta_article = delta.create_article(&create_test_article_params()).await?;
+    beta.resolve_article(delta_article.article.ap_id.inner().clone())
+        .await?;
+    let status = read_status().await?.unwrap();
+    assert_eq!(0, status.status.fail_count);
+    assert!(status.status.failing_since.is_none());
+    delta.stop().await;
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_feeds(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Manu Chao is a French <musician>.\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let comment_params = CreateCommentParams {
+        content: "Nice article".to_string(),
+        article_id: create_res.article.id,
+        parent_id: None,
+    };
+    alpha.create_comment(&comment_params).await.unwrap();
+
+    let hostname = alpha.hostname.clone();
+    let fetch = |path: &str| {
+        let url = format!("http://{hostname}/feed/{path}");
+        async move {
+            let res = reqwest::get(url).await?.error_for_status()?;
+            let content_type = res.headers()[http::header::CONTENT_TYPE]
+                .to_str()?
+                .to_string();
+            Ok::<_, anyhow::Error>((content_type, res.text().await?))
+        }
+    };
+
+    // newest edit comes first, and includes an escaped diff excerpt
+    let (content_type, changes) = fetch("atom/changes").await?;
+    assert!(content_type.starts_with("application/atom+xml"));
+    let shorten = changes.find("<title>Manu Chao: shorten</title>").unwrap();
+    let create = changes
+        .find("<title>Manu Chao: create article</title>")
+        .unwrap();
+    assert!(shorten < create);
+    assert!(changes.contains("<name>@alpha</name>"));
+    assert!(changes.contains("+Manu Chao is a French &amp;lt;musician&amp;gt;."));
+
+    let (content_type, history) = fetch("rss/article/Manu_Chao").await?;
+    assert!(content_type.starts_with("application/rss+xml"));
+    assert!(history.contains("<title>Manu Chao: shorten</title>"));
+    assert!(history.contains("<dc:creator>@alpha</dc:creator>"));
+
+    let (_, contributions) = fetch("atom/user/alpha").await?;
+    assert_eq!(2, contributions.matches("<entry>").count());
+
+    let (_, comments) = fetch("atom/article/Manu_Chao/comments").await?;
+    assert_eq!(1, comments.matches("<entry>").count());
+    assert!(comments.contains("Nice article"));
+
+    // links are percent-encoded
+    let special_params = CreateArticleParams {
+        title: "Rock (live)".to_string(),
+        ..create_test_article_params()
+    };
+    alpha.create_article(&special_params).await?;
+    let (_, changes) = fetch("atom/changes").await?;
+    assert!(changes.contains(&format!(
+        "http://{hostname}/article/Rock_%28live%29@{hostname}/diff/"
+    )));
+    let (_, history) = fetch("atom/article/Rock_%28live%29").await?;
+    assert!(history.contains(&format!(
+        "http://{hostname}/feed/atom/article/Rock_%28live%29"
+    )));
+
+    assert!(fetch("atom/article/Missing").await.is_err());
+    assert!(fetch("json/changes").await.is_err());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_recent_changes(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Manu Chao is a French <musician>.\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let beta_params = CreateArticleParams {
+        title: "Beta article".to_string(),
+        ..create_test_article_params()
+    };
+    let beta_article = beta.create_article(&beta_params).await?;
+    let beta_article = alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await?;
+
+    // newest first, with size of each change
+    let local = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            local_only: Some(true),
+            ..Default::default()
+        })
+        .await?
+        .items;
+    let summaries: Vec<_> = local.iter().map(|e| e.edit.summary.as_str()).collect();
+    assert_eq!(
+        vec!["shorten", "create article", "Default main page"],
+        summaries
+    );
+    assert_eq!(16, local[0].edit.size_delta());
+    assert_eq!(18, local[1].edit.size_delta());
+
+    let all = alpha
+        .list_recent_changes(&ListRecentChangesParams::default())
+        .await?
+        .items;
+    assert_eq!(beta_article.article.id, all[0].article.id);
+
+    let remote = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            instance_id: Some(beta_article.instance.id),
+            ..Default::default()
+        })
+        .await?
+        .items;
+    assert!(
+        remote
+            .iter()
+            .any(|e| e.article.id == beta_article.article.id)
+    );
+    assert!(remote.iter().all(|e| !e.article.local));
+
+    let person_id = alpha.site().await?.my_profile.unwrap().person.id;
+    let by_alpha_params = ListRecentChangesParams {
+        person_id: Some(person_id),
+        hide_bots: Some(true),
+        ..Default::default()
+    };
+    let by_alpha = alpha.list_recent_changes(&by_alpha_params).await?.items;
+    assert_eq!(local[..2], by_alpha);
+
+    let first_page = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            limit: Some(1),
+            ..by_alpha_params.clone()
+        })
+        .await?;
+    assert_eq!(vec![local[0].clone()], first_page.items);
+    let paged = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            limit: Some(1),
+            cursor: first_page.next_page,
+            ..by_alpha_params
+        })
+        .await?;
+    assert_eq!(vec![local[1].clone()], paged.items);
+
+    let future = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            since: Some(Utc::now() + TimeDelta::hours(1)),
+            ..Default::default()
+        })
+        .await?
+        .items;
+    assert!(future.is_empty());
+
+    // only moderators can list unreviewed edits
+    let unreviewed = ListRecentChangesParams {
+        unreviewed_only: Some(true),
+        ..Default::default()
+    };
+    assert!(alpha.list_recent_changes(&unreviewed).await.is_err());
+    alpha
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await?;
+    assert!(
+        alpha
+            .list_recent_changes(&unreviewed)
+            .await?
+            .items
+            .is_empty()
+    );
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_pagination(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut article = alpha.create_article(&create_test_article_params()).await?;
+    for i in 0..3 {
+        let params = CreateArticleParams {
+            title: format!("Article {i}"),
+            ..create_test_article_params()
+        };
+        alpha.create_article(&params).await?;
+    }
+
+    // walk through all articles page by page, newest first
+    let all_articles = alpha.list_articles(Default::default()).await?;
+    assert_eq!(5, all_articles.items.len());
+    assert!(all_articles.next_page.is_none());
+    let mut params = ListArticlesParams {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let mut paged = vec![];
+    loop {
+        let page = alpha.list_articles(params.clone()).await?;
+        assert!(page.items.len() <= 2);
+        paged.extend(page.items);
+        match page.next_page {
+            Some(next_page) => params.cursor = Some(next_page),
+            None => break,
+        }
+    }
+    assert_eq!(all_articles.items, paged);
+
+    // edits are listed oldest first
+    for i in 0..3 {
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: format!("Version {i}\n"),
+            summary: format!("edit {i}"),
+            previous_version_id: article.latest_version.clone(),
+            resolve_conflict_id: None,
+        };
+        article = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+    }
+    let edit_params = GetEditList {
+        article_id: Some(article.article.id),
+        limit: Some(3),
+        ..Default::default()
+    };
+    let first = alpha.list_edits(&edit_params).await?;
+    assert_eq!("create article", first.items[0].edit.summary);
+    let second = alpha
+        .list_edits(&GetEditList {
+            cursor: first.next_page.clone(),
+            ..edit_params
+        })
+        .await?;
+    assert_eq!(1, second.items.len());
+    assert_eq!("edit 2", second.items[0].edit.summary);
+    assert!(second.next_page.is_none());
+    assert_eq!(4, alpha.get_article_edits(article.article.id).await?.len());
+
+    // only the newest comments are included in the article
+    for i in 0..3 {
+        let comment_params = CreateCommentParams {
+            content: format!("Comment {i}"),
+            article_id: article.article.id,
+            parent_id: None,
+        };
+        alpha.create_comment(&comment_params).await?;
+    }
+    let comment_params = ListCommentsParams {
+        article_id: article.article.id,
+        cursor: None,
+        limit: Some(2),
+    };
+    let comments = alpha.list_comments(&comment_params).await?;
+    assert_eq!("Comment 2", comments.items[0].comment.content);
+    let comments = alpha
+        .list_comments(&ListCommentsParams {
+            cursor: comments.next_page,
+            ..comment_params
+        })
+        .await?;
+    assert_eq!(1, comments.items.len());
+    assert_eq!("Comment 0", comments.items[0].comment.content);
+
+    let instances = alpha.list_instances(&Default::default()).await?;
+    assert_eq!(1, instances.items.len());
+    assert!(instances.next_page.is_none());
+
+    // federation collections link to their first page
+    let get_json = |url: String| async move {
+        reqwest::Client::new()
+            .get(url)
+            .header(http::header::ACCEPT, "application/activity+json")
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await
+    };
+    let collection = get_json(format!("http://{}/all_articles", alpha.hostname)).await?;
+    assert_eq!("OrderedCollection", collection["type"]);
+    let first = collection["first"].as_str().unwrap().to_string();
+    let page = get_json(first).await?;
+    assert_eq!("OrderedCollectionPage", page["type"]);
+    assert_eq!(collection["id"], page["partOf"]);
+    assert_eq!(5, page["orderedItems"].as_array().unwrap().len());
+
+    assert!(
+        alpha
+            .list_articles(ListArticlesParams {
+                cursor: Some(PaginationCursor("invalid".to_string())),
+                ..Default::default()
+            })
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_federated_collection_pages(
+    TestData(alpha, beta, _): &mut TestData,
+) -> Result<()> {
+    // more edits than fit on a single collection page
+    let mut article = alpha.create_article(&create_test_article_params()).await?;
+    for i in 0..60 {
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: format!("Version {i}\n"),
+            summary: format!("edit {i}"),
+            previous_version_id: article.latest_version.clone(),
+            resolve_conflict_id: None,
+        };
+        article = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+    }
+
+    let get_json = |url: String| async move {
+        reqwest::Client::new()
+            .get(url)
+            .header(http::header::ACCEPT, "application/activity+json")
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await
+    };
+    let collection = get_json(format!("http://{}/article/Manu_Chao/edits", alpha.hostname)).await?;
+    assert_eq!("OrderedCollection", collection["type"]);
+    let page = get_json(collection["first"].as_str().unwrap().to_string()).await?;
+    assert_eq!("OrderedCollectionPage", page["type"]);
+    assert_eq!(collection["id"], page["partOf"]);
+    assert!(page["next"].is_string());
+
+    // remote instance follows the next links to read the whole history
+    let resolved = beta
+        .resolve_article(article.article.ap_id.inner().clone())
+        .await?;
+    assert_eq!(article.article.text, resolved.article.text);
+    let edits = beta.get_article_edits(resolved.article.id).await?;
+    assert_eq!(61, edits.len());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_api_token(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = CreateApiTokenParams {
+        name: "my bot".to_string(),
+        edit_articles: true,
+        bot_edits: true,
+        ..Default::default()
+    };
+    let res = alpha.create_api_token(&params).await?;
+    assert!(res.token.starts_with("ibis_"));
+    assert_eq!(
+        vec![ApiTokenScope::Read, ApiTokenScope::EditArticles],
+        res.api_token.scopes
+    );
+    assert!(res.api_token.last_used.is_none());
+
+    // requests with the token are authenticated as alpha
+    let client = reqwest::Client::new();
+    let api = |path: &str| format!("http://{}/api/v1{path}", alpha.hostname);
+    let site: SiteView = client
+        .get(api("/site"))
+        .bearer_auth(&res.token)
+        .send()
+        .await?
+        .json()
+        .await?;
+    let person_id = site.my_profile.unwrap().person.id;
+    assert_eq!(alpha.site().await?.my_profile.unwrap().person.id, person_id);
+
+    // edits made with the token are marked as bot edits
+    let article: ArticleView = client
+        .post(api("/article"))
+        .bearer_auth(&res.token)
+        .form(&create_test_article_params())
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let recent_params = ListRecentChangesParams {
+        person_id: Some(person_id),
+        ..Default::default()
+    };
+    let recent = alpha.list_recent_changes(&recent_params).await?.items;
+    assert_eq!(article.article.id, recent[0].article.id);
+    assert!(recent[0].edit.bot);
+    let recent = alpha
+        .list_recent_changes(&ListRecentChangesParams {
+            hide_bots: Some(true),
+            ..recent_params
+        })
+        .await?
+        .items;
+    assert!(recent.iter().all(|e| e.article.id != article.article.id));
+
+    // token doesn't have comment scope, and can't change account settings
+    let comment_params = CreateCommentParams {
+        content: "comment".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    let res_comment = client
+        .post(api("/comment"))
+        .bearer_auth(&res.token)
+        .form(&comment_params)
+        .send()
+        .await?;
+    assert_eq!(StatusCode::FORBIDDEN, res_comment.status());
+    let res_token = client
+        .post(api("/account/api_token"))
+        .header("auth", &res.token)
+        .form(&params)
+        .send()
+        .await?;
+    assert_eq!(StatusCode::FORBIDDEN, res_token.status());
+
+    // sessions and tokens can't be listed with the read scope
+    for path in ["/account/session/list", "/account/api_token/list"] {
+        let res = client.get(api(path)).bearer_auth(&res.token).send().await?;
+        assert_eq!(StatusCode::FORBIDDEN, res.status());
+    }
+
+    let tokens = alpha.list_api_tokens().await?;
+    assert_eq!(1, tokens.len());
+    assert!(tokens[0].last_used.is_some());
+
+    // revoked token is not accepted anymore
+    alpha.delete_api_token(res.api_token.id).await?;
+    assert!(alpha.list_api_tokens().await?.is_empty());
+    let site: SiteView = client
+        .get(api("/site"))
+        .bearer_auth(&res.token)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(site.my_profile.is_none());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_sessions(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // forwarded address is ignored without trusted proxy
+    let login_params = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+    reqwest::Client::new()
+        .post(format!("http://{}/api/v1/account/login", alpha.hostname))
+        .header("x-forwarded-for", "1.2.3.4")
+        .form(&login_params)
+        .send()
+        .await?
+        .error_for_status()?;
+    let res = alpha.list_sessions().await?;
+    assert_eq!(2, res.sessions.len());
+    assert!(
+        res.sessions
+            .iter()
+            .all(|s| s.ip.as_deref() == Some("127.0.0.1"))
+    );
+    let forwarded = res.sessions.iter().find(|s| Some(s.id) != res.current);
+    alpha.revoke_session(forwarded.unwrap().id).await?;
+
+    // login from another device
+    let other = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+    other.login(login_params).await?;
+    assert!(other.site().await?.my_profile.is_some());
+
+    let res = alpha.list_sessions().await?;
+    assert_eq!(2, res.sessions.len());
+    assert_eq!(Some("127.0.0.1"), res.sessions[0].ip.as_deref());
+    let current = res.current.unwrap();
+    let other_session = res.sessions.iter().find(|s| s.id != current).unwrap();
+
+    // revoked session is logged out, even though the login token is still signed correctly
+    alpha.revoke_session(other_session.id).await?;
+    assert!(other.site().await?.my_profile.is_none());
+    assert_eq!(1, alpha.list_sessions().await?.sessions.len());
+
+    // changing password logs out all other sessions and revokes api tokens
+    let login_params = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code: None,
+    };
+    other.login(login_params).await?;
+    assert!(other.site().await?.my_profile.is_some());
+    let token_params = CreateApiTokenParams {
+        name: "my bot".to_string(),
+        ..Default::default()
+    };
+    alpha.create_api_token(&token_params).await?;
+    let params = ChangePasswordParams {
+        new_password: "hunter23".to_string(),
+        confirm_new_password: "hunter23".to_string(),
+        old_password: "hunter22".to_string(),
+    };
+    alpha.change_password(params).await?;
+    assert!(other.site().await?.my_profile.is_none());
+    assert!(alpha.site().await?.my_profile.is_some());
+    assert!(alpha.list_api_tokens().await?.is_empty());
+
+    // logout deletes the session
+    alpha.logout().await?;
+    let login_params = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter23".to_string(),
+        totp_code: None,
+    };
+    alpha.login(login_params).await?;
+    assert_eq!(1, alpha.list_sessions().await?.sessions.len());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_totp(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let login_params = |totp_code: Option<String>| LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+        totp_code,
+    };
+
+    // enabling needs a valid code from the authenticator app
+    let setup = alpha.setup_totp().await?;
+    assert!(setup.uri.starts_with("otpauth://totp/"));
+    assert!(setup.qr_code.contains("<svg"));
+    assert!(alpha.enable_totp("000000".to_string()).await.is_err());
+    let totp = TOTP::from_url(&setup.uri)?;
+    // code for the next time step, which is also accepted
+    let next_code = |totp: &TOTP| -> Result<String> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        Ok(totp.generate(now + totp.step))
+    };
+    let res = alpha.enable_totp(totp.generate_current()?).await?;
+    assert_eq!(10, res.recovery_codes.len());
+    assert!(
+        alpha
+            .site()
+            .await?
+            .my_profile
+            .unwrap()
+            .local_user
+            .totp_enabled
+    );
+
+    // login requires a second step
+    let other = ApiClient::new(Some(alpha.hostname.clone()));
+    let err = other.login(login_params(None)).await.unwrap_err();
+    assert_eq!(TOTP_REQUIRED_ERROR, err.message());
+    let invalid_code = Some("000000".to_string());
+    assert!(other.login(login_params(invalid_code)).await.is_err());
+    let code = Some(next_code(&totp)?);
+    other.login(login_params(code.clone())).await?;
+    assert!(other.site().await?.my_profile.is_some());
+
+    // the same code can't be used twice
+    assert!(other.login(login_params(code)).await.is_err());
+
+    // recovery codes can only be used once
+    let recovery_code = Some(res.recovery_codes[0].clone());
+    other.login(login_params(recovery_code.clone())).await?;
+    assert!(other.login(login_params(recovery_code)).await.is_err());
+
+    // admin requires two-factor authentication for moderators, which needs it for themselves
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+            totp_code: None,
+        })
+        .await?;
+    let instance_params = UpdateInstanceParams {
+        name: None,
+        topic: None,
+        review_all_edits: None,
+        review_new_user_edits: None,
+        require_totp_for_moderators: Some(true),
+        reason: None,
+    };
+    assert!(admin.update_local_instance(&instance_params).await.is_err());
+    let admin_totp = TOTP::from_url(&admin.setup_totp().await?.uri)?;
+    admin.enable_totp(admin_totp.generate_current()?).await?;
+    admin.update_local_instance(&instance_params).await?;
+    let role_params = SetUserRoleParams {
+        person_id: alpha
+            .get_user(GetUserParams {
+                name: "alpha".to_string(),
+                domain: None,
+            })
+            .await?
+            .person
+            .id,
+        role: Role::Moderator,
+        reason: None,
+    };
+    admin.set_user_role(&role_params).await?;
+
+    // moderator can only use permissions while two-factor authentication is enabled
+    let reports_params = ListReportsParams::default();
+    alpha.list_reports(&reports_params).await?;
+    alpha.disable_totp(res.recovery_codes[1].clone()).await?;
+    assert!(alpha.list_reports(&reports_params).await.is_err());
+    other.login(login_params(None)).await?;
+
+    // after too many failed attempts even a valid code is rejected
+    let admin_login = |totp_code: String| LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_code: Some(totp_code),
+    };
+    for _ in 0..5 {
+        assert!(
+            other
+                .login(admin_login("000000".to_string()))
+                .await
+                .is_err()
+        );
+    }
+    let err = other
+        .login(admin_login(next_code(&admin_totp)?))
+        .await
+        .unwrap_err();
+    assert!(err.message().contains("Too many failed"));
+    Ok(())
+}
@@ -0,0 +1 @@
+drop table edit_snapshot;
@@ -0,0 +1,4 @@
+create table edit_snapshot (
+    edit_id int primary key references edit on update cascade on delete cascade,
+    text text not null
+);
@@ -0,0 +1,9 @@
+create table article_redirect (
+    id serial primary key,
+    article_id int references article on update cascade on delete cascade not null,
+    title text not null,
+    ap_id varchar(255) not null unique,
+    published timestamptz not null default now()
+);
+
+create index on article_redirect (title);
@@ -0,0 +1,20 @@
+create table article_link (
+    id serial primary key,
+    source_id int references article on update cascade on delete cascade not null,
+    target_title text not null,
+    target_domain text not null,
+    unique (source_id, target_title, target_domain)
+);
+
+create index on article_link (target_title, target_domain);
+
+-- Extract links from existing articles, same as `ibis_markdown::extract_article_links`
+insert into article_link (source_id, target_title, target_domain)
+select distinct
+    id,
+    replace(m[1], '_', ' '),
+    m[2]
+from
+    article,
+    regexp_matches(text, '\[\[([^@\]]*)@([^\]|]*)', 'g') as m
+on conflict do nothing;
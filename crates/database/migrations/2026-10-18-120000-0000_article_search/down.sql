@@ -0,0 +1,2 @@
+ALTER TABLE article
+    DROP COLUMN search_vector;
@@ -0,0 +1,5 @@
+-- Title matches are weighted higher than matches in the article text
+ALTER TABLE article
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (setweight(to_tsvector('english', title), 'A') || setweight(to_tsvector('english', text), 'D')) STORED;
+
+CREATE INDEX idx_article_search_vector ON article USING GIN (search_vector);
@@ -0,0 +1,14 @@
+CREATE TABLE media (
+    id serial PRIMARY KEY,
+    -- sha256 hash of the file content, followed by file extension
+    filename varchar(255) NOT NULL,
+    ap_id varchar(255) NOT NULL UNIQUE,
+    instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    mime_type varchar(255) NOT NULL,
+    local boolean NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (instance_id, filename)
+);
+
+CREATE INDEX ON media (filename);
@@ -0,0 +1,4 @@
+alter table edit drop column awaiting_review;
+alter table article drop column review_edits;
+alter table instance drop column review_all_edits;
+alter table instance drop column review_new_user_edits;
@@ -0,0 +1,6 @@
+alter table edit add column awaiting_review bool not null default false;
+alter table article add column review_edits bool not null default false;
+alter table instance add column review_all_edits bool not null default false;
+alter table instance add column review_new_user_edits int not null default 0;
+
+create index on edit (awaiting_review) where awaiting_review;
@@ -0,0 +1,3 @@
+alter table local_user add column admin bool not null default false;
+update local_user set admin = true where role = 'admin';
+alter table local_user drop column role;
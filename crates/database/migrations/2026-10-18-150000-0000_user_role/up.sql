@@ -0,0 +1,4 @@
+alter table local_user add column role text not null default 'user'
+    check (role in ('user', 'trusted_editor', 'moderator', 'admin'));
+update local_user set role = 'admin' where admin;
+alter table local_user drop column admin;
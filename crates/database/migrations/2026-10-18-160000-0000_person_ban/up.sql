@@ -0,0 +1,13 @@
+-- Bans are never deleted so that they remain as record. Unbanning sets the expiry to the current
+-- time.
+CREATE TABLE person_ban (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    moderator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    reason text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    -- null for permanent bans
+    expires timestamptz
+);
+
+CREATE INDEX ON person_ban (person_id);
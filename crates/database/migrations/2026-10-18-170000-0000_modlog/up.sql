@@ -0,0 +1,17 @@
+-- Public record of actions taken by moderators and admins. Targets are kept nullable so that
+-- entries remain if the article or user is deleted.
+CREATE TABLE modlog (
+    id serial PRIMARY KEY,
+    moderator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    action text NOT NULL CHECK (action IN ('protect_article', 'unprotect_article',
+        'remove_article', 'restore_article', 'revert_article', 'enable_review', 'disable_review',
+        'approve_edit', 'reject_edit', 'set_role', 'ban_user', 'unban_user', 'update_instance')),
+    reason text,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE SET NULL,
+    target_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX ON modlog (published DESC);
+CREATE INDEX ON modlog (moderator_id);
+CREATE INDEX ON modlog (article_id);
@@ -0,0 +1,13 @@
+DELETE FROM modlog
+WHERE action IN ('resolve_report', 'dismiss_report');
+
+ALTER TABLE modlog DROP CONSTRAINT modlog_action_check;
+
+ALTER TABLE modlog ADD CONSTRAINT modlog_action_check CHECK (action IN ('protect_article',
+    'unprotect_article', 'remove_article', 'restore_article', 'revert_article', 'enable_review',
+    'disable_review', 'approve_edit', 'reject_edit', 'set_role', 'ban_user', 'unban_user',
+    'update_instance'));
+
+ALTER TABLE notification DROP COLUMN report_id;
+
+DROP TABLE report;
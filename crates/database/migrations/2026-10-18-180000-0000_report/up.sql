@@ -0,0 +1,29 @@
+-- Reports by users about articles, edits or comments which need attention from moderators.
+-- Reports about remote content are also sent to the origin instance as Flag activity, whose
+-- id is stored in ap_id.
+CREATE TABLE report (
+    id serial PRIMARY KEY,
+    ap_id varchar(255) NOT NULL UNIQUE,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    edit_id int REFERENCES edit ON UPDATE CASCADE ON DELETE CASCADE,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    reason text NOT NULL,
+    status text NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved', 'dismissed')),
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz,
+    CHECK (num_nonnulls (edit_id, comment_id) < 2)
+);
+
+CREATE INDEX ON report (status, published);
+
+ALTER TABLE notification ADD COLUMN report_id int REFERENCES report ON UPDATE CASCADE ON DELETE CASCADE;
+
+ALTER TABLE notification ADD CONSTRAINT notification_local_user_id_report_id_key UNIQUE (local_user_id, report_id);
+
+ALTER TABLE modlog DROP CONSTRAINT modlog_action_check;
+
+ALTER TABLE modlog ADD CONSTRAINT modlog_action_check CHECK (action IN ('protect_article',
+    'unprotect_article', 'remove_article', 'restore_article', 'revert_article', 'enable_review',
+    'disable_review', 'approve_edit', 'reject_edit', 'set_role', 'ban_user', 'unban_user',
+    'update_instance', 'resolve_report', 'dismiss_report'));
@@ -0,0 +1,11 @@
+-- Instances which are allowed or blocked for federation. If there are any active allow entries,
+-- federation is limited to those domains. Replaces the allowlist and blocklist config options.
+CREATE TABLE federation_list (
+    id serial PRIMARY KEY,
+    domain varchar(255) NOT NULL UNIQUE,
+    mode text NOT NULL CHECK (mode IN ('allow', 'block')),
+    reason text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    -- null for permanent entries
+    expires timestamptz
+);
@@ -0,0 +1,3 @@
+DROP TABLE delivery_queue;
+
+DROP TABLE delivery_status;
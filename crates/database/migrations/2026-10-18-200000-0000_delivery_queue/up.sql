@@ -0,0 +1,28 @@
+-- Outgoing activities which still need to be delivered to a remote inbox. Rows are removed
+-- after successful delivery, or together with the sent activity after a week.
+CREATE TABLE delivery_queue (
+    id serial PRIMARY KEY,
+    activity_id varchar(255) REFERENCES sent_activity ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    actor_id varchar(255) NOT NULL,
+    inbox varchar(255) NOT NULL,
+    domain varchar(255) NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt timestamptz NOT NULL DEFAULT now(),
+    last_error text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX ON delivery_queue (next_attempt);
+
+CREATE INDEX ON delivery_queue (domain);
+
+-- Result of activity deliveries for each remote domain.
+CREATE TABLE delivery_status (
+    domain varchar(255) PRIMARY KEY,
+    fail_count int NOT NULL DEFAULT 0,
+    last_success timestamptz,
+    last_failure timestamptz,
+    last_error text,
+    -- set on the first failure after a successful delivery
+    failing_since timestamptz
+);
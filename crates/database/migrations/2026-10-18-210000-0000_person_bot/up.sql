@@ -0,0 +1,2 @@
+ALTER TABLE person
+    ADD COLUMN bot boolean NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+ALTER TABLE edit
+    DROP COLUMN bot;
+
+DROP TABLE api_token;
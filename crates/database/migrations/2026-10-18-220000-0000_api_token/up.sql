@@ -0,0 +1,19 @@
+-- Personal tokens which let bots and scripts use the API on behalf of a user. Only a hash of
+-- the token is stored, the token itself is shown once after creation.
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    token_hash text NOT NULL UNIQUE,
+    scopes text[] NOT NULL,
+    -- edits made with the token are marked as bot edits
+    bot_edits boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now(),
+    last_used timestamptz,
+    CHECK (scopes <@ ARRAY['read', 'edit_articles', 'comment'])
+);
+
+CREATE INDEX ON api_token (local_user_id);
+
+ALTER TABLE edit
+    ADD COLUMN bot boolean NOT NULL DEFAULT FALSE;
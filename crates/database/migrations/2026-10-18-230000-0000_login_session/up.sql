@@ -0,0 +1,14 @@
+-- Logins of local users. The id of the session is included in the login token, which is only
+-- accepted while the session exists. Tokens from before this migration have no session, so
+-- all users need to login again.
+CREATE TABLE login_session (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    token_id uuid NOT NULL UNIQUE,
+    user_agent text,
+    ip text,
+    published timestamptz NOT NULL DEFAULT now(),
+    last_seen timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX ON login_session (local_user_id);
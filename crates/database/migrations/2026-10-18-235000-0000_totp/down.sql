@@ -0,0 +1,8 @@
+ALTER TABLE instance
+    DROP COLUMN require_totp_for_moderators;
+
+DROP TABLE totp_recovery_code;
+
+ALTER TABLE local_user
+    DROP COLUMN totp_secret,
+    DROP COLUMN totp_enabled;
@@ -0,0 +1,17 @@
+-- Optional two-factor authentication with time based one-time passwords. The secret is stored
+-- during setup, and only required for login once it was confirmed with a valid code.
+ALTER TABLE local_user
+    ADD COLUMN totp_secret text,
+    ADD COLUMN totp_enabled bool NOT NULL DEFAULT FALSE;
+
+-- Single use codes to login when the authenticator app is lost. Only the hash is stored.
+CREATE TABLE totp_recovery_code (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    code_hash text NOT NULL
+);
+
+CREATE INDEX ON totp_recovery_code (local_user_id);
+
+ALTER TABLE instance
+    ADD COLUMN require_totp_for_moderators bool NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+ALTER TABLE local_user
+    DROP COLUMN totp_last_step,
+    DROP COLUMN totp_failed_attempts,
+    DROP COLUMN totp_last_failure;
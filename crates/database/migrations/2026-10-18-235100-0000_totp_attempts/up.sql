@@ -0,0 +1,6 @@
+-- Time step of the last accepted one-time code, so that a code can't be used twice. Failed
+-- attempts are counted to limit guessing of codes.
+ALTER TABLE local_user
+    ADD COLUMN totp_last_step bigint,
+    ADD COLUMN totp_failed_attempts int NOT NULL DEFAULT 0,
+    ADD COLUMN totp_last_failure timestamptz;
@@ -0,0 +1,2 @@
+ALTER TABLE person
+    DROP COLUMN instance_id;
@@ -0,0 +1,11 @@
+-- Instance which the user belongs to, so that users of a blocked instance can be found
+-- without matching on their ap_id. Null if the instance is not known yet.
+ALTER TABLE person
+    ADD COLUMN instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE SET NULL;
+
+UPDATE person
+SET instance_id = instance.id
+FROM instance
+WHERE split_part(person.ap_id, '/', 3) = instance.domain;
+
+CREATE INDEX idx_person_instance_id ON person (instance_id);
@@ -0,0 +1,2 @@
+ALTER TABLE instance
+    DROP COLUMN federation_list_imported;
@@ -0,0 +1,4 @@
+-- Entries from the deprecated allowlist and blocklist config options are only imported once,
+-- so that entries removed by an admin don't come back after restart.
+ALTER TABLE instance
+    ADD COLUMN federation_list_imported boolean NOT NULL DEFAULT FALSE;
@@ -0,0 +1,11 @@
+-- Domain for changes to the federation allowlist and blocklist
+ALTER TABLE modlog
+    ADD COLUMN target_domain text;
+
+ALTER TABLE modlog DROP CONSTRAINT modlog_action_check;
+
+ALTER TABLE modlog ADD CONSTRAINT modlog_action_check CHECK (action IN ('protect_article',
+    'unprotect_article', 'remove_article', 'restore_article', 'revert_article', 'enable_review',
+    'disable_review', 'approve_edit', 'reject_edit', 'set_role', 'ban_user', 'unban_user',
+    'update_instance', 'resolve_report', 'dismiss_report', 'allow_domain', 'block_domain',
+    'remove_federation_list_entry'));
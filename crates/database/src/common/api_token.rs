@@ -0,0 +1,81 @@
+use super::newtypes::{ApiTokenId, LocalUserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::api_token,
+};
+
+/// Prefix of all API tokens, so that they can be distinguished from login tokens.
+pub const API_TOKEN_PREFIX: &str = "ibis_";
+
+/// Permissions of an API token. Every token can read, regardless of its scopes.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    Read,
+    /// Create, edit and fork articles, and upload media
+    EditArticles,
+    /// Write and edit comments
+    Comment,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiTokenScope::Read => "read",
+            ApiTokenScope::EditArticles => "edit_articles",
+            ApiTokenScope::Comment => "comment",
+        }
+    }
+}
+
+impl FromStr for ApiTokenScope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "read" => ApiTokenScope::Read,
+            "edit_articles" => ApiTokenScope::EditArticles,
+            "comment" => ApiTokenScope::Comment,
+            _ => return Err(anyhow::anyhow!("Unknown api token scope {s}")),
+        })
+    }
+}
+
+/// Personal token which bots and scripts can use instead of logging in.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = api_token, check_for_backend(diesel::pg::Pg)))]
+pub struct ApiToken {
+    pub id: ApiTokenId,
+    pub local_user_id: LocalUserId,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub scopes: Vec<ApiTokenScope>,
+    /// Edits made with this token are marked as bot edits
+    pub bot_edits: bool,
+    pub published: DateTime<Utc>,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        scope == ApiTokenScope::Read || self.scopes.contains(&scope)
+    }
+}
+
+/// Returned once after a token is created. The token itself can't be read again later.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreateApiTokenResponse {
+    pub api_token: ApiToken,
+    pub token: String,
+}
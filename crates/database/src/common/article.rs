@@ -2,6 +2,7 @@ use super::{
     comment::CommentView,
     instance::Instance,
     newtypes::{ArticleId, ConflictId, EditId, InstanceId, PersonId},
+    pagination::PaginationCursor,
     user::Person,
 };
 use crate::{DbUrl, common::utils::extract_domain};
@@ -39,7 +40,10 @@ pub struct Conflict {
 pub struct ArticleView {
     pub article: Article,
     pub instance: Instance,
+    /// Newest comments of the article
     pub comments: Vec<CommentView>,
+    /// Set if there are older comments than those in `comments`
+    pub comments_next_page: Option<PaginationCursor>,
     pub latest_version: EditVersion,
     pub following: bool,
 }
@@ -59,6 +63,8 @@ pub struct Article {
     pub removed: bool,
     pub updated: DateTime<Utc>,
     pub pending: bool,
+    /// All edits to this article need to be approved by a reviewer
+    pub review_edits: bool,
 }
 
 impl Article {
@@ -90,17 +96,147 @@ pub struct Edit {
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
     pub pending: bool,
+    /// Edit is not applied to the article until it is approved by a reviewer. Such edits are
+    /// also `pending`.
+    pub awaiting_review: bool,
+    /// Edit was made by a script with an API token, and can be hidden like edits from bot
+    /// accounts
+    pub bot: bool,
+}
+
+impl Edit {
+    /// Number of characters which were added by this edit, negative if more were removed.
+    pub fn size_delta(&self) -> i64 {
+        let mut lines = self.diff.lines().peekable();
+        let mut delta = 0;
+        while let Some(line) = lines.next() {
+            let sign = if line.starts_with('+') && !line.starts_with("+++ ") {
+                1
+            } else if line.starts_with('-') && !line.starts_with("--- ") {
+                -1
+            } else {
+                continue;
+            };
+            let mut len = line.chars().count();
+            // Line ending is not counted if it is missing at the end of the text
+            if lines.peek().is_some_and(|l| l.starts_with('\\')) {
+                len -= 1;
+            }
+            delta += sign * i64::try_from(len).unwrap_or_default();
+        }
+        delta
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct EditView {
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub edit: Edit,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub article: Article,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub creator: Person,
 }
 
+/// Current article text, with each line attributed to the edit which last changed it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleBlame {
+    pub lines: Vec<BlameLine>,
+    /// All edits which are referenced by `lines`, oldest first
+    pub edits: Vec<EditView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BlameLine {
+    pub text: String,
+    pub edit: EditVersion,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiffGranularity {
+    #[default]
+    Line,
+    Word,
+    /// Compare the rendered HTML of both versions
+    Rendered,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiffChangeKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// Consecutive lines or words with the same kind of change.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DiffChunk {
+    pub kind: DiffChangeKind,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleDiff {
+    /// Changes between both versions, empty for [DiffGranularity::Rendered]
+    pub chunks: Vec<DiffChunk>,
+    /// Rendered HTML of the new version with changes wrapped in `<ins>` and `<del>`, only for
+    /// [DiffGranularity::Rendered]
+    pub html: Option<String>,
+}
+
+/// Article in a list of links, with only the fields needed to link to it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct LinkedArticle {
+    pub id: ArticleId,
+    pub title: String,
+    /// Domain of the instance where the article is hosted
+    pub domain: String,
+    pub local: bool,
+}
+
+/// Title which is linked from other articles, but no article exists for it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct WantedArticle {
+    pub title: String,
+    pub domain: String,
+    /// Number of articles linking to this title
+    pub link_count: i64,
+}
+
+/// Article found by full-text search, either in the local database or on a remote instance.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleSearchResult {
+    pub title: String,
+    pub ap_id: DbUrl,
+    /// Domain of the instance where the article is hosted
+    pub origin: String,
+    /// Excerpt of the article text with matching words wrapped in `<b>` tags. The rest of
+    /// the text is HTML-escaped.
+    pub snippet: String,
+    /// Relevance of the result, higher is better
+    pub rank: f32,
+    /// Only set for articles which are stored in the local database. Results from remote
+    /// instances need to be resolved first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub article: Option<Article>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SearchArticleResponse {
+    pub results: Vec<ArticleSearchResult>,
+    /// Cursor to read the next page, or `None` if this is the last page. Results from remote
+    /// instances are only included in the first page.
+    pub next_page: Option<PaginationCursor>,
+    /// Domains of remote instances which didn't respond in time
+    pub timed_out: Vec<String>,
+}
+
 /// The version hash of a specific edit. Generated by taking an SHA256 hash of the diff
 /// and using the first 16 bytes so that it fits into UUID.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -141,10 +277,10 @@ pub struct ApiConflict {
     pub published: DateTime<Utc>,
 }
 
-pub fn can_edit_article(article: &Article, is_admin: bool) -> Result<(), anyhow::Error> {
-    if article.protected && !(article.local && is_admin) {
+pub fn can_edit_article(article: &Article, can_moderate: bool) -> Result<(), anyhow::Error> {
+    if article.protected && !(article.local && can_moderate) {
         return Err(anyhow!(
-            "Article is protected, only admins on origin instance can edit".to_string()
+            "Article is protected, only moderators on origin instance can edit".to_string()
         ));
     }
     Ok(())
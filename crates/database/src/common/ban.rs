@@ -0,0 +1,41 @@
+use super::{
+    newtypes::{PersonBanId, PersonId},
+    user::Person,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::person_ban,
+};
+
+/// Ban of a local or remote user. Banned local users cannot login, and activities from banned
+/// remote users are rejected.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = person_ban, check_for_backend(diesel::pg::Pg)))]
+pub struct PersonBan {
+    pub id: PersonBanId,
+    pub person_id: PersonId,
+    pub moderator_id: PersonId,
+    pub reason: String,
+    pub published: DateTime<Utc>,
+    /// Permanent ban if `None`
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl PersonBan {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_none_or(|e| e > now)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PersonBanView {
+    pub ban: PersonBan,
+    pub person: Person,
+    pub moderator: Person,
+}
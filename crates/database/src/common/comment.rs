@@ -38,10 +38,13 @@ pub struct CommentView {
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct CommentViewWithArticle {
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub comment: Comment,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub creator: Person,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
     pub article: Article,
 }
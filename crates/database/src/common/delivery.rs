@@ -0,0 +1,41 @@
+use chrono::{DateTime, Days, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::delivery_status,
+};
+
+/// Instances which could not be reached for this many days are considered dead. No more
+/// activities are sent to them until they are reachable again.
+pub const DEAD_INSTANCE_DAYS: u64 = 3;
+
+/// Result of delivering activities to a remote domain.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = delivery_status, check_for_backend(diesel::pg::Pg)))]
+pub struct DeliveryStatus {
+    pub domain: String,
+    /// Number of failed deliveries since the last success
+    pub fail_count: i32,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_failure: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Time of the first failure after the last successful delivery
+    pub failing_since: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DeliveryStatusView {
+    pub status: DeliveryStatus,
+    /// Number of activities waiting to be delivered
+    pub queue_depth: i64,
+    pub dead: bool,
+}
+
+impl DeliveryStatus {
+    pub fn is_dead(&self, now: DateTime<Utc>) -> bool {
+        let cutoff = now.checked_sub_days(Days::new(DEAD_INSTANCE_DAYS));
+        self.failing_since.zip(cutoff).is_some_and(|(f, c)| f < c)
+    }
+}
@@ -0,0 +1,58 @@
+use super::newtypes::FederationListId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::federation_list,
+};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum FederationMode {
+    /// If any domain is allowed, federation with all other domains is blocked
+    Allow,
+    Block,
+}
+
+impl FederationMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FederationMode::Allow => "allow",
+            FederationMode::Block => "block",
+        }
+    }
+}
+
+impl FromStr for FederationMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "allow" => FederationMode::Allow,
+            "block" => FederationMode::Block,
+            _ => return Err(anyhow::anyhow!("Unknown federation mode {s}")),
+        })
+    }
+}
+
+/// Domain which is allowed or blocked for federation. The list is public so that other admins
+/// can see which instances are blocked and why.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = federation_list, check_for_backend(diesel::pg::Pg)))]
+pub struct FederationListEntry {
+    pub id: FederationListId,
+    pub domain: String,
+    pub mode: FederationMode,
+    pub reason: String,
+    pub published: DateTime<Utc>,
+    /// Permanent entry if `None`
+    pub expires: Option<DateTime<Utc>>,
+}
@@ -35,6 +35,20 @@ pub struct Instance {
     #[cfg(feature = "ssr")]
     pub instances_url: DbUrl,
     pub name: Option<String>,
+    /// Edits to local articles by all users except trusted editors need to be approved by a
+    /// reviewer
+    pub review_all_edits: bool,
+    /// Edits by users with fewer than this many approved edits on the instance need to be
+    /// approved by a reviewer. Zero to disable.
+    pub review_new_user_edits: i32,
+    /// Moderators and admins can only use their permissions after enabling two-factor
+    /// authentication
+    pub require_totp_for_moderators: bool,
+    /// Entries from the deprecated federation config options were added to the federation
+    /// list
+    #[cfg(feature = "ssr")]
+    #[serde(skip)]
+    pub federation_list_imported: bool,
 }
 
 impl Instance {
@@ -0,0 +1,86 @@
+use super::newtypes::{InstanceId, MediaId, PersonId};
+use crate::DbUrl;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::media,
+};
+
+/// Path under which uploaded media is served, relative to the instance domain.
+pub const MEDIA_PATH: &str = "/media";
+
+/// Mime types which can be uploaded.
+pub const ALLOWED_MEDIA_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// An uploaded image. Media from remote instances is only stored as reference, the file
+/// itself is served by the instance where it was uploaded.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = media, check_for_backend(diesel::pg::Pg)))]
+pub struct Media {
+    pub id: MediaId,
+    /// Hash of the file content with file extension, used to embed the media in articles
+    /// with `![description](/media/{filename})`
+    pub filename: String,
+    pub ap_id: DbUrl,
+    pub instance_id: InstanceId,
+    pub creator_id: Option<PersonId>,
+    pub mime_type: String,
+    pub local: bool,
+    pub published: DateTime<Utc>,
+}
+
+impl Media {
+    /// Relative path for use in article markdown.
+    pub fn path(&self) -> String {
+        format!("{MEDIA_PATH}/{}", self.filename)
+    }
+
+    /// Relative path of the scaled down preview image.
+    pub fn thumbnail_path(&self) -> String {
+        format!("{MEDIA_PATH}/thumbnail/{}", self.filename)
+    }
+
+    /// Check that the filename has the format of uploaded media, and that the url points to
+    /// the file with this name.
+    pub fn is_valid_url(filename: &str, url: &Url) -> bool {
+        let Some((hash, extension)) = filename.split_once('.') else {
+            return false;
+        };
+        hash.len() == 64
+            && hash.chars().all(|c| c.is_ascii_hexdigit())
+            && (1..=10).contains(&extension.len())
+            && extension.chars().all(|c| c.is_ascii_alphanumeric())
+            && url.path() == format!("{MEDIA_PATH}/{filename}")
+            && url.query().is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_url() {
+        let filename = format!("{}.png", "a1".repeat(32));
+        let url =
+            |path: &str| Url::parse(&format!("https://example.com{path}")).expect("parse url");
+        assert!(Media::is_valid_url(
+            &filename,
+            &url(&format!("/media/{filename}"))
+        ));
+        assert!(!Media::is_valid_url(
+            &filename,
+            &url(&format!("/other/{filename}"))
+        ));
+        assert!(!Media::is_valid_url(
+            &filename,
+            &url(&format!("/media/{filename}?x=1"))
+        ));
+        assert!(!Media::is_valid_url("abc.png", &url("/media/abc.png")));
+        assert!(!Media::is_valid_url("../x.png", &url("/x.png")));
+    }
+}
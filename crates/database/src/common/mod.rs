@@ -1,8 +1,18 @@
+pub mod api_token;
 pub mod article;
+pub mod ban;
 pub mod comment;
+pub mod delivery;
+pub mod federation_list;
 pub mod instance;
+pub mod media;
+pub mod modlog;
 pub mod newtypes;
 pub mod notifications;
+pub mod pagination;
+pub mod report;
+pub mod session;
+pub mod totp;
 pub mod user;
 pub mod utils;
 
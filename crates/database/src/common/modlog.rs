@@ -0,0 +1,135 @@
+use super::{
+    newtypes::{ArticleId, ModlogId, PersonId},
+    user::Person,
+};
+use crate::DbUrl;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::{article, modlog},
+};
+
+/// Type of action taken by a moderator or admin.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum ModAction {
+    ProtectArticle,
+    UnprotectArticle,
+    RemoveArticle,
+    RestoreArticle,
+    RevertArticle,
+    EnableReview,
+    DisableReview,
+    ApproveEdit,
+    RejectEdit,
+    SetRole,
+    BanUser,
+    UnbanUser,
+    UpdateInstance,
+    ResolveReport,
+    DismissReport,
+    AllowDomain,
+    BlockDomain,
+    RemoveFederationListEntry,
+}
+
+impl ModAction {
+    pub const ALL: [ModAction; 18] = [
+        ModAction::ProtectArticle,
+        ModAction::UnprotectArticle,
+        ModAction::RemoveArticle,
+        ModAction::RestoreArticle,
+        ModAction::RevertArticle,
+        ModAction::EnableReview,
+        ModAction::DisableReview,
+        ModAction::ApproveEdit,
+        ModAction::RejectEdit,
+        ModAction::SetRole,
+        ModAction::BanUser,
+        ModAction::UnbanUser,
+        ModAction::UpdateInstance,
+        ModAction::ResolveReport,
+        ModAction::DismissReport,
+        ModAction::AllowDomain,
+        ModAction::BlockDomain,
+        ModAction::RemoveFederationListEntry,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModAction::ProtectArticle => "protect_article",
+            ModAction::UnprotectArticle => "unprotect_article",
+            ModAction::RemoveArticle => "remove_article",
+            ModAction::RestoreArticle => "restore_article",
+            ModAction::RevertArticle => "revert_article",
+            ModAction::EnableReview => "enable_review",
+            ModAction::DisableReview => "disable_review",
+            ModAction::ApproveEdit => "approve_edit",
+            ModAction::RejectEdit => "reject_edit",
+            ModAction::SetRole => "set_role",
+            ModAction::BanUser => "ban_user",
+            ModAction::UnbanUser => "unban_user",
+            ModAction::UpdateInstance => "update_instance",
+            ModAction::ResolveReport => "resolve_report",
+            ModAction::DismissReport => "dismiss_report",
+            ModAction::AllowDomain => "allow_domain",
+            ModAction::BlockDomain => "block_domain",
+            ModAction::RemoveFederationListEntry => "remove_federation_list_entry",
+        }
+    }
+}
+
+impl FromStr for ModAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModAction::ALL
+            .into_iter()
+            .find(|a| a.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown mod action {s}"))
+    }
+}
+
+/// Entry in the public moderation log.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = modlog, check_for_backend(diesel::pg::Pg)))]
+pub struct Modlog {
+    pub id: ModlogId,
+    pub moderator_id: PersonId,
+    pub action: ModAction,
+    pub reason: Option<String>,
+    pub article_id: Option<ArticleId>,
+    pub target_person_id: Option<PersonId>,
+    pub published: DateTime<Utc>,
+    /// Domain whose entry in the federation list was changed
+    pub target_domain: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModlogView {
+    pub modlog: Modlog,
+    pub moderator: Person,
+    pub article: Option<ModlogArticle>,
+    pub target_person: Option<Person>,
+}
+
+/// Article referenced by a log entry. Leaves out the text, so that the public log doesn't
+/// expose the content of removed articles.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg)))]
+pub struct ModlogArticle {
+    pub id: ArticleId,
+    pub title: String,
+    pub ap_id: DbUrl,
+    pub local: bool,
+}
@@ -2,6 +2,7 @@ use super::{
     article::{Article, Edit},
     comment::Comment,
     newtypes::{ConflictId, NotificationId},
+    report::Report,
     user::Person,
 };
 use chrono::{DateTime, Utc};
@@ -25,4 +26,6 @@ pub enum ApiNotificationData {
     },
     Comment(Comment),
     Edit(Edit),
+    /// Only sent to moderators
+    Report(Report),
 }
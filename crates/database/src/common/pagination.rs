@@ -0,0 +1,128 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Position in a list after which the next page starts. It is returned by the API with each
+/// page and needs to be passed back unchanged, the content is an implementation detail.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct PaginationCursor(pub String);
+
+impl PaginationCursor {
+    /// Cursor for an item with the given sort key and id.
+    pub fn new(key: Option<DateTime<Utc>>, id: i32) -> Self {
+        let key = key
+            .map(|k| k.timestamp_micros().to_string())
+            .unwrap_or_default();
+        PaginationCursor(format!("{key}_{id}"))
+    }
+
+    /// Cursor for an item in a list which is sorted by relevance instead of time.
+    pub fn new_ranked(rank: f32, id: i32) -> Self {
+        PaginationCursor(format!("r{}_{id}", rank.to_bits()))
+    }
+
+    /// Cursor for an item in a list which is sorted by a name like article title or domain,
+    /// together with a number such as the id or a count.
+    pub fn new_named(key: i64, name: &str) -> Self {
+        PaginationCursor(format!("n{key}_{name}"))
+    }
+
+    pub fn parse(&self) -> Result<(Option<DateTime<Utc>>, i32), anyhow::Error> {
+        let invalid = || anyhow!("Invalid pagination cursor");
+        let (key, id) = self.0.split_once('_').ok_or_else(invalid)?;
+        let key = if key.is_empty() {
+            None
+        } else {
+            let micros = key.parse().map_err(|_| invalid())?;
+            Some(DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?)
+        };
+        Ok((key, id.parse().map_err(|_| invalid())?))
+    }
+
+    pub fn parse_ranked(&self) -> Result<(f32, i32), anyhow::Error> {
+        let invalid = || anyhow!("Invalid pagination cursor");
+        let (rank, id) = self
+            .0
+            .strip_prefix('r')
+            .and_then(|c| c.split_once('_'))
+            .ok_or_else(invalid)?;
+        let rank = f32::from_bits(rank.parse().map_err(|_| invalid())?);
+        Ok((rank, id.parse().map_err(|_| invalid())?))
+    }
+
+    pub fn parse_named(&self) -> Result<(i64, String), anyhow::Error> {
+        let invalid = || anyhow!("Invalid pagination cursor");
+        let (key, name) = self
+            .0
+            .strip_prefix('n')
+            .and_then(|c| c.split_once('_'))
+            .ok_or_else(invalid)?;
+        Ok((key.parse().map_err(|_| invalid())?, name.to_string()))
+    }
+}
+
+/// A single page of a list which may be too long to return at once.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    /// Cursor to read the next page, or `None` if this is the last page
+    pub next_page: Option<PaginationCursor>,
+}
+
+impl<T> Default for PaginatedResponse<T> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            next_page: None,
+        }
+    }
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Build a page from items which were read with a limit of `limit + 1`. The extra item
+    /// only shows that there is a next page, so it is removed.
+    pub fn new(
+        mut items: Vec<T>,
+        limit: Option<i64>,
+        cursor: impl Fn(&T) -> PaginationCursor,
+    ) -> Self {
+        let limit = limit.and_then(|l| usize::try_from(l).ok());
+        let next_page = match limit {
+            Some(limit) if items.len() > limit => {
+                items.truncate(limit);
+                items.last().map(cursor)
+            }
+            _ => None,
+        };
+        Self { items, next_page }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedResponse<U> {
+        PaginatedResponse {
+            items: self.items.into_iter().map(f).collect(),
+            next_page: self.next_page,
+        }
+    }
+}
+
+#[test]
+fn test_pagination_cursor() -> Result<(), anyhow::Error> {
+    let published = DateTime::from_timestamp_micros(1_700_000_000_123_456);
+    let cursor = PaginationCursor::new(published, 42);
+    assert_eq!("1700000000123456_42", cursor.0);
+    assert_eq!((published, 42), cursor.parse()?);
+    assert_eq!((None, 7), PaginationCursor::new(None, 7).parse()?);
+    assert!(PaginationCursor("abc".to_string()).parse().is_err());
+
+    let ranked = PaginationCursor::new_ranked(0.0607927, 3);
+    assert_eq!((0.0607927, 3), ranked.parse_ranked()?);
+    assert!(ranked.parse().is_err());
+    assert!(cursor.parse_ranked().is_err());
+
+    let named = PaginationCursor::new_named(5, "Manu_Chao & friends");
+    assert_eq!((5, "Manu_Chao & friends".to_string()), named.parse_named()?);
+    assert!(named.parse().is_err());
+    assert!(cursor.parse_named().is_err());
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use super::{
+    article::{Article, Edit},
+    comment::Comment,
+    newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+    user::Person,
+};
+use crate::DbUrl;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::report,
+};
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    /// Waiting for a moderator
+    #[default]
+    Open,
+    /// Moderator took action on the reported content
+    Resolved,
+    /// Moderator decided that no action is necessary
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Resolved => "resolved",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "open" => ReportStatus::Open,
+            "resolved" => ReportStatus::Resolved,
+            "dismissed" => ReportStatus::Dismissed,
+            _ => return Err(anyhow::anyhow!("Unknown report status {s}")),
+        })
+    }
+}
+
+/// Report about an article, or about a single edit or comment on the article if the
+/// corresponding id is set.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = report, check_for_backend(diesel::pg::Pg)))]
+pub struct Report {
+    pub id: ReportId,
+    pub ap_id: DbUrl,
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+    pub status: ReportStatus,
+    pub published: DateTime<Utc>,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct ReportView {
+    #[cfg_attr(feature = "ssr", diesel(embed))]
+    pub report: Report,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
+    pub creator: Person,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
+    pub article: Article,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
+    pub edit: Option<Edit>,
+    #[cfg_attr(feature = "ssr", diesel(embed))]
+    pub comment: Option<Comment>,
+}
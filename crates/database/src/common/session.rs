@@ -0,0 +1,35 @@
+use super::newtypes::{LocalUserId, LoginSessionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+#[cfg(feature = "ssr")]
+use {
+    diesel::{Queryable, Selectable},
+    ibis_database_schema::login_session,
+};
+
+/// A login of a local user on some device. The login token is only valid as long as the
+/// session exists.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = login_session, check_for_backend(diesel::pg::Pg)))]
+pub struct LoginSession {
+    pub id: LoginSessionId,
+    pub local_user_id: LocalUserId,
+    /// Included as `jti` claim in the login token
+    #[serde(skip)]
+    pub token_id: Uuid,
+    pub user_agent: Option<String>,
+    /// Address from which the user logged in
+    pub ip: Option<String>,
+    pub published: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ListSessionsResponse {
+    /// All sessions of the user, most recently used first
+    pub sessions: Vec<LoginSession>,
+    /// Session which was used for this request, if any
+    pub current: Option<LoginSessionId>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Error message of a login attempt with valid password, where the user has two-factor
+/// authentication enabled but no code was given. Used by the frontend to ask for the code.
+pub const TOTP_REQUIRED_ERROR: &str = "Two-factor authentication code required";
+
+/// Secret for a new two-factor setup, which still needs to be confirmed with a valid code.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TotpSetupResponse {
+    /// Base32 encoded secret, for manual entry in the authenticator app
+    pub secret: String,
+    /// `otpauth://` uri containing the secret
+    pub uri: String,
+    /// The uri as QR code in SVG format
+    pub qr_code: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TotpEnableResponse {
+    /// Single use codes for login without the authenticator app. These are only shown once.
+    pub recovery_codes: Vec<String>,
+}
@@ -1,7 +1,11 @@
-use super::newtypes::{LocalUserId, PersonId};
+use super::{
+    ban::PersonBan,
+    newtypes::{InstanceId, LocalUserId, PersonId},
+};
 use crate::{DbUrl, common::utils::extract_domain};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use url::Url;
 #[cfg(feature = "ssr")]
 use {
@@ -26,10 +30,86 @@ pub struct LocalUser {
     #[serde(skip)]
     pub password_encrypted: Option<String>,
     pub person_id: PersonId,
-    pub admin: bool,
     pub email: Option<String>,
     pub email_verified: bool,
     pub email_notifications: bool,
+    pub role: Role,
+    #[serde(skip)]
+    pub totp_secret: Option<String>,
+    /// Login requires a one-time code in addition to the password
+    pub totp_enabled: bool,
+    #[serde(skip)]
+    pub totp_last_step: Option<i64>,
+    #[serde(skip)]
+    pub totp_failed_attempts: i32,
+    #[serde(skip)]
+    pub totp_last_failure: Option<DateTime<Utc>>,
+}
+
+/// Permissions of a local user. Each role includes the permissions of the roles before it.
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    /// Edits don't need to be reviewed
+    TrustedEditor,
+    /// Can protect, remove and revert articles, review edits and ban users
+    Moderator,
+    /// Can additionally change instance settings and assign roles
+    Admin,
+}
+
+impl Role {
+    pub fn is_admin(self) -> bool {
+        self == Role::Admin
+    }
+
+    pub fn can_moderate(self) -> bool {
+        self >= Role::Moderator
+    }
+
+    pub fn skips_review(self) -> bool {
+        self >= Role::TrustedEditor
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::TrustedEditor => "trusted_editor",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "user" => Role::User,
+            "trusted_editor" => Role::TrustedEditor,
+            "moderator" => Role::Moderator,
+            "admin" => Role::Admin,
+            _ => return Err(anyhow::anyhow!("Unknown role {s}")),
+        })
+    }
+}
+
+/// Public profile of a user, with the role if it is a local user.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PersonView {
+    pub person: Person,
+    pub role: Option<Role>,
+    pub ban: Option<PersonBan>,
 }
 
 /// Federation related data from a local or remote user.
@@ -50,6 +130,10 @@ pub struct Person {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    /// Automated account, whose edits can be hidden from recent changes
+    pub bot: bool,
+    /// Instance of the user, if it is known
+    pub instance_id: Option<InstanceId>,
 }
 
 impl Person {
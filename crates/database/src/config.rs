@@ -16,7 +16,15 @@ pub struct IbisConfig {
     pub setup: IbisConfigSetup,
     /// Domain for HTTP and frontend
     pub domain: String,
+    /// Set this if ibis runs behind a reverse proxy, so that the client address for login
+    /// sessions is read from the X-Forwarded-For header. Otherwise the header is ignored, as
+    /// any client could set it.
+    #[default(false)]
+    #[doku(example = "false")]
+    pub trusted_proxy: bool,
     pub federation: IbisConfigFederation,
+    /// Storage for uploaded images
+    pub media: IbisConfigMedia,
     pub options: Options,
     pub email: Option<IbisConfigEmail>,
     pub oauth_providers: Vec<OAuthProvider>,
@@ -105,17 +113,32 @@ pub struct IbisConfigFederation {
     /// Domain used for federation. Only set this if it is different from config.domain (eg for debugging).
     #[doku(example = "example.com")]
     pub domain: Option<String>,
-    /// Comma separated list of instances which are allowed for federation. If set, federation
-    /// with other domains is blocked
+    /// Deprecated, use the federation list in the admin settings instead. Domains from this
+    /// comma separated list are added to the allowlist on startup.
     #[default(None)]
     #[doku(example = "good.com,friends.org")]
     pub allowlist: Option<String>,
-    /// Comma separated list of instances which are blocked for federation
+    /// Deprecated, use the federation list in the admin settings instead. Domains from this
+    /// comma separated list are added to the blocklist on startup.
     #[default(None)]
     #[doku(example = "evil.com,bad.org")]
     pub blocklist: Option<String>,
 }
 
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigMedia {
+    /// Folder where uploaded files and their thumbnails are stored
+    #[default("media")]
+    #[doku(example = "media")]
+    pub upload_dir: String,
+    /// Maximum size of a single upload in bytes
+    #[default(10_485_760)]
+    #[doku(example = "10485760")]
+    pub max_upload_size: usize,
+}
+
 #[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document)]
 #[serde(deny_unknown_fields)]
 /// oauth provider with client_secret - should never be sent to the client
@@ -22,7 +22,24 @@ pub(crate) async fn send_notification_email(
                 &context.conf.domain
             );
 
-            let (subject, html) = if let Some(comment) = data.comment {
+            let (subject, html) = if let Some(report) = data.report {
+                let reason = report.reason;
+                let reports_link = format!(
+                    "{}://{}/admin/reports",
+                    http_protocol_str(),
+                    &context.conf.domain
+                );
+                (
+                    format!("New report on article {article_title}"),
+                    format!(
+                        r#"<h1>Report</h1>
+                    <br>
+                    <div>{creator_title} reported "{article_title}": {reason}</div>
+                    <br>
+                    <a href="{reports_link}">reports</a>"#,
+                    ),
+                )
+            } else if let Some(comment) = data.comment {
                 let comment_text = render_comment_markdown(&comment.content);
                 (
                     format!("New comment on article {article_title}"),
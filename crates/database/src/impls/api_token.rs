@@ -0,0 +1,118 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        api_token::{API_TOKEN_PREFIX, ApiToken, ApiTokenScope},
+        ban::PersonBan,
+        newtypes::{ApiTokenId, LocalUserId},
+        user::LocalUserView,
+    },
+    error::BackendResult,
+    impls::{session::LAST_USED_INTERVAL, user::LocalUserViewQuery},
+};
+use chrono::Utc;
+use diesel::{
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::now,
+    insert_into,
+    update,
+};
+use ibis_database_schema::api_token;
+use sha2::{Digest, Sha256};
+use std::ops::DerefMut;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = api_token, check_for_backend(diesel::pg::Pg))]
+struct ApiTokenForm {
+    local_user_id: LocalUserId,
+    name: String,
+    token_hash: String,
+    scopes: Vec<ApiTokenScope>,
+    bot_edits: bool,
+}
+
+impl ApiToken {
+    /// Create a new token, and return it together with the secret token string. Only the hash
+    /// of the secret is stored.
+    pub fn create(
+        local_user_id: LocalUserId,
+        name: String,
+        scopes: Vec<ApiTokenScope>,
+        bot_edits: bool,
+        context: &IbisContext,
+    ) -> BackendResult<(Self, String)> {
+        let mut conn = context.db_pool.get()?;
+        let token = format!("{API_TOKEN_PREFIX}{}", Uuid::new_v4().simple());
+        let form = ApiTokenForm {
+            local_user_id,
+            name,
+            token_hash: hash_token(&token),
+            scopes,
+            bot_edits,
+        };
+        let api_token = insert_into(api_token::table)
+            .values(form)
+            .get_result(conn.deref_mut())?;
+        Ok((api_token, token))
+    }
+
+    /// Tokens of the user, newest first.
+    pub fn list(local_user_id: LocalUserId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(api_token::table
+            .filter(api_token::local_user_id.eq(local_user_id))
+            .order(api_token::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Revoke a token, which only works for tokens of the given user.
+    pub fn delete(
+        id: ApiTokenId,
+        local_user_id: LocalUserId,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delete(
+            api_token::table
+                .find(id)
+                .filter(api_token::local_user_id.eq(local_user_id)),
+        )
+        .get_result(conn.deref_mut())?)
+    }
+
+    /// Revoke all tokens of the user.
+    pub fn delete_all(local_user_id: LocalUserId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(api_token::table.filter(api_token::local_user_id.eq(local_user_id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Find the token and its user, and store the current time as last use (at most once per
+    /// [`LAST_USED_INTERVAL`]). Tokens of banned users are rejected.
+    pub fn validate(token: &str, context: &IbisContext) -> BackendResult<(Self, LocalUserView)> {
+        let mut conn = context.db_pool.get()?;
+        let mut api_token: Self = api_token::table
+            .filter(api_token::token_hash.eq(hash_token(token)))
+            .get_result(conn.deref_mut())?;
+        if api_token
+            .last_used
+            .is_none_or(|l| l < Utc::now() - LAST_USED_INTERVAL)
+        {
+            api_token = update(api_token::table.find(api_token.id))
+                .set(api_token::last_used.eq(now))
+                .get_result(conn.deref_mut())?;
+        }
+        let user = LocalUserView::read(LocalUserViewQuery::Id(api_token.local_user_id), context)?;
+        PersonBan::check_not_banned(user.person.id, context)?;
+        Ok((api_token, user))
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token))
+}
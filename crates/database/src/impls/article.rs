@@ -2,9 +2,10 @@ use super::{is_conflict, notifications::Notification};
 use crate::{
     DbUrl,
     common::{
-        article::{Article, ArticleView, EditVersion},
+        article::{Article, ArticleSearchResult, ArticleView, EditVersion},
         comment::Comment,
         newtypes::{ArticleId, InstanceId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
         user::LocalUserView,
     },
     error::BackendResult,
@@ -14,20 +15,35 @@ use chrono::{DateTime, Utc};
 use diesel::{
     AsChangeset,
     BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
     NullableExpressionMethods,
-    PgTextExpressionMethods,
+    OptionalExtension,
     QueryDsl,
     RunQueryDsl,
-    dsl::{delete, max, not, now, update},
+    SelectableHelper,
+    dsl::{delete, max, not, now, sql, update},
     insert_into,
+    result::Error,
+    sql_types::{Bool, Float, Text},
 };
-use ibis_database_schema::{article, article_follow, edit, instance};
+use ibis_database_schema::{article, article_follow, article_redirect, edit, instance};
 use std::ops::DerefMut;
 use url::Url;
 
+/// Number of comments which are included in [ArticleView], older ones need to be read separately.
+const ARTICLE_VIEW_COMMENTS: i64 = 50;
+
+/// Control characters which mark the start and end of a search match in `ts_headline()`.
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+/// Control characters which replace `<` and `>` in the text passed to `ts_headline()`, as it
+/// would otherwise strip anything that looks like an html tag.
+const LESS_THAN: char = '\u{4}';
+const GREATER_THAN: char = '\u{5}';
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = article, check_for_backend(diesel::pg::Pg))]
 pub struct DbArticleForm {
@@ -71,7 +87,9 @@ impl Article {
         let mut conn = context.db_pool.get()?;
         let article = insert_into(article::table)
             .values(form)
-            .get_result::<Self>(conn.deref_mut())?;
+            .returning(Self::as_returning())
+            .get_result(conn.deref_mut())?;
+        article.update_links(context)?;
 
         Notification::notify_article(&article, creator_id, context).await?;
         Ok(article)
@@ -85,24 +103,31 @@ impl Article {
         let mut conn = context.db_pool.get()?;
         let article = insert_into(article::table)
             .values(&form)
-            .get_result::<Self>(conn.deref_mut());
-        Ok(if is_conflict(&article) {
+            .returning(Self::as_returning())
+            .get_result(conn.deref_mut());
+        let article = if is_conflict(&article) {
             update(article::table)
                 .filter(article::ap_id.eq(form.ap_id.clone()))
                 .set(form)
-                .get_result::<Self>(conn.deref_mut())?
+                .returning(Self::as_returning())
+                .get_result(conn.deref_mut())?
         } else {
             let a = article?;
             Notification::notify_article(&a, creator_id, context).await?;
             a
-        })
+        };
+        article.update_links(context)?;
+        Ok(article)
     }
 
     pub fn update_text(id: ArticleId, text: &str, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(diesel::update(article::dsl::article.find(id))
+        let article: Self = diesel::update(article::dsl::article.find(id))
             .set((article::dsl::text.eq(text), article::dsl::updated.eq(now)))
-            .get_result(conn.deref_mut())?)
+            .returning(Self::as_returning())
+            .get_result(conn.deref_mut())?;
+        article.update_links(context)?;
+        Ok(article)
     }
 
     pub fn update_protected(
@@ -113,6 +138,31 @@ impl Article {
         let mut conn = context.db_pool.get()?;
         Ok(diesel::update(article::dsl::article.find(id))
             .set(article::dsl::protected.eq(locked))
+            .returning(Self::as_returning())
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn update_review_edits(
+        id: ArticleId,
+        review_edits: bool,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::review_edits.eq(review_edits))
+            .returning(Self::as_returning())
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn update_pending(
+        id: ArticleId,
+        pending: bool,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::pending.eq(pending))
+            .returning(Self::as_returning())
             .get_result(conn.deref_mut())?)
     }
 
@@ -124,6 +174,7 @@ impl Article {
         let mut conn = context.db_pool.get()?;
         Ok(diesel::update(article::dsl::article.find(id))
             .set(article::dsl::removed.eq(removed))
+            .returning(Self::as_returning())
             .get_result(conn.deref_mut())?)
     }
 
@@ -132,9 +183,21 @@ impl Article {
         Ok(article::table
             .find(id)
             .filter(not(article::removed))
+            .select(Self::as_select())
             .get_result(conn.deref_mut())?)
     }
 
+    /// Read the local article with the given title, including removed articles.
+    pub fn read_local_by_title(title: &str, context: &IbisContext) -> BackendResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article::table
+            .filter(article::local)
+            .filter(article::title.eq(title))
+            .select(Self::as_select())
+            .get_result(conn.deref_mut())
+            .optional()?)
+    }
+
     pub fn read_view<'a>(
         params: impl Into<ArticleViewQuery<'a>>,
         user: Option<&LocalUserView>,
@@ -150,10 +213,18 @@ impl Article {
                     .and(article_follow::local_user_id.nullable().eq(local_user_id))),
             )
             .into_boxed();
-        if !user.map(|u| u.local_user.admin).unwrap_or_default() {
+        if !user
+            .map(|u| u.local_user.role.can_moderate())
+            .unwrap_or_default()
+        {
             query = query.filter(not(article::removed));
         }
-        query = match params.into() {
+        let params = params.into();
+        let redirect = match &params {
+            ArticleViewQuery::Name(title, domain) => Some((title.to_string(), domain.clone())),
+            ArticleViewQuery::Id(_) => None,
+        };
+        query = match params {
             ArticleViewQuery::Id(id) => query.filter(article::id.eq(id)),
             ArticleViewQuery::Name(title, domain) => {
                 query = query.filter(article::dsl::title.eq(title));
@@ -165,47 +236,150 @@ impl Article {
             }
         };
 
-        let (article, instance, following): (Article, _, _) = query
+        let res = query
             .select((
-                article::all_columns,
+                Article::as_select(),
                 instance::all_columns,
                 article_follow::local_user_id.nullable().is_not_null(),
             ))
-            .get_result(conn.deref_mut())?;
-        let comments = Comment::read_for_article(article.id, context)?;
+            .get_result(conn.deref_mut());
+        let (article, instance, following): (Article, _, _) = match (res, redirect) {
+            (Err(Error::NotFound), Some((title, domain))) => {
+                // The article may have been renamed, follow redirect to its current title
+                let id = Self::read_redirect(&title, domain, context)?;
+                return Self::read_view(id, user, context);
+            }
+            (res, _) => res?,
+        };
+        let comments =
+            Comment::read_for_article(article.id, None, Some(ARTICLE_VIEW_COMMENTS), context)?;
         let latest_version = article.latest_edit_version(context)?;
         Ok(ArticleView {
             article,
             instance,
-            comments,
+            comments: comments.items,
+            comments_next_page: comments.next_page,
             latest_version,
             following,
         })
     }
 
+    /// Read article by its ap_id, or by a previous ap_id if it was renamed.
     pub fn read_from_ap_id(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(article::table
+        let article = article::table
             .filter(article::dsl::ap_id.eq(ap_id))
-            .get_result(conn.deref_mut())?)
+            .select(Self::as_select())
+            .get_result(conn.deref_mut());
+        Ok(match article {
+            Err(Error::NotFound) => article_redirect::table
+                .inner_join(article::table)
+                .filter(article_redirect::ap_id.eq(ap_id))
+                .select(Article::as_select())
+                .get_result(conn.deref_mut())?,
+            a => a?,
+        })
+    }
+
+    /// Find the article which previously had the given title.
+    fn read_redirect(
+        title: &str,
+        domain: Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<ArticleId> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = article_redirect::table
+            .inner_join(article::table.inner_join(instance::table))
+            .filter(article_redirect::title.eq(title))
+            .order_by(article_redirect::published.desc())
+            .select(article_redirect::article_id)
+            .into_boxed();
+        if let Some(domain) = domain {
+            query = query.filter(instance::domain.eq(domain));
+        } else {
+            query = query.filter(article::local.eq(true));
+        }
+        Ok(query.first(conn.deref_mut())?)
+    }
+
+    /// Change the title and ap_id of an article. The previous values are stored as redirect so
+    /// that old links keep working.
+    pub fn rename(
+        id: ArticleId,
+        new_title: String,
+        new_ap_id: DbUrl,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(conn.transaction::<_, Error, _>(|conn| {
+            let old = article::table
+                .find(id)
+                .select(Article::as_select())
+                .get_result(conn)?;
+            // Remove redirect in case the article is moved back to a previous title
+            delete(article_redirect::table.filter(article_redirect::ap_id.eq(&new_ap_id)))
+                .execute(conn)?;
+            insert_into(article_redirect::table)
+                .values((
+                    article_redirect::article_id.eq(id),
+                    article_redirect::title.eq(old.title),
+                    article_redirect::ap_id.eq(old.ap_id),
+                ))
+                .execute(conn)?;
+            update(article::table.find(id))
+                .set((article::title.eq(new_title), article::ap_id.eq(new_ap_id)))
+                .returning(Article::as_returning())
+                .get_result(conn)
+        })?)
     }
 
-    /// Read all articles, ordered by most recently edited first.
+    /// Make the article also reachable under another title, without renaming it.
+    pub fn create_redirect(
+        id: ArticleId,
+        title: String,
+        ap_id: DbUrl,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(article_redirect::table)
+            .values((
+                article_redirect::article_id.eq(id),
+                article_redirect::title.eq(title),
+                article_redirect::ap_id.eq(ap_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// All previous ap_ids of the article, in case it was renamed.
+    pub fn read_redirect_ap_ids(&self, context: &IbisContext) -> BackendResult<Vec<DbUrl>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article_redirect::table
+            .filter(article_redirect::article_id.eq(self.id))
+            .select(article_redirect::ap_id)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Read all articles, ordered by most recently edited first. Without `limit` all articles
+    /// after `cursor` are returned at once.
     ///
     /// TODO: Should get rid of only_local param and rely on instance_id
     pub fn read_all(
         only_local: Option<bool>,
         instance_id: Option<InstanceId>,
         include_removed: bool,
+        cursor: Option<PaginationCursor>,
+        limit: Option<i64>,
         context: &IbisContext,
-    ) -> BackendResult<Vec<Self>> {
+    ) -> BackendResult<PaginatedResponse<Self>> {
         let mut conn = context.db_pool.get()?;
         let mut query = article::table
             .inner_join(edit::table)
             .inner_join(instance::table)
             .group_by(article::id)
-            .order_by(max(edit::published).desc())
-            .select(article::all_columns)
+            .order_by((max(edit::published).desc(), article::id.desc()))
+            .select((Article::as_select(), max(edit::published)))
             .into_boxed();
 
         if let Some(true) = only_local {
@@ -219,31 +393,106 @@ impl Article {
         if let Some(instance_id) = instance_id {
             query = query.filter(instance::dsl::id.eq(instance_id));
         }
-        Ok(query.get_results(&mut conn)?)
+        if let Some(cursor) = cursor {
+            let (last_edit, id) = cursor.parse()?;
+            query = query.having(
+                max(edit::published)
+                    .lt(last_edit)
+                    .or(max(edit::published).eq(last_edit).and(article::id.lt(id))),
+            );
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit + 1);
+        }
+        let articles: Vec<(Self, Option<DateTime<Utc>>)> = query.get_results(&mut conn)?;
+        Ok(PaginatedResponse::new(articles, limit, |(a, last_edit)| {
+            PaginationCursor::new(*last_edit, a.id.0)
+        })
+        .map(|(a, _)| a))
     }
 
-    pub fn search(query: &str, context: &IbisContext) -> BackendResult<Vec<Self>> {
+    /// Full-text search over article titles and text, ordered by relevance. Matches in the
+    /// title are ranked higher than matches in the text.
+    pub fn search(
+        query: &str,
+        instance_id: Option<InstanceId>,
+        only_local: bool,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<ArticleSearchResult>> {
         let mut conn = context.db_pool.get()?;
-        let replaced = query
-            .replace('%', "\\%")
-            .replace('_', "\\_")
-            .replace(' ', "%");
-        let replaced = format!("%{replaced}%");
-        Ok(article::table
+        // The text search config needs to match the one of the `article.search_vector` column
+        let matches = sql::<Bool>("article.search_vector @@ websearch_to_tsquery('english', ")
+            .bind::<Text, _>(query.to_string())
+            .sql(")");
+        let rank = sql::<Float>("ts_rank(article.search_vector, websearch_to_tsquery('english', ")
+            .bind::<Text, _>(query.to_string())
+            .sql("))");
+        // Matches and angle brackets are marked with placeholders, which are removed from the
+        // text beforehand. Html is escaped afterwards in `highlight_snippet()`.
+        let snippet = sql::<Text>("ts_headline('english', translate(article.text, ")
+            .bind::<Text, _>(format!(
+                "<>{HIGHLIGHT_START}{HIGHLIGHT_END}{LESS_THAN}{GREATER_THAN}"
+            ))
+            .sql(", ")
+            .bind::<Text, _>(format!("{LESS_THAN}{GREATER_THAN}"))
+            .sql("), websearch_to_tsquery('english', ")
+            .bind::<Text, _>(query.to_string())
+            .sql("), ")
+            .bind::<Text, _>(format!(
+                "MaxFragments=2, MaxWords=20, MinWords=10, StartSel={HIGHLIGHT_START}, StopSel={HIGHLIGHT_END}"
+            ))
+            .sql(")");
+        let mut db_query = article::table
+            .inner_join(instance::table)
             .filter(not(article::removed))
             .filter(not(article::pending))
-            .filter(
-                article::dsl::title
-                    .ilike(&replaced)
-                    .or(article::dsl::text.ilike(&replaced)),
-            )
-            .get_results(conn.deref_mut())?)
+            .filter(matches)
+            .select((
+                Article::as_select(),
+                instance::domain,
+                snippet,
+                rank.clone(),
+            ))
+            .order_by((rank.clone().desc(), article::id))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (last_rank, id) = cursor.parse_ranked()?;
+            db_query = db_query.filter(
+                rank.clone()
+                    .lt(last_rank)
+                    .or(rank.eq(last_rank).and(article::id.gt(id))),
+            );
+        }
+        if only_local {
+            db_query = db_query.filter(article::local);
+        }
+        if let Some(instance_id) = instance_id {
+            db_query = db_query.filter(article::instance_id.eq(instance_id));
+        }
+        let res: Vec<(Article, String, String, f32)> = db_query.get_results(conn.deref_mut())?;
+        Ok(
+            PaginatedResponse::new(res, Some(limit), |(article, _, _, rank)| {
+                PaginationCursor::new_ranked(*rank, article.id.0)
+            })
+            .map(|(article, origin, snippet, rank)| ArticleSearchResult {
+                title: article.title.clone(),
+                ap_id: article.ap_id.clone(),
+                origin,
+                snippet: highlight_snippet(&snippet),
+                rank,
+                article: Some(article),
+            }),
+        )
     }
 
     pub fn latest_edit_version(&self, context: &IbisContext) -> BackendResult<EditVersion> {
         let mut conn = context.db_pool.get()?;
         let latest_version: Option<EditVersion> = edit::table
             .filter(edit::dsl::article_id.eq(self.id))
+            .filter(not(edit::dsl::awaiting_review))
             .order_by(edit::dsl::id.desc())
             .limit(1)
             .select(edit::dsl::hash)
@@ -290,3 +539,33 @@ impl Article {
         Ok(())
     }
 }
+
+/// Escape html in a search snippet so that it can be rendered directly, and highlight the
+/// matches with `<b>` tags.
+fn highlight_snippet(snippet: &str) -> String {
+    let mut res = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' | LESS_THAN => res.push_str("&lt;"),
+            '>' | GREATER_THAN => res.push_str("&gt;"),
+            HIGHLIGHT_START => res.push_str("<b>"),
+            HIGHLIGHT_END => res.push_str("</b>"),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_highlight_snippet() {
+        assert_eq!(
+            "a <b>match</b> &lt;b&gt;not&lt;/b&gt; &amp;amp;",
+            highlight_snippet("a \u{2}match\u{3} \u{4}b\u{5}not\u{4}/b\u{5} &amp;")
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        MAIN_PAGE_NAME,
+        article::{Article, LinkedArticle, WantedArticle},
+        instance::Instance,
+        newtypes::ArticleId,
+        pagination::{PaginatedResponse, PaginationCursor},
+    },
+    error::BackendResult,
+};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    JoinOnDsl,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::{count_star, exists, not},
+    insert_into,
+};
+use ibis_database_schema::{article, article_link, article_redirect, instance};
+use ibis_markdown::extract_article_links;
+use std::ops::DerefMut;
+
+impl Article {
+    /// Store all articles linked from the text, replacing previously stored links.
+    pub(super) fn update_links(&self, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(article_link::table.filter(article_link::source_id.eq(self.id)))
+            .execute(conn.deref_mut())?;
+        let forms: Vec<_> = extract_article_links(&self.text)
+            .into_iter()
+            .map(|l| {
+                (
+                    article_link::source_id.eq(self.id),
+                    article_link::target_title.eq(l.title),
+                    article_link::target_domain.eq(l.domain),
+                )
+            })
+            .collect();
+        insert_into(article_link::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Articles which link to the given article, either to its current or a previous title,
+    /// sorted by title.
+    pub fn read_backlinks(
+        id: ArticleId,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<LinkedArticle>> {
+        let mut conn = context.db_pool.get()?;
+        let (title, domain): (String, String) = article::table
+            .inner_join(instance::table)
+            .filter(article::id.eq(id))
+            .select((article::title, instance::domain))
+            .get_result(conn.deref_mut())?;
+        let mut titles: Vec<String> = article_redirect::table
+            .filter(article_redirect::article_id.eq(id))
+            .select(article_redirect::title)
+            .get_results(conn.deref_mut())?;
+        titles.push(title);
+        let mut query = article::table
+            .inner_join(article_link::table)
+            .inner_join(instance::table)
+            .filter(article_link::target_domain.eq(domain))
+            .filter(article_link::target_title.eq_any(titles))
+            .filter(article::id.ne(id))
+            .filter(not(article::removed))
+            .filter(not(article::pending))
+            .select((
+                article::id,
+                article::title,
+                instance::domain,
+                article::local,
+            ))
+            .distinct()
+            .order_by((article::title, article::id))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (id, title) = cursor.parse_named()?;
+            let id = i32::try_from(id)?;
+            query = query.filter(
+                article::title
+                    .gt(title.clone())
+                    .or(article::title.eq(title).and(article::id.gt(id))),
+            );
+        }
+        let articles = query.get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(
+            articles,
+            Some(limit),
+            |a: &LinkedArticle| PaginationCursor::new_named(a.id.0.into(), &a.title),
+        ))
+    }
+
+    /// Titles on the local instance which are linked from other articles, but don't exist yet.
+    /// Links from removed articles are ignored. Titles with the most links come first.
+    pub fn read_wanted(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<WantedArticle>> {
+        let domain = Instance::read_local(context)?.domain;
+        let mut conn = context.db_pool.get()?;
+        let source = diesel::alias!(article as source);
+        let existing = article::table
+            .inner_join(instance::table)
+            .filter(article::title.eq(article_link::target_title))
+            .filter(instance::domain.eq(article_link::target_domain));
+        let redirected = article_redirect::table
+            .inner_join(article::table.inner_join(instance::table))
+            .filter(article_redirect::title.eq(article_link::target_title))
+            .filter(instance::domain.eq(article_link::target_domain));
+        let mut query = article_link::table
+            .inner_join(source.on(source.field(article::id).eq(article_link::source_id)))
+            .filter(article_link::target_domain.eq(domain))
+            .filter(not(source.field(article::removed)))
+            .filter(not(source.field(article::pending)))
+            .filter(not(exists(existing)))
+            .filter(not(exists(redirected)))
+            .group_by((article_link::target_title, article_link::target_domain))
+            .select((
+                article_link::target_title,
+                article_link::target_domain,
+                count_star(),
+            ))
+            .order_by((count_star().desc(), article_link::target_title))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (link_count, title) = cursor.parse_named()?;
+            query = query.having(
+                count_star().lt(link_count).or(count_star()
+                    .eq(link_count)
+                    .and(article_link::target_title.gt(title))),
+            );
+        }
+        let wanted = query.get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(
+            wanted,
+            Some(limit),
+            |w: &WantedArticle| PaginationCursor::new_named(w.link_count, &w.title),
+        ))
+    }
+
+    /// Local articles which are not linked from any other article, sorted by title.
+    pub fn read_orphaned(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<LinkedArticle>> {
+        let domain = Instance::read_local(context)?.domain;
+        let mut conn = context.db_pool.get()?;
+        let (source, redirect_source) =
+            diesel::alias!(article as source, article as redirect_source);
+        let linked = article_link::table
+            .inner_join(source.on(source.field(article::id).eq(article_link::source_id)))
+            .filter(article_link::target_domain.eq(&domain))
+            .filter(article_link::target_title.eq(article::title))
+            .filter(source.field(article::id).ne(article::id))
+            .filter(not(source.field(article::removed)));
+        let linked_redirect = article_redirect::table
+            .inner_join(
+                article_link::table.on(article_link::target_title.eq(article_redirect::title)),
+            )
+            .inner_join(
+                redirect_source.on(redirect_source
+                    .field(article::id)
+                    .eq(article_link::source_id)),
+            )
+            .filter(article_redirect::article_id.eq(article::id))
+            .filter(article_link::target_domain.eq(&domain))
+            .filter(article_link::source_id.ne(article::id))
+            .filter(not(redirect_source.field(article::removed)));
+        let mut query = article::table
+            .inner_join(instance::table)
+            .filter(article::local)
+            .filter(not(article::removed))
+            .filter(article::title.ne(MAIN_PAGE_NAME))
+            .filter(not(exists(linked)))
+            .filter(not(exists(linked_redirect)))
+            .order_by(article::title)
+            .select((
+                article::id,
+                article::title,
+                instance::domain,
+                article::local,
+            ))
+            .limit(limit + 1)
+            .into_boxed();
+        // Titles of local articles are unique, so there is no need to compare ids
+        if let Some(cursor) = cursor {
+            let (_, title) = cursor.parse_named()?;
+            query = query.filter(article::title.gt(title));
+        }
+        let articles = query.get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(
+            articles,
+            Some(limit),
+            |a: &LinkedArticle| PaginationCursor::new_named(a.id.0.into(), &a.title),
+        ))
+    }
+}
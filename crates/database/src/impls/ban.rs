@@ -0,0 +1,136 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        ban::{PersonBan, PersonBanView},
+        newtypes::PersonId,
+        pagination::{PaginatedResponse, PaginationCursor},
+        user::Person,
+    },
+    error::BackendResult,
+};
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    Insertable,
+    JoinOnDsl,
+    NullableExpressionMethods,
+    OptionalExtension,
+    PgSortExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+    dsl::now,
+    insert_into,
+    update,
+};
+use ibis_database_schema::{person, person_ban};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = person_ban, check_for_backend(diesel::pg::Pg))]
+pub struct PersonBanInsertForm {
+    pub person_id: PersonId,
+    pub moderator_id: PersonId,
+    pub reason: String,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl PersonBan {
+    pub fn create(form: &PersonBanInsertForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(person_ban::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// The ban which is currently in effect for the user, if any. If there are multiple, returns
+    /// the one which lasts longest.
+    pub fn read_active(person_id: PersonId, context: &IbisContext) -> BackendResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(person_ban::table
+            .filter(person_ban::person_id.eq(person_id))
+            .filter(
+                person_ban::expires
+                    .is_null()
+                    .or(person_ban::expires.gt(now)),
+            )
+            .order(person_ban::expires.desc().nulls_first())
+            .first(conn.deref_mut())
+            .optional()?)
+    }
+
+    /// Returns an error with the ban reason if the user is currently banned.
+    pub fn check_not_banned(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        if let Some(ban) = Self::read_active(person_id, context)? {
+            let until = match ban.expires {
+                Some(expires) => format!("until {}", expires.format("%Y-%m-%d %H:%M UTC")),
+                None => "permanently".to_string(),
+            };
+            return Err(anyhow!("User is banned {until}: {}", ban.reason).into());
+        }
+        Ok(())
+    }
+
+    /// End all active bans of the user.
+    pub fn unban(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(person_ban::table)
+            .filter(person_ban::person_id.eq(person_id))
+            .filter(
+                person_ban::expires
+                    .is_null()
+                    .or(person_ban::expires.gt(now)),
+            )
+            .set(person_ban::expires.eq(now))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// All bans which are currently active, newest first.
+    pub fn list_active(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<PersonBanView>> {
+        let mut conn = context.db_pool.get()?;
+        let moderator = diesel::alias!(person as moderator);
+        let mut query = person_ban::table
+            .inner_join(person::table.on(person::id.eq(person_ban::person_id)))
+            .inner_join(moderator.on(moderator.field(person::id).eq(person_ban::moderator_id)))
+            .filter(
+                person_ban::expires
+                    .is_null()
+                    .or(person_ban::expires.gt(now)),
+            )
+            .select((
+                person_ban::all_columns,
+                person::all_columns,
+                moderator.fields(person::all_columns),
+            ))
+            .order((person_ban::published.desc(), person_ban::id.desc()))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                person_ban::published
+                    .nullable()
+                    .lt(published)
+                    .or(person_ban::published
+                        .nullable()
+                        .eq(published)
+                        .and(person_ban::id.lt(id))),
+            );
+        }
+        let bans = query.get_results::<(PersonBan, Person, Person)>(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(bans, Some(limit), |(ban, ..)| {
+            PaginationCursor::new(Some(ban.published), ban.id.0)
+        })
+        .map(|(ban, person, moderator)| PersonBanView {
+            ban,
+            person,
+            moderator,
+        }))
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     common::{
         comment::{Comment, CommentView, CommentViewWithArticle},
         newtypes::{ArticleId, CommentId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
         user::Person,
     },
     error::BackendResult,
@@ -12,11 +13,13 @@ use crate::{
 use chrono::{DateTime, Utc};
 use diesel::{
     AsChangeset,
+    BoolExpressionMethods,
     ExpressionMethods,
     Insertable,
     NullableExpressionMethods,
     QueryDsl,
     RunQueryDsl,
+    SelectableHelper,
     dsl::insert_into,
     update,
 };
@@ -97,7 +100,8 @@ impl Comment {
             .find(id)
             .inner_join(person::table)
             .inner_join(article::table)
-            .get_result::<CommentViewWithArticle>(conn.deref_mut())?;
+            .select(CommentViewWithArticle::as_select())
+            .get_result(conn.deref_mut())?;
         if comment_view.comment.deleted {
             comment_view.comment.content = String::new();
         }
@@ -111,27 +115,61 @@ impl Comment {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Comments by the user which were published after `since` and are not deleted.
+    pub fn list_for_creator_since(
+        creator_id: PersonId,
+        since: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(comment::table
+            .filter(comment::creator_id.eq(creator_id))
+            .filter(comment::published.ge(since))
+            .filter(comment::deleted.eq(false))
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Comments of an article, newest first. Without `limit` all comments after `cursor` are
+    /// returned at once.
     pub fn read_for_article(
         article_id: ArticleId,
+        cursor: Option<PaginationCursor>,
+        limit: Option<i64>,
         context: &IbisContext,
-    ) -> BackendResult<Vec<CommentView>> {
+    ) -> BackendResult<PaginatedResponse<CommentView>> {
         let mut conn = context.db_pool.get()?;
-        let comments = comment::table
+        let mut query = comment::table
             .inner_join(person::table)
             .filter(comment::article_id.eq(article_id))
-            .order_by(comment::published.desc())
-            .get_results::<CommentView>(conn.deref_mut())?;
+            .order_by((comment::published.desc(), comment::id.desc()))
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                comment::published
+                    .nullable()
+                    .lt(published)
+                    .or(comment::published
+                        .nullable()
+                        .eq(published)
+                        .and(comment::id.lt(id))),
+            );
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit + 1);
+        }
+        let comments = query.get_results::<CommentView>(conn.deref_mut())?;
 
         // Clear content of deleted comments. comments themselves are returned
         // so that tree can be rendered.
-        Ok(comments
-            .into_iter()
-            .map(|mut view| {
-                if view.comment.deleted {
-                    view.comment.content = String::new()
-                };
-                view
-            })
-            .collect())
+        Ok(PaginatedResponse::new(comments, limit, |view| {
+            PaginationCursor::new(Some(view.comment.published), view.comment.id.0)
+        })
+        .map(|mut view| {
+            if view.comment.deleted {
+                view.comment.content = String::new()
+            };
+            view
+        }))
     }
 }
@@ -40,6 +40,7 @@ impl Conflict {
             comment_id: None,
             edit_id: None,
             conflict_id: Some(conflict.id),
+            report_id: None,
         };
 
         insert_into(notification::table)
@@ -0,0 +1,271 @@
+use super::IbisContext;
+use crate::{
+    DbUrl,
+    common::{
+        delivery::{DEAD_INSTANCE_DAYS, DeliveryStatus, DeliveryStatusView},
+        pagination::{PaginatedResponse, PaginationCursor},
+    },
+    error::BackendResult,
+};
+use chrono::{DateTime, Days, TimeDelta, Utc};
+use diesel::{
+    Connection,
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    Queryable,
+    RunQueryDsl,
+    Selectable,
+    SelectableHelper,
+    delete,
+    dsl::{count_star, now},
+    insert_into,
+    result::Error,
+    update,
+};
+use ibis_database_schema::{delivery_queue, delivery_status, sent_activity};
+use std::{collections::HashMap, ops::DerefMut};
+
+/// Entries which are being delivered are not picked up again by the retry task during this time.
+const DELIVERY_LEASE: TimeDelta = TimeDelta::minutes(5);
+
+/// Delivery of a sent activity to a single remote inbox.
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = delivery_queue, check_for_backend(diesel::pg::Pg))]
+pub struct DeliveryQueueEntry {
+    pub id: i32,
+    pub activity_id: DbUrl,
+    pub actor_id: DbUrl,
+    pub inbox: DbUrl,
+    pub domain: String,
+    pub attempts: i32,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = delivery_queue, check_for_backend(diesel::pg::Pg))]
+pub struct DeliveryQueueInsertForm {
+    pub activity_id: DbUrl,
+    pub actor_id: DbUrl,
+    pub inbox: DbUrl,
+    pub domain: String,
+}
+
+impl DeliveryQueueEntry {
+    /// Add entries to the queue. They are leased right away so that the caller can attempt the
+    /// first delivery.
+    pub fn create(
+        forms: Vec<DeliveryQueueInsertForm>,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let next_attempt = Utc::now() + DELIVERY_LEASE;
+        let values: Vec<_> = forms
+            .into_iter()
+            .map(|f| (f, delivery_queue::next_attempt.eq(next_attempt)))
+            .collect();
+        Ok(insert_into(delivery_queue::table)
+            .values(values)
+            .returning(Self::as_returning())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Lease entries which are due for another delivery attempt, together with the activity json.
+    pub fn claim_due(limit: i64, context: &IbisContext) -> BackendResult<Vec<(Self, String)>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(conn.transaction::<_, Error, _>(|conn| {
+            let ids: Vec<i32> = delivery_queue::table
+                .filter(delivery_queue::next_attempt.le(now))
+                .order(delivery_queue::next_attempt.asc())
+                .limit(limit)
+                .select(delivery_queue::id)
+                .for_update()
+                .skip_locked()
+                .get_results(conn)?;
+            update(delivery_queue::table.filter(delivery_queue::id.eq_any(&ids)))
+                .set(delivery_queue::next_attempt.eq(Utc::now() + DELIVERY_LEASE))
+                .execute(conn)?;
+            delivery_queue::table
+                .inner_join(sent_activity::table)
+                .filter(delivery_queue::id.eq_any(&ids))
+                .select((Self::as_select(), sent_activity::json))
+                .get_results(conn)
+        })?)
+    }
+
+    pub fn delivered(&self, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, Error, _>(|conn| {
+            delete(delivery_queue::table.find(self.id)).execute(conn)?;
+            insert_into(delivery_status::table)
+                .values((
+                    delivery_status::domain.eq(&self.domain),
+                    delivery_status::last_success.eq(now),
+                ))
+                .on_conflict(delivery_status::domain)
+                .do_update()
+                .set((
+                    delivery_status::fail_count.eq(0),
+                    delivery_status::last_success.eq(now),
+                    delivery_status::failing_since.eq(None::<DateTime<Utc>>),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Schedule another attempt with exponential backoff. If the instance is dead, all of its
+    /// queued activities are dropped instead.
+    pub fn failed(&self, error: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let status = conn.transaction::<_, Error, _>(|conn| {
+            insert_into(delivery_status::table)
+                .values((
+                    delivery_status::domain.eq(&self.domain),
+                    delivery_status::fail_count.eq(1),
+                    delivery_status::last_failure.eq(now),
+                    delivery_status::last_error.eq(error),
+                    delivery_status::failing_since.eq(now),
+                ))
+                .on_conflict(delivery_status::domain)
+                .do_update()
+                .set((
+                    delivery_status::fail_count.eq(delivery_status::fail_count + 1),
+                    delivery_status::last_failure.eq(now),
+                    delivery_status::last_error.eq(error),
+                ))
+                .execute(conn)?;
+            update(delivery_status::table.find(&self.domain))
+                .filter(delivery_status::failing_since.is_null())
+                .set(delivery_status::failing_since.eq(now))
+                .execute(conn)?;
+            delivery_status::table
+                .find(&self.domain)
+                .get_result::<DeliveryStatus>(conn)
+        })?;
+
+        if status.is_dead(Utc::now()) {
+            delete(delivery_queue::table.filter(delivery_queue::domain.eq(&self.domain)))
+                .execute(conn.deref_mut())?;
+        } else {
+            update(delivery_queue::table.find(self.id))
+                .set((
+                    delivery_queue::attempts.eq(delivery_queue::attempts + 1),
+                    delivery_queue::next_attempt.eq(Utc::now() + retry_delay(self.attempts)),
+                    delivery_queue::last_error.eq(error),
+                ))
+                .execute(conn.deref_mut())?;
+        }
+        Ok(())
+    }
+}
+
+/// Wait one minute after the first failure, and double the delay for each further attempt up
+/// to one day.
+fn retry_delay(attempts: i32) -> TimeDelta {
+    let exponent = u32::try_from(attempts).unwrap_or_default().min(11);
+    TimeDelta::minutes(1 << exponent).min(TimeDelta::days(1))
+}
+
+impl DeliveryStatus {
+    /// Domains which have not been reachable for at least [DEAD_INSTANCE_DAYS].
+    pub fn read_dead_domains(context: &IbisContext) -> BackendResult<Vec<String>> {
+        let mut conn = context.db_pool.get()?;
+        let Some(cutoff) = Utc::now().checked_sub_days(Days::new(DEAD_INSTANCE_DAYS)) else {
+            return Ok(vec![]);
+        };
+        Ok(delivery_status::table
+            .filter(delivery_status::failing_since.lt(cutoff))
+            .select(delivery_status::domain)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Called when data was received from the domain, so that activities are sent to it again
+    /// if it was considered dead.
+    pub fn mark_alive(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(delivery_status::table.find(domain))
+            .filter(delivery_status::failing_since.is_not_null())
+            .set((
+                delivery_status::fail_count.eq(0),
+                delivery_status::failing_since.eq(None::<DateTime<Utc>>),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Delivery status of each domain, sorted by domain.
+    pub fn list(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<DeliveryStatusView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = delivery_status::table
+            .order(delivery_status::domain.asc())
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (_, domain) = cursor.parse_named()?;
+            query = query.filter(delivery_status::domain.gt(domain));
+        }
+        let statuses = PaginatedResponse::new(
+            query.get_results(conn.deref_mut())?,
+            Some(limit),
+            |s: &DeliveryStatus| PaginationCursor::new_named(0, &s.domain),
+        );
+        let domains: Vec<_> = statuses.items.iter().map(|s| &s.domain).collect();
+        let queue_depths: HashMap<String, i64> = delivery_queue::table
+            .filter(delivery_queue::domain.eq_any(domains))
+            .group_by(delivery_queue::domain)
+            .select((delivery_queue::domain, count_star()))
+            .get_results(conn.deref_mut())?
+            .into_iter()
+            .collect();
+        let time = Utc::now();
+        Ok(statuses.map(|status| DeliveryStatusView {
+            queue_depth: queue_depths
+                .get(&status.domain)
+                .copied()
+                .unwrap_or_default(),
+            dead: status.is_dead(time),
+            status,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(TimeDelta::minutes(1), retry_delay(0));
+        assert_eq!(TimeDelta::minutes(2), retry_delay(1));
+        assert_eq!(TimeDelta::minutes(64), retry_delay(6));
+        assert_eq!(TimeDelta::minutes(1024), retry_delay(10));
+        // capped at one day
+        assert_eq!(TimeDelta::days(1), retry_delay(11));
+        assert_eq!(TimeDelta::days(1), retry_delay(i32::MAX));
+        assert_eq!(TimeDelta::minutes(1), retry_delay(-1));
+    }
+
+    #[test]
+    fn test_is_dead() {
+        let time = Utc::now();
+        let status = |failing_since| DeliveryStatus {
+            domain: "example.com".to_string(),
+            fail_count: 1,
+            last_success: None,
+            last_failure: failing_since,
+            last_error: None,
+            failing_since,
+        };
+        assert!(!status(None).is_dead(time));
+        assert!(!status(Some(time - TimeDelta::days(2))).is_dead(time));
+        assert!(status(Some(time - TimeDelta::days(4))).is_dead(time));
+    }
+}
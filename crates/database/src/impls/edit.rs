@@ -1,10 +1,12 @@
-use super::{is_conflict, notifications::Notification};
+use super::{edit_snapshot::EditSnapshot, is_conflict, notifications::Notification};
 use crate::{
     DbUrl,
     common::{
         article::{Article, Edit, EditVersion, EditView},
-        newtypes::{ArticleId, PersonId},
-        user::LocalUserView,
+        instance::Instance,
+        newtypes::{ArticleId, EditId, InstanceId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
+        user::{LocalUser, LocalUserView},
     },
     error::BackendResult,
     impls::IbisContext,
@@ -13,12 +15,16 @@ use chrono::{DateTime, Utc};
 use diesel::{
     AsChangeset,
     BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
+    NullableExpressionMethods,
     QueryDsl,
     RunQueryDsl,
-    dsl::{not, update},
+    SelectableHelper,
+    dsl::{delete, not, now, update},
     insert_into,
+    result::Error,
 };
 use diffy::create_patch;
 use ibis_database_schema::{article, edit, person};
@@ -37,6 +43,8 @@ pub struct DbEditForm {
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
     pub pending: bool,
+    pub awaiting_review: bool,
+    pub bot: bool,
 }
 
 impl DbEditForm {
@@ -61,6 +69,8 @@ impl DbEditForm {
             summary,
             published: Utc::now(),
             pending,
+            awaiting_review: false,
+            bot: false,
         })
     }
 
@@ -80,12 +90,22 @@ impl Edit {
             .values(form)
             .get_result::<Edit>(conn.deref_mut());
         Ok(if is_conflict(&edit) {
-            update(edit::table)
+            let existing = Edit::read_from_ap_id(&form.ap_id, context)?;
+            let updated = update(edit::table)
                 .filter(edit::ap_id.eq(form.ap_id.clone()))
                 .set(form)
-                .get_result::<Self>(conn.deref_mut())?
+                .get_result::<Self>(conn.deref_mut())?;
+            // Only changes which affect generated versions require updating the snapshots
+            if (&existing.diff, existing.published, existing.pending)
+                != (&updated.diff, updated.published, updated.pending)
+            {
+                let published = existing.published.min(updated.published);
+                EditSnapshot::refresh(updated.article_id, published, context)?;
+            }
+            updated
         } else {
             let e = edit?;
+            EditSnapshot::refresh(e.article_id, e.published, context)?;
             if notify {
                 Notification::notify_edit(&e, context).await?;
             }
@@ -106,9 +126,15 @@ impl Edit {
             .filter(edit::dsl::hash.eq(version))
             .inner_join(article::table)
             .inner_join(person::table)
+            .select(EditView::as_select())
             .get_result(conn.deref_mut())?)
     }
 
+    pub fn read_from_id(id: EditId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table.find(id).get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(edit::table
@@ -125,29 +151,256 @@ impl Edit {
             .get_results(conn.deref_mut())?)
     }
 
+    /// Whether an edit by `creator_id` has to be approved by a reviewer before it is applied to
+    /// the article. This only applies to local articles, and trusted editors can always edit
+    /// directly.
+    pub fn requires_review(
+        article: &Article,
+        creator_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<bool> {
+        if !article.local {
+            return Ok(false);
+        }
+        let role = LocalUser::read_role(creator_id, context)?.unwrap_or_default();
+        if role.skips_review() {
+            return Ok(false);
+        }
+        let mut conn = context.db_pool.get()?;
+        let instance = Instance::read_local(context)?;
+        if article.review_edits || instance.review_all_edits {
+            return Ok(true);
+        }
+        if instance.review_new_user_edits > 0 {
+            let approved_edits: i64 = edit::table
+                .inner_join(article::table)
+                .filter(article::local)
+                .filter(edit::creator_id.eq(creator_id))
+                .filter(not(edit::pending))
+                .count()
+                .get_result(conn.deref_mut())?;
+            return Ok(approved_edits < i64::from(instance.review_new_user_edits));
+        }
+        Ok(false)
+    }
+
+    /// Ids of local articles which the user edited after `since`.
+    pub fn list_articles_for_creator_since(
+        creator_id: PersonId,
+        since: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ArticleId>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table
+            .inner_join(article::table)
+            .filter(article::local)
+            .filter(edit::creator_id.eq(creator_id))
+            .filter(edit::published.ge(since))
+            .filter(not(edit::pending))
+            .select(edit::article_id)
+            .distinct()
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Edits by the user which are waiting for approval by a reviewer.
+    pub fn list_awaiting_review_for_creator(
+        creator_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table
+            .filter(edit::creator_id.eq(creator_id))
+            .filter(edit::awaiting_review)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Edits which are waiting for approval by a reviewer, oldest first.
+    pub fn list_awaiting_review(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(edit::awaiting_review)
+            .order((edit::published, edit::id))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                edit::published.nullable().gt(published).or(edit::published
+                    .nullable()
+                    .eq(published)
+                    .and(edit::id.gt(id))),
+            );
+        }
+        let edits = query
+            .select(EditView::as_select())
+            .get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(edits, Some(limit), |e| {
+            PaginationCursor::new(Some(e.edit.published), e.edit.id.0)
+        }))
+    }
+
+    /// Make an edit which was waiting for review part of the article history, and change the
+    /// article to the resulting `text` in the same transaction. The edit is published with the
+    /// current time so that it becomes the latest version.
+    pub fn approve(
+        id: EditId,
+        text: &str,
+        context: &IbisContext,
+    ) -> BackendResult<(Self, Article)> {
+        let mut conn = context.db_pool.get()?;
+        let (edit, article) = conn.transaction::<_, Error, _>(|conn| {
+            let edit: Self = update(edit::table.find(id))
+                .set((
+                    edit::pending.eq(false),
+                    edit::awaiting_review.eq(false),
+                    edit::published.eq(Utc::now()),
+                ))
+                .get_result(conn)?;
+            let article = update(article::table.find(edit.article_id))
+                .set((
+                    article::text.eq(text),
+                    article::updated.eq(now),
+                    article::pending.eq(false),
+                ))
+                .returning(Article::as_returning())
+                .get_result(conn)?;
+            Ok((edit, article))
+        })?;
+        article.update_links(context)?;
+        EditSnapshot::refresh(edit.article_id, edit.published, context)?;
+        Ok((edit, article))
+    }
+
+    pub fn delete(id: EditId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(edit::table.find(id)).execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Edits of an article or user, oldest first. Without `limit` all edits after `cursor` are
+    /// returned at once.
     pub fn list_views(
         params: ViewEditParams,
         user: &Option<LocalUserView>,
+        cursor: Option<PaginationCursor>,
+        limit: Option<i64>,
         context: &IbisContext,
-    ) -> BackendResult<Vec<EditView>> {
+    ) -> BackendResult<PaginatedResponse<EditView>> {
         let mut conn = context.db_pool.get()?;
         let person_id = user.as_ref().map(|u| u.person.id).unwrap_or(PersonId(-1));
-        let query = edit::table
+        let mut query = edit::table
             .inner_join(article::table)
             .inner_join(person::table)
             // only the creator can view pending edits
             .filter(not(edit::pending).or(edit::creator_id.eq(person_id)))
+            .order((edit::published, edit::id))
             .into_boxed();
 
-        let query = match params {
+        query = match params {
             ViewEditParams::PersonId(person_id) => query.filter(edit::creator_id.eq(person_id)),
             ViewEditParams::ArticleId(article_id) => query.filter(edit::article_id.eq(article_id)),
         };
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                edit::published.nullable().gt(published).or(edit::published
+                    .nullable()
+                    .eq(published)
+                    .and(edit::id.gt(id))),
+            );
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit + 1);
+        }
+        let edits = query
+            .select(EditView::as_select())
+            .get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(edits, limit, |e| {
+            PaginationCursor::new(Some(e.edit.published), e.edit.id.0)
+        }))
+    }
 
-        Ok(query.order(edit::published).get_results(conn.deref_mut())?)
+    /// Edits matching all filters in `query`, newest first. Edits of removed articles are
+    /// never included.
+    pub fn list_recent(
+        query: RecentChangesQuery,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut sql = edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(not(article::removed))
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            sql = sql.filter(
+                edit::published.nullable().lt(published).or(edit::published
+                    .nullable()
+                    .eq(published)
+                    .and(edit::id.lt(id))),
+            );
+        }
+        sql = if query.unreviewed_only {
+            sql.filter(edit::awaiting_review)
+        } else {
+            sql.filter(not(edit::pending))
+        };
+        if let Some(instance_id) = query.instance_id {
+            sql = sql.filter(article::instance_id.eq(instance_id));
+        }
+        if let Some(article_id) = query.article_id {
+            sql = sql.filter(edit::article_id.eq(article_id));
+        }
+        if let Some(person_id) = query.person_id {
+            sql = sql.filter(edit::creator_id.eq(person_id));
+        }
+        if query.local_only {
+            sql = sql.filter(article::local);
+        }
+        if let Some(since) = query.since {
+            sql = sql.filter(edit::published.ge(since));
+        }
+        if let Some(until) = query.until {
+            sql = sql.filter(edit::published.lt(until));
+        }
+        if query.hide_bots {
+            sql = sql.filter(not(person::bot)).filter(not(edit::bot));
+        }
+        let edits = sql
+            .select(EditView::as_select())
+            .get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(edits, Some(limit), |e| {
+            PaginationCursor::new(Some(e.edit.published), e.edit.id.0)
+        }))
     }
 }
 
+/// Filters for [Edit::list_recent]
+#[derive(Debug, Default)]
+pub struct RecentChangesQuery {
+    pub instance_id: Option<InstanceId>,
+    pub article_id: Option<ArticleId>,
+    pub person_id: Option<PersonId>,
+    /// Only edits of local articles
+    pub local_only: bool,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub hide_bots: bool,
+    /// Only edits which are waiting for approval by a reviewer, instead of published edits
+    pub unreviewed_only: bool,
+}
+
 pub enum ViewEditParams {
     PersonId(PersonId),
     ArticleId(ArticleId),
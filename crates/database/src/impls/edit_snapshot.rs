@@ -0,0 +1,136 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        article::Edit,
+        newtypes::{ArticleId, EditId},
+    },
+    error::BackendResult,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    Queryable,
+    RunQueryDsl,
+    Selectable,
+    delete,
+    insert_into,
+};
+use diffy::{Patch, apply};
+use ibis_database_schema::{edit, edit_snapshot};
+use std::ops::DerefMut;
+
+/// Store the full article text after every this many edits.
+pub const SNAPSHOT_INTERVAL: usize = 100;
+
+/// Full article text after a given edit, so that old versions can be generated without
+/// replaying the whole edit history. Snapshots reference a specific edit row (not its hash),
+/// so forked articles which share edit hashes each have their own snapshots.
+#[derive(Insertable, Queryable, Selectable)]
+#[diesel(table_name = edit_snapshot, check_for_backend(diesel::pg::Pg))]
+pub struct EditSnapshot {
+    pub edit_id: EditId,
+    pub text: String,
+}
+
+impl EditSnapshot {
+    pub fn create(edit_id: EditId, text: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(edit_snapshot::table)
+            .values((
+                edit_snapshot::edit_id.eq(edit_id),
+                edit_snapshot::text.eq(text),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn read(edit_id: EditId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit_snapshot::table
+            .find(edit_id)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Returns those of the given edits which have a snapshot stored.
+    pub fn list_existing(edit_ids: &[EditId], context: &IbisContext) -> BackendResult<Vec<EditId>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit_snapshot::table
+            .filter(edit_snapshot::edit_id.eq_any(edit_ids))
+            .select(edit_snapshot::edit_id)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Called after an edit published at the given time was inserted or changed. Deletes
+    /// outdated snapshots, and stores new ones when a checkpoint was reached.
+    pub fn refresh(
+        article_id: ArticleId,
+        published: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let invalidated = Self::invalidate(article_id, published, context)?;
+        let mut conn = context.db_pool.get()?;
+        let count: i64 = edit::table
+            .filter(edit::article_id.eq(article_id))
+            .filter(edit::pending.eq(false))
+            .count()
+            .get_result(conn.deref_mut())?;
+        let checkpoint_reached =
+            usize::try_from(count).is_ok_and(|count| count > 0 && count % SNAPSHOT_INTERVAL == 0);
+        if invalidated > 0 || checkpoint_reached {
+            Self::create_missing(article_id, context)?;
+        }
+        Ok(())
+    }
+
+    /// Versions are generated by applying edits in order of publish time, so inserting an edit
+    /// with an earlier timestamp changes the text of all later versions. Delete the snapshots
+    /// which may be outdated, and return how many were deleted.
+    pub fn invalidate(
+        article_id: ArticleId,
+        published: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<usize> {
+        let mut conn = context.db_pool.get()?;
+        let outdated = edit::table
+            .filter(edit::article_id.eq(article_id))
+            .filter(edit::published.ge(published))
+            .select(edit::id);
+        Ok(
+            delete(edit_snapshot::table.filter(edit_snapshot::edit_id.eq_any(outdated)))
+                .execute(conn.deref_mut())?,
+        )
+    }
+
+    /// Store snapshots for all checkpoints of the article which don't have one yet.
+    pub fn create_missing(article_id: ArticleId, context: &IbisContext) -> BackendResult<()> {
+        let edits = Edit::list_for_article(article_id, context)?;
+        let checkpoints: Vec<_> = edits
+            .iter()
+            .skip(SNAPSHOT_INTERVAL - 1)
+            .step_by(SNAPSHOT_INTERVAL)
+            .map(|e| e.id)
+            .collect();
+        let existing = Self::list_existing(&checkpoints, context)?;
+        // text after the previous checkpoint, if it was generated here
+        let mut text: Option<String> = Some(String::new());
+        for (i, id) in checkpoints.iter().enumerate() {
+            if existing.contains(id) {
+                text = None;
+                continue;
+            }
+            let mut generated = match text {
+                Some(text) => text,
+                None => Self::read(checkpoints[i - 1], context)?.text,
+            };
+            for e in &edits[i * SNAPSHOT_INTERVAL..(i + 1) * SNAPSHOT_INTERVAL] {
+                generated = apply(&generated, &Patch::from_str(&e.diff)?)?;
+            }
+            Self::create(*id, &generated, context)?;
+            text = Some(generated);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        federation_list::{FederationListEntry, FederationMode},
+        pagination::{PaginatedResponse, PaginationCursor},
+    },
+    config::IbisConfigFederation,
+    error::BackendResult,
+};
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use diesel::{
+    AsChangeset,
+    BoolExpressionMethods,
+    Connection,
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::{not, now},
+    insert_into,
+    result::Error,
+    update,
+};
+use ibis_database_schema::{federation_list, instance};
+use moka::sync::Cache;
+use std::{
+    ops::DerefMut,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = federation_list, check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+pub struct FederationListForm {
+    pub domain: String,
+    pub mode: FederationMode,
+    pub reason: String,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl FederationListEntry {
+    /// Add a domain to the list, or replace the existing entry for the domain.
+    pub fn upsert(form: &FederationListForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let entry = insert_into(federation_list::table)
+            .values(form)
+            .on_conflict(federation_list::domain)
+            .do_update()
+            .set((form, federation_list::published.eq(now)))
+            .get_result(conn.deref_mut())?;
+        CACHE.invalidate(context.conf.federation_domain());
+        Ok(entry)
+    }
+
+    pub fn delete(domain: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let entry = delete(federation_list::table.filter(federation_list::domain.eq(domain)))
+            .get_result(conn.deref_mut())?;
+        CACHE.invalidate(context.conf.federation_domain());
+        Ok(entry)
+    }
+
+    /// Entries which have not expired yet, sorted by domain.
+    pub fn list(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = federation_list::table
+            .filter(
+                federation_list::expires
+                    .is_null()
+                    .or(federation_list::expires.gt(now)),
+            )
+            .order(federation_list::domain.asc())
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (_, domain) = cursor.parse_named()?;
+            query = query.filter(federation_list::domain.gt(domain));
+        }
+        let entries = query.get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(entries, Some(limit), |e: &Self| {
+            PaginationCursor::new_named(e.id.0.into(), &e.domain)
+        }))
+    }
+
+    /// All entries which have not expired yet, sorted by domain.
+    fn list_active(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(federation_list::table
+            .filter(
+                federation_list::expires
+                    .is_null()
+                    .or(federation_list::expires.gt(now)),
+            )
+            .order(federation_list::domain.asc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Returns an error if federation with the domain is not allowed. This is the case if the
+    /// domain is blocked, or if other domains are allowed but this one isn't.
+    ///
+    /// This is called for every federated url, so the list is kept in memory.
+    pub fn check_domain(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let entries = CACHE
+            .try_get_with_by_ref(context.conf.federation_domain(), || {
+                Self::list_active(context).map(Arc::new)
+            })
+            .map_err(|e| anyhow!(e))?;
+        let active = entries
+            .iter()
+            .filter(|e| e.expires.is_none_or(|e| e > Utc::now()));
+        let mut allowlist_exists = false;
+        for entry in active {
+            match (entry.mode, entry.domain == domain) {
+                (FederationMode::Block, true) => {
+                    return Err(anyhow!("Domain {domain} is blocked").into());
+                }
+                (FederationMode::Allow, true) => return Ok(()),
+                (FederationMode::Allow, false) => allowlist_exists = true,
+                (FederationMode::Block, false) => {}
+            }
+        }
+        if allowlist_exists {
+            return Err(anyhow!("Domain {domain} is not allowed").into());
+        }
+        Ok(())
+    }
+
+    /// Add domains from the deprecated allowlist and blocklist config options. This is only done
+    /// once, so that entries which were removed by an admin are not added again. Existing
+    /// entries are not changed.
+    pub fn import_from_config(
+        config: &IbisConfigFederation,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let lists = [
+            (&config.allowlist, FederationMode::Allow),
+            (&config.blocklist, FederationMode::Block),
+        ];
+        let forms: Vec<_> = lists
+            .into_iter()
+            .flat_map(|(list, mode)| {
+                list.iter()
+                    .flat_map(|l| l.split(','))
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                    .map(move |domain| FederationListForm {
+                        domain: domain.to_lowercase(),
+                        mode,
+                        reason: "Imported from config file".to_string(),
+                        expires: None,
+                    })
+            })
+            .collect();
+        if forms.is_empty() {
+            return Ok(());
+        }
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, Error, _>(|conn| {
+            let updated = update(
+                instance::table
+                    .filter(instance::local)
+                    .filter(not(instance::federation_list_imported)),
+            )
+            .set(instance::federation_list_imported.eq(true))
+            .execute(conn)?;
+            if updated > 0 {
+                insert_into(federation_list::table)
+                    .values(forms)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+        CACHE.invalidate(context.conf.federation_domain());
+        Ok(())
+    }
+}
+
+/// Active federation list entries, keyed by domain of the local instance.
+static CACHE: LazyLock<Cache<String, Arc<Vec<FederationListEntry>>>> = LazyLock::new(|| {
+    Cache::builder()
+        .max_capacity(10)
+        .time_to_live(Duration::from_secs(3600))
+        .build()
+});
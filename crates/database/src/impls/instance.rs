@@ -1,19 +1,21 @@
 use crate::{
     DbUrl,
     common::{
+        article::Article,
         instance::{Instance, InstanceView},
         newtypes::{CommentId, InstanceId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
         user::Person,
     },
     error::BackendResult,
-    impls::IbisContext,
+    impls::{IbisContext, split_part},
 };
 use chrono::{DateTime, Utc};
 use diesel::{
     dsl::{count, max, not},
     *,
 };
-use ibis_database_schema::{article, comment, edit, instance, instance_follow};
+use ibis_database_schema::{article, comment, edit, instance, instance_follow, person};
 use std::{fmt::Debug, ops::DerefMut};
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -37,6 +39,9 @@ pub struct DbInstanceForm {
 pub struct DbInstanceUpdateForm {
     pub topic: Option<String>,
     pub name: Option<String>,
+    pub review_all_edits: Option<bool>,
+    pub review_new_user_edits: Option<i32>,
+    pub require_totp_for_moderators: Option<bool>,
 }
 
 #[derive(Debug)]
@@ -48,12 +53,21 @@ pub enum InstanceViewQuery<'a> {
 impl Instance {
     pub fn create(form: &DbInstanceForm, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(instance::table)
+        let instance: Self = insert_into(instance::table)
             .values(form)
             .on_conflict(instance::ap_id)
             .do_update()
             .set(form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        // link users which were fetched before their instance
+        update(
+            person::table
+                .filter(person::instance_id.is_null())
+                .filter(split_part(person::ap_id, "/", 3).eq(&instance.domain)),
+        )
+        .set(person::instance_id.eq(instance.id))
+        .execute(conn.deref_mut())?;
+        Ok(instance)
     }
 
     pub fn read(id: InstanceId, context: &IbisContext) -> BackendResult<Self> {
@@ -116,7 +130,7 @@ impl Instance {
             .group_by(article::id)
             .order_by((article::local.desc(), max(edit::published).desc()))
             .limit(5)
-            .select(article::all_columns)
+            .select(Article::as_select())
             .get_results(conn.deref_mut())?;
 
         Ok(InstanceView {
@@ -189,22 +203,67 @@ impl Instance {
             .get_results(conn.deref_mut())?)
     }
 
-    pub fn list_with_articles(context: &IbisContext) -> BackendResult<Vec<InstanceView>> {
+    /// Remote instances which were refreshed most recently, and are therefore likely to still be
+    /// online.
+    pub fn list_recently_refreshed(
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Instance>> {
         let mut conn = context.db_pool.get()?;
-        // select all instances, with most recently edited first (pending edits are ignored)
-        let instances = instance::table
+        Ok(instance::table
+            .filter(instance::local.eq(false))
+            .order_by(instance::last_refreshed_at.desc())
+            .limit(limit)
+            .get_results(conn.deref_mut())?)
+    }
+
+    pub fn list_with_articles(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<InstanceView>> {
+        let mut conn = context.db_pool.get()?;
+        // select instances with most recently edited first (pending edits are ignored), and
+        // instances without any edits at the end
+        let mut query = instance::table
             // need to join manually, otherwise the order is wrong
             .left_join(article::table.on(article::instance_id.eq(instance::id)))
             .left_join(edit::table.on(article::id.eq(edit::article_id).and(not(edit::pending))))
             .group_by(instance::id)
-            .order_by(max(edit::published).desc())
-            .select(instance::all_columns)
-            .get_results::<Instance>(conn.deref_mut())?;
+            .order_by((
+                max(edit::published).desc().nulls_last(),
+                instance::id.desc(),
+            ))
+            .select((instance::all_columns, max(edit::published.nullable())))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            query = match cursor.parse()? {
+                (Some(last_edit), id) => query.having(
+                    max(edit::published.nullable())
+                        .lt(last_edit)
+                        .or(max(edit::published.nullable())
+                            .eq(last_edit)
+                            .and(instance::id.lt(id)))
+                        .or(max(edit::published.nullable()).is_null()),
+                ),
+                (None, id) => query.having(
+                    max(edit::published.nullable())
+                        .is_null()
+                        .and(instance::id.lt(id)),
+                ),
+            };
+        }
+        let instances: Vec<(Instance, Option<DateTime<Utc>>)> =
+            query.get_results(conn.deref_mut())?;
+        let instances = PaginatedResponse::new(instances, Some(limit), |(i, last_edit)| {
+            PaginationCursor::new(*last_edit, i.id.0)
+        });
         let mut res = vec![];
         // Get the last edited articles for each instance.
         // TODO: This is very inefficient, should use single query with lateral join
         // https://github.com/diesel-rs/diesel/discussions/4450
-        for instance in instances {
+        for (instance, _) in instances.items {
             let articles = article::table
                 .filter(article::instance_id.eq(instance.id))
                 .inner_join(edit::table)
@@ -213,7 +272,7 @@ impl Instance {
                 .group_by(article::id)
                 .order_by((article::local.desc(), max(edit::published).desc()))
                 .limit(5)
-                .select(article::all_columns)
+                .select(Article::as_select())
                 .get_results(conn.deref_mut())?;
             res.push(InstanceView {
                 instance,
@@ -222,7 +281,10 @@ impl Instance {
             });
         }
 
-        Ok(res)
+        Ok(PaginatedResponse {
+            items: res,
+            next_page: instances.next_page,
+        })
     }
 
     /// Read the instance where an article is hosted, based on a comment id.
@@ -239,4 +301,60 @@ impl Instance {
             .select(instance::all_columns)
             .get_result(conn.deref_mut())?)
     }
+
+    /// Mark articles of the instance as removed, and comments from its users as deleted.
+    pub fn hide_content(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let instance_ids = instance::table
+            .filter(instance::domain.eq(domain))
+            .select(instance::id);
+        conn.transaction::<_, result::Error, _>(|conn| {
+            update(article::table.filter(article::instance_id.eq_any(instance_ids)))
+                .set(article::removed.eq(true))
+                .execute(conn)?;
+            update(comment::table.filter(comment::creator_id.eq_any(persons_of_domain(domain))))
+                .set(comment::deleted.eq(true))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Delete the instance with its articles, and the comments and users from the instance.
+    /// Users who edited articles from other instances are kept so that the edit history remains
+    /// intact.
+    pub fn purge(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, result::Error, _>(|conn| {
+            delete(comment::table.filter(comment::creator_id.eq_any(persons_of_domain(domain))))
+                .execute(conn)?;
+            // read users first, because deleting the instance unsets their instance_id
+            let persons: Vec<PersonId> = persons_of_domain(domain).get_results(conn)?;
+            delete(instance::table.filter(instance::domain.eq(domain)))
+                .filter(not(instance::local))
+                .execute(conn)?;
+            delete(
+                person::table
+                    .filter(person::id.eq_any(persons))
+                    .filter(not(dsl::exists(
+                        edit::table.filter(edit::creator_id.eq(person::id)),
+                    ))),
+            )
+            .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Ids of remote users from the instance with the given domain.
+fn persons_of_domain(domain: &str) -> person::BoxedQuery<'static, pg::Pg, sql_types::Integer> {
+    let instance_ids = instance::table
+        .filter(instance::domain.eq(domain.to_string()))
+        .select(instance::id.nullable());
+    person::table
+        .filter(person::instance_id.eq_any(instance_ids))
+        .filter(not(person::local))
+        .select(person::id)
+        .into_boxed()
 }
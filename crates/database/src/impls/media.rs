@@ -0,0 +1,107 @@
+use super::IbisContext;
+use crate::{
+    DbUrl,
+    common::{
+        media::Media,
+        newtypes::{InstanceId, MediaId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
+    },
+    error::BackendResult,
+};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    Insertable,
+    NullableExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    insert_into,
+};
+use ibis_database_schema::media;
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = media, check_for_backend(diesel::pg::Pg))]
+pub struct MediaInsertForm {
+    pub filename: String,
+    pub ap_id: DbUrl,
+    pub instance_id: InstanceId,
+    pub creator_id: Option<PersonId>,
+    pub mime_type: String,
+    pub local: bool,
+}
+
+impl Media {
+    /// Store new media, or return the existing entry if the same file was stored before.
+    pub fn create(form: &MediaInsertForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(media::table)
+            .values(form)
+            .on_conflict(media::ap_id)
+            .do_update()
+            .set(media::mime_type.eq(&form.mime_type))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(id: MediaId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(media::table.find(id).get_result(conn.deref_mut())?)
+    }
+
+    /// Read media by filename, preferring the local copy if there is one.
+    pub fn read_from_filename(filename: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(media::table
+            .filter(media::filename.eq(filename))
+            .order_by(media::local.desc())
+            .first(conn.deref_mut())?)
+    }
+
+    /// Read all known media with one of the given filenames.
+    pub fn read_from_filenames(
+        filenames: &[String],
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(media::table
+            .filter(media::filename.eq_any(filenames))
+            .order_by(media::local.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// List media which was uploaded to this instance, newest first.
+    pub fn list_local(
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = media::table
+            .filter(media::local)
+            .order_by((media::published.desc(), media::id.desc()))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                media::published
+                    .nullable()
+                    .lt(published)
+                    .or(media::published
+                        .nullable()
+                        .eq(published)
+                        .and(media::id.lt(id))),
+            );
+        }
+        let media = query.get_results::<Self>(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(media, Some(limit), |m| {
+            PaginationCursor::new(Some(m.published), m.id.0)
+        }))
+    }
+
+    pub fn delete(id: MediaId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delete(media::table.find(id)).get_result(conn.deref_mut())?)
+    }
+}
@@ -13,14 +13,25 @@ use ibis_database_schema::jwt_secret;
 use reqwest::Client;
 use std::{env::var, ops::DerefMut};
 
+pub mod api_token;
 pub mod article;
+pub mod article_link;
+pub mod ban;
 pub mod comment;
 pub mod conflict;
+pub mod delivery;
 pub mod edit;
+pub mod edit_snapshot;
+pub mod federation_list;
 pub mod instance;
 pub mod instance_stats;
+pub mod media;
+pub mod modlog;
 pub mod notifications;
+pub mod report;
 pub mod sent_activity;
+pub mod session;
+pub mod totp;
 pub mod user;
 
 pub type DbPool = Pool<ConnectionManager<PgConnection>>;
@@ -73,6 +84,8 @@ pub fn read_jwt_secret(context: &IbisContext) -> BackendResult<String> {
 
 define_sql_function!(fn lower(x: sql_types::Text) -> sql_types::Text);
 
+define_sql_function!(fn split_part(x: sql_types::Text, delimiter: sql_types::Text, n: sql_types::Integer) -> sql_types::Text);
+
 define_sql_function!(fn coalesce<T: sql_types::SqlType + sql_types::SingleValue>(x: sql_types::Nullable<T>, y: T) -> T);
 
 /// Need to handle conflicts manually to avoid duplicate notifications
@@ -0,0 +1,145 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        modlog::{ModAction, Modlog, ModlogArticle, ModlogView},
+        newtypes::{ArticleId, PersonId},
+        pagination::{PaginatedResponse, PaginationCursor},
+        user::Person,
+    },
+    error::BackendResult,
+};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    Insertable,
+    JoinOnDsl,
+    NullableExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+    insert_into,
+};
+use ibis_database_schema::{article, modlog, person};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = modlog, check_for_backend(diesel::pg::Pg))]
+struct DbModlogForm {
+    moderator_id: PersonId,
+    action: ModAction,
+    reason: Option<String>,
+    article_id: Option<ArticleId>,
+    target_person_id: Option<PersonId>,
+    target_domain: Option<String>,
+}
+
+impl Modlog {
+    /// Write an entry for an action taken by `moderator_id`, which may affect an article, a user
+    /// or both.
+    pub fn log(
+        action: ModAction,
+        moderator_id: PersonId,
+        article_id: Option<ArticleId>,
+        target_person_id: Option<PersonId>,
+        reason: Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let form = DbModlogForm {
+            moderator_id,
+            action,
+            reason,
+            article_id,
+            target_person_id,
+            target_domain: None,
+        };
+        Self::create(form, context)
+    }
+
+    /// Write an entry for a change to the federation list entry of `domain`.
+    pub fn log_domain(
+        action: ModAction,
+        moderator_id: PersonId,
+        domain: String,
+        reason: Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let form = DbModlogForm {
+            moderator_id,
+            action,
+            reason,
+            article_id: None,
+            target_person_id: None,
+            target_domain: Some(domain),
+        };
+        Self::create(form, context)
+    }
+
+    fn create(form: DbModlogForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(modlog::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Log entries matching all of the given filters, newest first.
+    pub fn list(
+        moderator_id: Option<PersonId>,
+        article_id: Option<ArticleId>,
+        action: Option<ModAction>,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<ModlogView>> {
+        let mut conn = context.db_pool.get()?;
+        let (moderator, target) = diesel::alias!(person as moderator, person as target_person);
+        let mut query = modlog::table
+            .inner_join(moderator.on(moderator.field(person::id).eq(modlog::moderator_id)))
+            .left_join(article::table)
+            .left_join(
+                target.on(target
+                    .field(person::id)
+                    .nullable()
+                    .eq(modlog::target_person_id)),
+            )
+            .select((
+                modlog::all_columns,
+                moderator.fields(person::all_columns),
+                (article::id, article::title, article::ap_id, article::local).nullable(),
+                target.fields(person::all_columns).nullable(),
+            ))
+            .order_by((modlog::published.desc(), modlog::id.desc()))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                modlog::published
+                    .nullable()
+                    .lt(published)
+                    .or(modlog::published
+                        .nullable()
+                        .eq(published)
+                        .and(modlog::id.lt(id))),
+            );
+        }
+        if let Some(moderator_id) = moderator_id {
+            query = query.filter(modlog::moderator_id.eq(moderator_id));
+        }
+        if let Some(article_id) = article_id {
+            query = query.filter(modlog::article_id.eq(article_id));
+        }
+        if let Some(action) = action {
+            query = query.filter(modlog::action.eq(action));
+        }
+        let res: Vec<(Modlog, Person, Option<ModlogArticle>, Option<Person>)> =
+            query.get_results(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(res, Some(limit), |(modlog, ..)| {
+            PaginationCursor::new(Some(modlog.published), modlog.id.0)
+        })
+        .map(|(modlog, moderator, article, target_person)| ModlogView {
+            modlog,
+            moderator,
+            article,
+            target_person,
+        }))
+    }
+}
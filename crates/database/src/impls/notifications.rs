@@ -10,9 +10,12 @@ use crate::{
             LocalUserId,
             NotificationId,
             PersonId,
+            ReportId,
         },
         notifications::{ApiNotification, ApiNotificationData},
-        user::{LocalUser, LocalUserView, Person},
+        pagination::{PaginatedResponse, PaginationCursor},
+        report::Report,
+        user::{LocalUser, LocalUserView, Person, Role},
     },
     email::notification::send_notification_email,
     error::BackendResult,
@@ -20,6 +23,7 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use diesel::{
+    BoolExpressionMethods,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
@@ -28,6 +32,7 @@ use diesel::{
     Queryable,
     RunQueryDsl,
     Selectable,
+    SelectableHelper,
     dsl::*,
 };
 use ibis_database_schema::{
@@ -40,6 +45,7 @@ use ibis_database_schema::{
     local_user,
     notification,
     person,
+    report,
 };
 use std::ops::DerefMut;
 
@@ -55,6 +61,7 @@ pub struct Notification {
     edit_id: Option<EditId>,
     pub published: DateTime<Utc>,
     conflict_id: Option<ConflictId>,
+    report_id: Option<ReportId>,
 }
 
 #[derive(Debug, Insertable)]
@@ -66,18 +73,28 @@ pub(crate) struct NotificationInsertForm {
     pub comment_id: Option<CommentId>,
     pub edit_id: Option<EditId>,
     pub conflict_id: Option<ConflictId>,
+    pub report_id: Option<ReportId>,
 }
 
-#[derive(Queryable, Debug)]
+#[derive(Queryable, Selectable, Debug)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub(crate) struct NotificationData {
+    #[diesel(embed)]
     pub(crate) notification: Notification,
+    #[diesel(embed)]
     pub(crate) article: Article,
+    #[diesel(embed)]
     pub(crate) creator: Person,
+    #[diesel(embed)]
     pub(crate) local_user: LocalUser,
+    #[diesel(embed)]
     pub(crate) comment: Option<Comment>,
+    #[diesel(embed)]
     pub(crate) edit: Option<Edit>,
+    #[diesel(embed)]
     pub(crate) conflict: Option<Conflict>,
+    #[diesel(embed)]
+    pub(crate) report: Option<Report>,
 }
 
 impl Notification {
@@ -90,6 +107,7 @@ impl Notification {
             .left_join(comment::table)
             .left_join(edit::table)
             .left_join(conflict::table)
+            .left_join(report::table)
     }
 
     pub(crate) fn read_data(
@@ -99,24 +117,47 @@ impl Notification {
         let mut conn = context.db_pool.get()?;
         Ok(Notification::joins()
             .filter(notification::id.eq(id))
+            .select(NotificationData::as_select())
             .get_result(&mut conn)?)
     }
+    /// Notifications of the user, newest first.
     pub fn list(
         user: &LocalUserView,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
         context: &IbisContext,
-    ) -> BackendResult<Vec<ApiNotification>> {
+    ) -> BackendResult<PaginatedResponse<ApiNotification>> {
         let mut conn = context.db_pool.get()?;
 
-        let article_notifications = Self::joins()
+        let mut query = Self::joins()
             .filter(notification::local_user_id.eq(user.local_user.id))
-            .order_by(notification::published.desc())
-            .get_results::<NotificationData>(&mut conn)?;
+            .order_by((notification::published.desc(), notification::id.desc()))
+            .select(NotificationData::as_select())
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                notification::published
+                    .nullable()
+                    .lt(published)
+                    .or(notification::published
+                        .nullable()
+                        .eq(published)
+                        .and(notification::id.lt(id))),
+            );
+        }
+        let article_notifications = query.get_results::<NotificationData>(&mut conn)?;
 
-        Ok(article_notifications
-            .into_iter()
+        Ok(
+            PaginatedResponse::new(article_notifications, Some(limit), |n| {
+                PaginationCursor::new(Some(n.notification.published), n.notification.id.0)
+            })
             .map(|n| {
                 use ApiNotificationData::*;
-                let (published, data) = if let Some(c) = n.comment {
+                let (published, data) = if let Some(r) = n.report {
+                    (r.published, Report(r))
+                } else if let Some(c) = n.comment {
                     (c.published, Comment(c))
                 } else if let Some(e) = n.edit {
                     (e.published, Edit(e))
@@ -138,8 +179,8 @@ impl Notification {
                     published,
                     data,
                 }
-            })
-            .collect())
+            }),
+        )
     }
 
     pub fn count(user: &LocalUserView, context: &IbisContext) -> BackendResult<i64> {
@@ -206,6 +247,7 @@ impl Notification {
                 comment_id: None,
                 edit_id: None,
                 conflict_id: None,
+                report_id: None,
             })
             .collect();
 
@@ -248,6 +290,7 @@ impl Notification {
                         comment_id: Some(comment.id),
                         edit_id: None,
                         conflict_id: None,
+                        report_id: None,
                     };
                     insert_into(notification::table)
                         .values(&form)
@@ -268,6 +311,7 @@ impl Notification {
                 comment_id: Some(comment.id),
                 edit_id: None,
                 conflict_id: None,
+                report_id: None,
             },
             context,
         )
@@ -287,6 +331,7 @@ impl Notification {
                 comment_id: None,
                 edit_id: Some(edit.id),
                 conflict_id: None,
+                report_id: None,
             },
             context,
         )
@@ -294,6 +339,34 @@ impl Notification {
         Ok(())
     }
 
+    /// Notify all local moderators about a new report.
+    pub async fn notify_report(report: &Report, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let moderators = local_user::table
+            .filter(local_user::role.eq_any([Role::Moderator, Role::Admin]))
+            .filter(local_user::person_id.ne(report.creator_id))
+            .select(local_user::id)
+            .get_results::<LocalUserId>(&mut conn)?;
+        let notifs: Vec<_> = moderators
+            .into_iter()
+            .map(|local_user_id| NotificationInsertForm {
+                local_user_id,
+                article_id: report.article_id,
+                creator_id: report.creator_id,
+                comment_id: None,
+                edit_id: None,
+                conflict_id: None,
+                report_id: Some(report.id),
+            })
+            .collect();
+        let notifs = insert_into(notification::table)
+            .values(&notifs)
+            .on_conflict_do_nothing()
+            .get_results(&mut conn)?;
+        send_notification_email(notifs, context).await?;
+        Ok(())
+    }
+
     async fn notify<F>(
         article_id: ArticleId,
         creator_id: PersonId,
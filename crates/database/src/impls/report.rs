@@ -0,0 +1,97 @@
+use super::IbisContext;
+use crate::{
+    DbUrl,
+    common::{
+        newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+        pagination::{PaginatedResponse, PaginationCursor},
+        report::{Report, ReportStatus, ReportView},
+    },
+    error::BackendResult,
+};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    Insertable,
+    NullableExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+    SelectableHelper,
+    dsl::now,
+    insert_into,
+    update,
+};
+use ibis_database_schema::{article, comment, edit, person, report};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = report, check_for_backend(diesel::pg::Pg))]
+pub struct DbReportForm {
+    pub ap_id: DbUrl,
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+}
+
+impl Report {
+    pub fn create(form: &DbReportForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(report::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(id: ReportId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(report::table.find(id).get_result(conn.deref_mut())?)
+    }
+
+    /// Reports with the given status, oldest first.
+    pub fn list(
+        status: ReportStatus,
+        cursor: Option<PaginationCursor>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<PaginatedResponse<ReportView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = report::table
+            .inner_join(person::table)
+            .inner_join(article::table)
+            .left_join(edit::table)
+            .left_join(comment::table)
+            .filter(report::status.eq(status))
+            .select(ReportView::as_select())
+            .order_by((report::published.asc(), report::id.asc()))
+            .limit(limit + 1)
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let (published, id) = cursor.parse()?;
+            query = query.filter(
+                report::published
+                    .nullable()
+                    .gt(published)
+                    .or(report::published
+                        .nullable()
+                        .eq(published)
+                        .and(report::id.gt(id))),
+            );
+        }
+        let reports = query.get_results::<ReportView>(conn.deref_mut())?;
+        Ok(PaginatedResponse::new(reports, Some(limit), |r| {
+            PaginationCursor::new(Some(r.report.published), r.report.id.0)
+        }))
+    }
+
+    pub fn update_status(
+        id: ReportId,
+        status: ReportStatus,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(update(report::table.find(id))
+            .set((report::status.eq(status), report::updated.eq(now)))
+            .returning(Report::as_returning())
+            .get_result(conn.deref_mut())?)
+    }
+}
@@ -0,0 +1,101 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        newtypes::{LocalUserId, LoginSessionId},
+        session::LoginSession,
+    },
+    error::BackendResult,
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::now,
+    insert_into,
+    update,
+};
+use ibis_database_schema::login_session;
+use std::ops::DerefMut;
+use uuid::Uuid;
+
+/// Minimum time between updates of last use for sessions and API tokens, so that not every
+/// request needs a database write.
+pub(crate) const LAST_USED_INTERVAL: TimeDelta = TimeDelta::minutes(1);
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = login_session, check_for_backend(diesel::pg::Pg))]
+pub struct LoginSessionForm {
+    pub local_user_id: LocalUserId,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+impl LoginSession {
+    /// Create a new session with random token id.
+    pub fn create(form: &LoginSessionForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(login_session::table)
+            .values((form, login_session::token_id.eq(Uuid::new_v4())))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Read the session for a login token and store the current time as last seen, unless it
+    /// was updated less than [`LAST_USED_INTERVAL`] ago. Fails if the session was revoked.
+    pub fn validate(token_id: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let token_id = Uuid::parse_str(token_id)?;
+        let mut session: Self = login_session::table
+            .filter(login_session::token_id.eq(token_id))
+            .get_result(conn.deref_mut())?;
+        if session.last_seen < Utc::now() - LAST_USED_INTERVAL {
+            session = update(login_session::table.find(session.id))
+                .set(login_session::last_seen.eq(now))
+                .get_result(conn.deref_mut())?;
+        }
+        Ok(session)
+    }
+
+    /// Sessions of the user, most recently used first.
+    pub fn list(local_user_id: LocalUserId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(login_session::table
+            .filter(login_session::local_user_id.eq(local_user_id))
+            .order(login_session::last_seen.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Revoke a session, which only works for sessions of the given user.
+    pub fn delete(
+        id: LoginSessionId,
+        local_user_id: LocalUserId,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delete(
+            login_session::table
+                .find(id)
+                .filter(login_session::local_user_id.eq(local_user_id)),
+        )
+        .get_result(conn.deref_mut())?)
+    }
+
+    /// Revoke all sessions of the user, except for `keep` if given.
+    pub fn delete_all(
+        local_user_id: LocalUserId,
+        keep: Option<LoginSessionId>,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let keep = keep.unwrap_or(LoginSessionId(-1));
+        delete(
+            login_session::table
+                .filter(login_session::local_user_id.eq(local_user_id))
+                .filter(login_session::id.ne(keep)),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use super::IbisContext;
+use crate::{
+    common::{
+        newtypes::LocalUserId,
+        totp::TotpSetupResponse,
+        user::{LocalUser, LocalUserView},
+    },
+    error::BackendResult,
+};
+use anyhow::anyhow;
+use chrono::{TimeDelta, Utc};
+use diesel::{
+    BoolExpressionMethods,
+    ExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::now,
+    insert_into,
+    update,
+};
+use ibis_database_schema::{local_user, totp_recovery_code};
+use qrcode::{QrCode, render::svg};
+use sha2::{Digest, Sha256};
+use std::{
+    ops::DerefMut,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Duration of a time step in seconds, a new code is generated for each step
+const TOTP_STEP: u64 = 30;
+
+/// After this many failed attempts, codes are rejected until `TOTP_LOCKOUT` has passed since
+/// the last failed attempt.
+const TOTP_MAX_FAILED_ATTEMPTS: i32 = 5;
+const TOTP_LOCKOUT: TimeDelta = TimeDelta::minutes(15);
+
+impl LocalUser {
+    /// Generate a new two-factor secret for the user. It is only required for login after
+    /// calling [`LocalUser::totp_enable`] with a valid code.
+    pub fn totp_setup(
+        user: &LocalUserView,
+        context: &IbisContext,
+    ) -> BackendResult<TotpSetupResponse> {
+        if user.local_user.totp_enabled {
+            return Err(anyhow!("Two-factor authentication is already enabled").into());
+        }
+        let Secret::Encoded(secret) = Secret::generate_secret().to_encoded() else {
+            return Err(anyhow!("Failed to encode secret").into());
+        };
+        let totp = build_totp(&secret, &user.person.username, context)?;
+        let uri = totp.get_url();
+        let qr_code = QrCode::new(&uri)?
+            .render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .build();
+
+        let mut conn = context.db_pool.get()?;
+        update(local_user::table.find(user.local_user.id))
+            .set(local_user::totp_secret.eq(&secret))
+            .execute(conn.deref_mut())?;
+        Ok(TotpSetupResponse {
+            secret,
+            uri,
+            qr_code,
+        })
+    }
+
+    /// Enable two-factor authentication if the code matches the secret from setup. Returns
+    /// newly generated recovery codes.
+    pub fn totp_enable(
+        user: &LocalUserView,
+        code: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<String>> {
+        if user.local_user.totp_enabled {
+            return Err(anyhow!("Two-factor authentication is already enabled").into());
+        }
+        let Some(secret) = &user.local_user.totp_secret else {
+            return Err(anyhow!("Two-factor authentication was not set up").into());
+        };
+        let totp = build_totp(secret, &user.person.username, context)?;
+        let Some(step) = matching_step(&totp, code.trim())? else {
+            return Err(anyhow!("Invalid two-factor authentication code").into());
+        };
+
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                Uuid::new_v4()
+                    .simple()
+                    .to_string()
+                    .chars()
+                    .take(12)
+                    .collect()
+            })
+            .collect();
+        let forms: Vec<_> = recovery_codes
+            .iter()
+            .map(|c| {
+                (
+                    totp_recovery_code::local_user_id.eq(user.local_user.id),
+                    totp_recovery_code::code_hash.eq(hash_code(c)),
+                )
+            })
+            .collect();
+        let mut conn = context.db_pool.get()?;
+        delete(
+            totp_recovery_code::table
+                .filter(totp_recovery_code::local_user_id.eq(user.local_user.id)),
+        )
+        .execute(conn.deref_mut())?;
+        insert_into(totp_recovery_code::table)
+            .values(forms)
+            .execute(conn.deref_mut())?;
+        update(local_user::table.find(user.local_user.id))
+            .set((
+                local_user::totp_enabled.eq(true),
+                local_user::totp_last_step.eq(step),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(recovery_codes)
+    }
+
+    /// Remove the two-factor secret and all recovery codes of the user.
+    pub fn totp_disable(id: LocalUserId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(totp_recovery_code::table.filter(totp_recovery_code::local_user_id.eq(id)))
+            .execute(conn.deref_mut())?;
+        update(local_user::table.find(id))
+            .set((
+                local_user::totp_secret.eq(None::<String>),
+                local_user::totp_enabled.eq(false),
+                local_user::totp_last_step.eq(None::<i64>),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Check a one-time code from the authenticator app. Each code is only accepted once.
+    /// Alternatively a recovery code can be given, which is deleted so that it can't be used
+    /// again. After too many failed attempts all codes are rejected for some time.
+    pub fn totp_check(
+        user: &LocalUserView,
+        code: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let local_user = &user.local_user;
+        let recent_failure = local_user
+            .totp_last_failure
+            .is_some_and(|f| f > Utc::now() - TOTP_LOCKOUT);
+        if recent_failure && local_user.totp_failed_attempts >= TOTP_MAX_FAILED_ATTEMPTS {
+            return Err(anyhow!(
+                "Too many failed two-factor authentication attempts, try again later"
+            )
+            .into());
+        }
+
+        let code = code.trim();
+        let mut conn = context.db_pool.get()?;
+        let mut valid = false;
+        if let Some(secret) = &local_user.totp_secret {
+            let totp = build_totp(secret, &user.person.username, context)?;
+            if let Some(step) = matching_step(&totp, code)? {
+                // store the step only if it is newer than the last accepted one, so that the
+                // same code can't be used again
+                let updated = update(
+                    local_user::table.find(local_user.id).filter(
+                        local_user::totp_last_step
+                            .is_null()
+                            .or(local_user::totp_last_step.lt(step)),
+                    ),
+                )
+                .set(local_user::totp_last_step.eq(step))
+                .execute(conn.deref_mut())?;
+                valid = updated > 0;
+            }
+        }
+        if !valid {
+            let deleted = delete(
+                totp_recovery_code::table
+                    .filter(totp_recovery_code::local_user_id.eq(local_user.id))
+                    .filter(totp_recovery_code::code_hash.eq(hash_code(&code.to_lowercase()))),
+            )
+            .execute(conn.deref_mut())?;
+            valid = deleted > 0;
+        }
+
+        if !valid {
+            // start counting again if the last failure is older than the lockout
+            let attempts = if recent_failure {
+                local_user.totp_failed_attempts + 1
+            } else {
+                1
+            };
+            update(local_user::table.find(local_user.id))
+                .set((
+                    local_user::totp_failed_attempts.eq(attempts),
+                    local_user::totp_last_failure.eq(now),
+                ))
+                .execute(conn.deref_mut())?;
+            return Err(anyhow!("Invalid two-factor authentication code").into());
+        }
+        if local_user.totp_failed_attempts > 0 {
+            update(local_user::table.find(local_user.id))
+                .set(local_user::totp_failed_attempts.eq(0))
+                .execute(conn.deref_mut())?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the time step for which the code is valid. Codes from the previous and next step are
+/// also accepted to allow for clock drift.
+fn matching_step(totp: &TOTP, code: &str) -> BackendResult<Option<i64>> {
+    let current = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / TOTP_STEP;
+    let step =
+        (current.saturating_sub(1)..=current + 1).find(|step| totp.check(code, step * TOTP_STEP));
+    Ok(step.map(i64::try_from).transpose()?)
+}
+
+fn build_totp(secret: &str, username: &str, context: &IbisContext) -> BackendResult<TOTP> {
+    // the issuer must not contain a colon, so leave out the port
+    let issuer = context
+        .conf
+        .domain
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let secret = Secret::Encoded(secret.to_string()).to_bytes()?;
+    // skew is handled by `matching_step()`
+    Ok(TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP,
+        secret,
+        Some(issuer),
+        username.to_string(),
+    )?)
+}
+
+fn hash_code(code: &str) -> String {
+    hex::encode(Sha256::digest(code))
+}
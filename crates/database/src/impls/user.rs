@@ -1,10 +1,11 @@
 use crate::{
     DbUrl,
     common::{
+        ban::PersonBan,
         instance::InstanceFollow,
-        newtypes::{LocalUserId, PersonId},
-        user::{LocalUser, LocalUserView, Person},
-        utils::http_protocol_str,
+        newtypes::{InstanceId, LocalUserId, PersonId},
+        user::{LocalUser, LocalUserView, Person, PersonView, Role},
+        utils::{extract_domain, http_protocol_str},
     },
     error::BackendResult,
     impls::{IbisContext, coalesce, lower},
@@ -19,6 +20,8 @@ use diesel::{
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
+    OptionalExtension,
+    PgConnection,
     PgTextExpressionMethods,
     QueryDsl,
     Queryable,
@@ -43,7 +46,7 @@ use url::Url;
 pub struct LocalUserInsertForm {
     pub password_encrypted: Option<String>,
     pub person_id: PersonId,
-    pub admin: bool,
+    pub role: Role,
     pub email: Option<String>,
     pub email_verified: bool,
 }
@@ -66,6 +69,7 @@ pub struct PersonInsertForm {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    pub bot: bool,
 }
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -99,11 +103,12 @@ pub struct OAuthAccountInsertForm {
 impl Person {
     pub fn create(person_form: &PersonInsertForm, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
+        let instance_id = person::instance_id.eq(instance_of(&person_form.ap_id, &mut conn)?);
         Ok(insert_into(person::table)
-            .values(person_form)
+            .values((person_form, instance_id))
             .on_conflict(person::dsl::ap_id)
             .do_update()
-            .set(person_form)
+            .set((person_form, instance_id))
             .get_result(conn.deref_mut())?)
     }
 
@@ -116,7 +121,8 @@ impl Person {
         let mut conn = context.db_pool.get()?;
         Ok(person::table
             .inner_join(local_user::table)
-            .filter(local_user::admin)
+            .filter(local_user::role.eq(Role::Admin))
+            .order(local_user::id)
             .select(person::all_columns)
             .get_result(conn.deref_mut())?)
     }
@@ -149,6 +155,18 @@ impl Person {
         Ok(query.get_result(conn.deref_mut())?)
     }
 
+    /// Same as [Person::read_from_name], but also returns the role of local users.
+    pub fn read_view_from_name(
+        username: &str,
+        domain: &Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<PersonView> {
+        let person = Self::read_from_name(username, domain, context)?;
+        let role = LocalUser::read_role(person.id, context)?;
+        let ban = PersonBan::read_active(person.id, context)?;
+        Ok(PersonView { person, role, ban })
+    }
+
     pub fn update(
         form: &PersonUpdateForm,
         id: PersonId,
@@ -186,15 +204,30 @@ impl Person {
 
     /// Ghost user serves as placeholder for deleted accounts
     pub fn ghost(context: &IbisContext) -> BackendResult<Person> {
-        Self::get_or_create_person_with_name("ghost", context)
+        Self::get_or_create_person_with_name("ghost", false, context)
     }
 
     pub fn wikibot(context: &IbisContext) -> BackendResult<Person> {
-        Self::get_or_create_person_with_name(&context.conf.setup.wiki_bot_name, context)
+        Self::get_or_create_person_with_name(&context.conf.setup.wiki_bot_name, true, context)
+    }
+
+    /// Set the bot flag for the wiki bot account, which may have been created before the flag
+    /// existed.
+    pub fn mark_wikibot(context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        diesel::update(
+            person::table
+                .filter(person::local)
+                .filter(person::username.eq(&context.conf.setup.wiki_bot_name)),
+        )
+        .set(person::bot.eq(true))
+        .execute(conn.deref_mut())?;
+        Ok(())
     }
 
     fn get_or_create_person_with_name(
         username: &str,
+        bot: bool,
         context: &IbisContext,
     ) -> BackendResult<Person> {
         let read = Person::read_from_name(username, &None, context);
@@ -219,6 +252,7 @@ impl Person {
                 local: true,
                 display_name: None,
                 bio: None,
+                bot,
             };
             Person::create(&person_form, context)
         }
@@ -251,7 +285,7 @@ impl LocalUserView {
     pub fn create(
         username: String,
         password: Option<String>,
-        admin: bool,
+        role: Role,
         email: Option<String>,
         context: &IbisContext,
     ) -> BackendResult<Self> {
@@ -274,16 +308,18 @@ impl LocalUserView {
             local: true,
             display_name: None,
             bio: None,
+            bot: false,
         };
 
+        let instance_id = person::instance_id.eq(instance_of(&person_form.ap_id, &mut conn)?);
         let person = insert_into(person::table)
-            .values(person_form)
+            .values((person_form, instance_id))
             .get_result::<Person>(conn.deref_mut())?;
 
         let local_user_form = LocalUserInsertForm {
             password_encrypted: password.map(|p| hash(p, DEFAULT_COST)).transpose()?,
             person_id: person.id,
-            admin,
+            role,
             email,
             email_verified: false,
         };
@@ -344,6 +380,28 @@ impl LocalUser {
         .ok_or(anyhow!("Email is taken").into())
     }
 
+    /// Role of the user, or `None` if it is not a local user.
+    pub fn read_role(person_id: PersonId, context: &IbisContext) -> BackendResult<Option<Role>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::person_id.eq(person_id))
+            .select(local_user::role)
+            .get_result(conn.deref_mut())
+            .optional()?)
+    }
+
+    pub fn update_role(
+        person_id: PersonId,
+        role: Role,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(local_user::table)
+            .filter(local_user::person_id.eq(person_id))
+            .set(local_user::role.eq(role))
+            .get_result(conn.deref_mut())?)
+    }
+
     pub fn update_password(
         password: String,
         id: LocalUserId,
@@ -377,3 +435,12 @@ impl OAuthAccount {
             .get_result(conn.deref_mut())?)
     }
 }
+
+/// Id of the instance which the user belongs to, or `None` if the instance is not known.
+fn instance_of(ap_id: &DbUrl, conn: &mut PgConnection) -> BackendResult<Option<InstanceId>> {
+    Ok(instance::table
+        .filter(instance::domain.eq(extract_domain(ap_id.inner())))
+        .select(instance::id)
+        .first(conn)
+        .optional()?)
+}
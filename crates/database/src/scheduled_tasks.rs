@@ -8,7 +8,7 @@ use diesel::{
     sql_query,
     sql_types::Timestamptz,
 };
-use ibis_database_schema::sent_activity;
+use ibis_database_schema::{login_session, sent_activity};
 use log::{error, info};
 use std::time::Duration;
 
@@ -19,11 +19,17 @@ pub fn start(pool: DbPool) {
     cleanup_sent_activities(&pool)
         .inspect_err(|e| error!("{e}"))
         .ok();
+    cleanup_login_sessions(&pool)
+        .inspect_err(|e| error!("{e}"))
+        .ok();
     scheduler.every(1.hour()).run(move || {
         active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
         cleanup_sent_activities(&pool)
             .inspect_err(|e| error!("{e}"))
             .ok();
+        cleanup_login_sessions(&pool)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
     });
 
     let _ = scheduler.watch_thread(Duration::from_secs(60));
@@ -59,6 +65,22 @@ fn cleanup_sent_activities(pool: &DbPool) -> BackendResult<()> {
     Ok(())
 }
 
+/// Login tokens expire after one year, so their sessions are not needed anymore.
+fn cleanup_login_sessions(pool: &DbPool) -> BackendResult<()> {
+    use diesel::dsl::IntervalDsl;
+    info!("Cleanup expired login sessions");
+    let mut conn = pool.get()?;
+
+    let now = diesel::dsl::now.into_sql::<Timestamptz>();
+    diesel::delete(
+        login_session::table.filter(login_session::published.lt(now - IntervalDsl::years(1))),
+    )
+    .execute(&mut conn)?;
+
+    info!("Done with cleaning up login sessions");
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -69,6 +91,7 @@ mod test {
         let context = IbisContext::init(IbisConfig::read()?, false)?;
         active_counts(&context.db_pool)?;
         cleanup_sent_activities(&context.db_pool)?;
+        cleanup_login_sessions(&context.db_pool)?;
         Ok(())
     }
 }
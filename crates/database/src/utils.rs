@@ -1,4 +1,14 @@
-use crate::{DbUrl, error::BackendResult};
+use crate::{
+    DbUrl,
+    common::{
+        api_token::ApiTokenScope,
+        federation_list::FederationMode,
+        modlog::ModAction,
+        report::ReportStatus,
+        user::Role,
+    },
+    error::BackendResult,
+};
 use activitypub_federation::{
     fetch::{collection_id::CollectionId, object_id::ObjectId},
     http_signatures::{Keypair, generate_actor_keypair},
@@ -31,6 +41,81 @@ where
     }
 }
 
+impl ToSql<Text, Pg> for Role {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for Role
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
+impl ToSql<Text, Pg> for ModAction {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ModAction
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
+impl ToSql<Text, Pg> for ReportStatus {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ReportStatus
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
+impl ToSql<Text, Pg> for ApiTokenScope {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ApiTokenScope
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
+impl ToSql<Text, Pg> for FederationMode {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for FederationMode
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
 #[expect(clippy::from_over_into)]
 impl Into<DbUrl> for Url {
     fn into(self) -> DbUrl {
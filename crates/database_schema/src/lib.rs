@@ -1,6 +1,28 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+}
+
 diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Text>,
+        bot_edits -> Bool,
+        published -> Timestamptz,
+        last_used -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
     article (id) {
         id -> Int4,
         title -> Text,
@@ -14,6 +36,8 @@ diesel::table! {
         removed -> Bool,
         updated -> Timestamptz,
         pending -> Bool,
+        search_vector -> Nullable<Tsvector>,
+        review_edits -> Bool,
     }
 }
 
@@ -24,6 +48,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    article_link (id) {
+        id -> Int4,
+        source_id -> Int4,
+        target_title -> Text,
+        target_domain -> Text,
+    }
+}
+
+diesel::table! {
+    article_redirect (id) {
+        id -> Int4,
+        article_id -> Int4,
+        title -> Text,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     comment (id) {
         id -> Int4,
@@ -54,6 +98,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    delivery_queue (id) {
+        id -> Int4,
+        #[max_length = 255]
+        activity_id -> Varchar,
+        #[max_length = 255]
+        actor_id -> Varchar,
+        #[max_length = 255]
+        inbox -> Varchar,
+        #[max_length = 255]
+        domain -> Varchar,
+        attempts -> Int4,
+        next_attempt -> Timestamptz,
+        last_error -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    delivery_status (domain) {
+        #[max_length = 255]
+        domain -> Varchar,
+        fail_count -> Int4,
+        last_success -> Nullable<Timestamptz>,
+        last_failure -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        failing_since -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     edit (id) {
         id -> Int4,
@@ -67,6 +141,15 @@ diesel::table! {
         previous_version_id -> Uuid,
         published -> Timestamptz,
         pending -> Bool,
+        awaiting_review -> Bool,
+        bot -> Bool,
+    }
+}
+
+diesel::table! {
+    edit_snapshot (edit_id) {
+        edit_id -> Int4,
+        text -> Text,
     }
 }
 
@@ -79,6 +162,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federation_list (id) {
+        id -> Int4,
+        #[max_length = 255]
+        domain -> Varchar,
+        mode -> Text,
+        reason -> Text,
+        published -> Timestamptz,
+        expires -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     instance (id) {
         id -> Int4,
@@ -97,6 +192,10 @@ diesel::table! {
         #[max_length = 255]
         instances_url -> Varchar,
         name -> Nullable<Text>,
+        review_all_edits -> Bool,
+        review_new_user_edits -> Int4,
+        require_totp_for_moderators -> Bool,
+        federation_list_imported -> Bool,
     }
 }
 
@@ -131,10 +230,56 @@ diesel::table! {
         id -> Int4,
         password_encrypted -> Nullable<Text>,
         person_id -> Int4,
-        admin -> Bool,
         email -> Nullable<Text>,
         email_verified -> Bool,
         email_notifications -> Bool,
+        role -> Text,
+        totp_secret -> Nullable<Text>,
+        totp_enabled -> Bool,
+        totp_last_step -> Nullable<Int8>,
+        totp_failed_attempts -> Int4,
+        totp_last_failure -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    login_session (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        token_id -> Uuid,
+        user_agent -> Nullable<Text>,
+        ip -> Nullable<Text>,
+        published -> Timestamptz,
+        last_seen -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    media (id) {
+        id -> Int4,
+        #[max_length = 255]
+        filename -> Varchar,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        instance_id -> Int4,
+        creator_id -> Nullable<Int4>,
+        #[max_length = 255]
+        mime_type -> Varchar,
+        local -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    modlog (id) {
+        id -> Int4,
+        moderator_id -> Int4,
+        action -> Text,
+        reason -> Nullable<Text>,
+        article_id -> Nullable<Int4>,
+        target_person_id -> Nullable<Int4>,
+        published -> Timestamptz,
+        target_domain -> Nullable<Text>,
     }
 }
 
@@ -148,6 +293,7 @@ diesel::table! {
         edit_id -> Nullable<Int4>,
         published -> Timestamptz,
         conflict_id -> Nullable<Int4>,
+        report_id -> Nullable<Int4>,
     }
 }
 
@@ -186,6 +332,19 @@ diesel::table! {
         display_name -> Nullable<Varchar>,
         #[max_length = 1000]
         bio -> Nullable<Varchar>,
+        bot -> Bool,
+        instance_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    person_ban (id) {
+        id -> Int4,
+        person_id -> Int4,
+        moderator_id -> Int4,
+        reason -> Text,
+        published -> Timestamptz,
+        expires -> Nullable<Timestamptz>,
     }
 }
 
@@ -196,6 +355,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    report (id) {
+        id -> Int4,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        creator_id -> Int4,
+        article_id -> Int4,
+        edit_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        reason -> Text,
+        status -> Text,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     sent_activity (id) {
         #[max_length = 255]
@@ -205,44 +380,80 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    totp_recovery_code (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        code_hash -> Text,
+    }
+}
+
+diesel::joinable!(api_token -> local_user (local_user_id));
 diesel::joinable!(article -> instance (instance_id));
 diesel::joinable!(article_follow -> article (article_id));
 diesel::joinable!(article_follow -> local_user (local_user_id));
+diesel::joinable!(article_link -> article (source_id));
+diesel::joinable!(article_redirect -> article (article_id));
 diesel::joinable!(comment -> article (article_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(conflict -> article (article_id));
 diesel::joinable!(conflict -> person (creator_id));
+diesel::joinable!(delivery_queue -> sent_activity (activity_id));
 diesel::joinable!(edit -> article (article_id));
 diesel::joinable!(edit -> person (creator_id));
+diesel::joinable!(edit_snapshot -> edit (edit_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(instance_follow -> instance (instance_id));
 diesel::joinable!(instance_follow -> person (follower_id));
 diesel::joinable!(local_user -> person (person_id));
+diesel::joinable!(login_session -> local_user (local_user_id));
+diesel::joinable!(media -> instance (instance_id));
+diesel::joinable!(media -> person (creator_id));
+diesel::joinable!(modlog -> article (article_id));
 diesel::joinable!(notification -> article (article_id));
 diesel::joinable!(notification -> comment (comment_id));
 diesel::joinable!(notification -> conflict (conflict_id));
 diesel::joinable!(notification -> edit (edit_id));
 diesel::joinable!(notification -> local_user (local_user_id));
 diesel::joinable!(notification -> person (creator_id));
+diesel::joinable!(notification -> report (report_id));
 diesel::joinable!(oauth_account -> local_user (local_user_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
+diesel::joinable!(report -> article (article_id));
+diesel::joinable!(report -> comment (comment_id));
+diesel::joinable!(report -> edit (edit_id));
+diesel::joinable!(report -> person (creator_id));
+diesel::joinable!(totp_recovery_code -> local_user (local_user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_token,
     article,
     article_follow,
+    article_link,
+    article_redirect,
     comment,
     conflict,
+    delivery_queue,
+    delivery_status,
     edit,
+    edit_snapshot,
     email_verification,
+    federation_list,
     instance,
     instance_follow,
     instance_stats,
     jwt_secret,
     local_user,
+    login_session,
+    media,
+    modlog,
     notification,
     oauth_account,
     password_reset_request,
     person,
+    person_ban,
     person_follow,
+    report,
     sent_activity,
+    totp_recovery_code,
 );
@@ -1,7 +1,7 @@
 use super::update_article::UpdateArticle;
 use crate::{
     AnnounceActivity,
-    activities::reject::RejectEdit,
+    activities::{check_author_not_banned, reject::RejectEdit},
     generate_activity_id,
     objects::{
         edit::{ApubEdit, EditWrapper},
@@ -103,6 +103,7 @@ impl Activity for CreateOrEditArticle {
     }
 
     async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        check_author_not_banned(&self.object.attributed_to, context)?;
         let article = Article::read_from_ap_id(&self.object.object.clone().into(), context);
         if self.kind == CreateOrEditType::Create {
             if article.is_ok() {
@@ -153,6 +154,13 @@ impl Activity for CreateOrEditArticle {
         match apply(&article.text, &patch) {
             Ok(applied) => {
                 let edit = EditWrapper::from_json(self.object.clone(), context).await?;
+                if edit.awaiting_review {
+                    // hide new article until its first edit is approved
+                    if self.kind == CreateOrEditType::Create {
+                        Article::update_pending(article.id, true, context)?;
+                    }
+                    return Ok(());
+                }
                 let article = Article::update_text(edit.article_id, &applied, context)?;
                 if article.local {
                     AnnounceActivity::send(AnnouncableActivities::EditArticle(self), context)
@@ -1,4 +1,5 @@
 use crate::{
+    activities::check_author_not_banned,
     generate_activity_id,
     objects::{
         comment::{ApubComment, CommentWrapper},
@@ -82,9 +83,10 @@ impl Activity for CreateOrUpdateComment {
         self.actor.inner()
     }
 
-    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         verify_domains_match(&self.id, self.object.id.inner())?;
         verify_domains_match(&self.id, self.actor.inner())?;
+        check_author_not_banned(&self.object.attributed_to, context)?;
         Ok(())
     }
 
@@ -0,0 +1,154 @@
+use crate::{
+    activities::check_author_not_banned,
+    generate_activity_id,
+    objects::{instance::InstanceWrapper, user::PersonWrapper},
+    send_ibis_activity,
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::FlagType,
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::Activity,
+};
+use anyhow::anyhow;
+use ibis_database::{
+    DbUrl,
+    common::{
+        article::{Article, Edit},
+        comment::Comment,
+        instance::Instance,
+        newtypes::{CommentId, EditId},
+        report::Report,
+    },
+    error::{BackendError, BackendResult},
+    impls::{IbisContext, notifications::Notification, report::DbReportForm},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Report about an article, edit or comment. Sent to the instance where the content originates
+/// so that its moderators can take action.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Flag {
+    pub actor: ObjectId<PersonWrapper>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub to: Vec<Url>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub object: Vec<Url>,
+    #[serde(rename = "type")]
+    pub kind: FlagType,
+    pub id: Url,
+    /// Reason for the report
+    pub content: String,
+}
+
+impl Flag {
+    /// Store a new report and notify local moderators. Reports about remote content are also
+    /// sent to the origin instance.
+    pub async fn submit(
+        article: &Article,
+        edit: Option<&Edit>,
+        comment: Option<&Comment>,
+        reason: String,
+        creator: PersonWrapper,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<Report> {
+        let id = generate_activity_id(context)?;
+        let form = DbReportForm {
+            ap_id: id.clone().into(),
+            creator_id: creator.id,
+            article_id: article.id,
+            edit_id: edit.map(|e| e.id),
+            comment_id: comment.map(|c| c.id),
+            reason: reason.clone(),
+        };
+        let report = Report::create(&form, context)?;
+        Notification::notify_report(&report, context).await?;
+
+        if !article.local {
+            let object = match (edit, comment) {
+                (Some(edit), _) => edit.ap_id.clone(),
+                (_, Some(comment)) => comment.ap_id.clone(),
+                _ => article.ap_id.clone(),
+            };
+            let instance: InstanceWrapper = Instance::read(article.instance_id, context)?.into();
+            let flag = Flag {
+                actor: creator.ap_id.clone().into(),
+                to: vec![instance.ap_id.clone().into()],
+                object: vec![object.into()],
+                kind: Default::default(),
+                id,
+                content: reason,
+            };
+            let inbox = instance.inbox_url.parse()?;
+            send_ibis_activity(&creator, flag, vec![inbox], context).await?;
+        }
+        Ok(report)
+    }
+}
+
+#[async_trait::async_trait]
+impl Activity for Flag {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(&self.id, self.actor.inner())?;
+        check_author_not_banned(&self.actor, context)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // other software may include additional objects such as the reported user
+        let (article, edit_id, comment_id) = self
+            .object
+            .iter()
+            .find_map(|o| read_local_target(o.clone().into(), context))
+            .ok_or(anyhow!("Flag does not contain local content"))?;
+        let creator = self.actor.dereference(context).await?;
+        let form = DbReportForm {
+            ap_id: self.id.into(),
+            creator_id: creator.id,
+            article_id: article.id,
+            edit_id,
+            comment_id,
+            reason: self.content,
+        };
+        let report = Report::create(&form, context)?;
+        Notification::notify_report(&report, context).await?;
+        Ok(())
+    }
+}
+
+/// Find the local article, edit or comment with the given id.
+fn read_local_target(
+    ap_id: DbUrl,
+    context: &IbisContext,
+) -> Option<(Article, Option<EditId>, Option<CommentId>)> {
+    let target = if let Ok(edit) = Edit::read_from_ap_id(&ap_id, context) {
+        (
+            Article::read(edit.article_id, context).ok()?,
+            Some(edit.id),
+            None,
+        )
+    } else if let Ok(comment) = Comment::read_from_ap_id(&ap_id, context) {
+        (
+            Article::read(comment.article_id, context).ok()?,
+            None,
+            Some(comment.id),
+        )
+    } else {
+        (Article::read_from_ap_id(&ap_id, context).ok()?, None, None)
+    };
+    target.0.local.then_some(target)
+}
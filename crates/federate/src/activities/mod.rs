@@ -3,13 +3,15 @@ use crate::{
     objects::{instance::InstanceWrapper, user::PersonWrapper},
     routes::AnnouncableActivities,
 };
-use activitypub_federation::config::Data;
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use announce::AnnounceActivity;
 use article::update_article::UpdateArticle;
 use ibis_database::{
     common::{
         article::{Article, Edit, EditVersion},
+        ban::PersonBan,
         instance::Instance,
+        user::Person,
     },
     error::BackendResult,
     impls::{IbisContext, edit::DbEditForm},
@@ -18,9 +20,25 @@ use ibis_database::{
 pub mod announce;
 pub mod article;
 pub mod comment;
+pub mod flag;
 pub mod following;
 pub mod reject;
 
+/// Reject edits and comments by users who are banned on this instance. Users which are not
+/// stored locally cannot be banned yet.
+pub(crate) fn check_author_not_banned(
+    author: &ObjectId<PersonWrapper>,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    if let Ok(person) = Person::read_from_ap_id(&author.clone().into(), context) {
+        PersonBan::check_not_banned(person.id, context)?;
+    }
+    Ok(())
+}
+
+/// Store a new edit by a local user and federate it. With `bot` the edit is marked as made by
+/// a script.
+#[expect(clippy::too_many_arguments)]
 pub async fn submit_article_update(
     new_text: String,
     summary: String,
@@ -28,6 +46,7 @@ pub async fn submit_article_update(
     article: &Article,
     person: PersonWrapper,
     is_create: bool,
+    bot: bool,
     context: &Data<IbisContext>,
 ) -> BackendResult<()> {
     let mut form = DbEditForm::new(
@@ -39,8 +58,19 @@ pub async fn submit_article_update(
         false,
     )?;
 
+    form.bot = bot;
     // insert edit to remote instance as pending, so only the creator can see it
     form.pending = !article.local;
+    if Edit::requires_review(article, person.id, context)? {
+        // keep the edit hidden until it is approved by a reviewer
+        form.pending = true;
+        form.awaiting_review = true;
+        Edit::create_or_update(&form, false, context).await?;
+        if is_create {
+            Article::update_pending(article.id, true, context)?;
+        }
+        return Ok(());
+    }
     let edit = Edit::create_or_update(&form, true, context).await?;
 
     let article_instance: InstanceWrapper = Instance::read(article.instance_id, context)?.into();
@@ -60,3 +90,19 @@ pub async fn submit_article_update(
     }
     Ok(())
 }
+
+/// Federate an edit to a local article which was approved by a reviewer.
+pub async fn submit_approved_edit(
+    edit: Edit,
+    article: Article,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    let person: PersonWrapper = Person::read(edit.creator_id, context)?.into();
+    let local_instance: InstanceWrapper = Instance::read_local(context)?.into();
+    let is_create = edit.previous_version_id == EditVersion::default();
+    let edit_activity =
+        CreateOrEditArticle::new(edit.into(), &person, &local_instance, is_create, context).await?;
+    UpdateArticle::send(article.into(), context).await?;
+    AnnounceActivity::send(AnnouncableActivities::EditArticle(edit_activity), context).await?;
+    Ok(())
+}
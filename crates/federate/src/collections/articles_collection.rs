@@ -1,14 +1,15 @@
+use super::{COLLECTION_PAGE_SIZE, collection_page_url};
 use crate::{activities::article::update_article::UpdateArticle, objects::article::ArticleWrapper};
 use activitypub_federation::{
     config::Data,
-    fetch::collection_id::CollectionId,
-    kinds::collection::CollectionType,
+    fetch::{collection_id::CollectionId, fetch_object_http},
+    kinds::collection::OrderedCollectionPageType,
     protocol::verification::verify_domains_match,
     traits::{Activity, Collection},
 };
 use futures::future::{join_all, try_join_all};
 use ibis_database::{
-    common::{article::Article, utils::http_protocol_str},
+    common::{article::Article, pagination::PaginationCursor, utils::http_protocol_str},
     error::{BackendError, BackendResult},
     impls::IbisContext,
 };
@@ -16,13 +17,38 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// All local articles. The items are served in pages starting at `first`, older versions
+/// without pagination send all of them in `items` instead.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApubArticleCollection {
-    pub r#type: CollectionType,
+    pub r#type: ArticleCollectionType,
     pub id: Url,
-    pub total_items: i32,
+    #[serde(default, skip_serializing)]
     pub items: Vec<UpdateArticle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first: Option<Url>,
+}
+
+/// Older versions serve the articles as unordered collection.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub enum ArticleCollectionType {
+    #[default]
+    OrderedCollection,
+    Collection,
+}
+
+/// A single page of local articles. Remote instances follow the `next` links to read all of
+/// them.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubArticleCollectionPage {
+    pub r#type: OrderedCollectionPageType,
+    pub id: Url,
+    pub part_of: Url,
+    pub ordered_items: Vec<UpdateArticle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Url>,
 }
 
 #[derive(Clone, Debug)]
@@ -35,6 +61,41 @@ pub fn local_articles_url(domain: &str) -> BackendResult<CollectionId<ArticleCol
     ))?)
 }
 
+impl ArticleCollection {
+    pub(crate) async fn read_local_page(
+        cursor: Option<PaginationCursor>,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<ApubArticleCollectionPage> {
+        let collection_url: Url = local_articles_url(context.conf.federation_domain())?.into();
+        let local_articles = Article::read_all(
+            Some(true),
+            None,
+            false,
+            cursor.clone(),
+            Some(COLLECTION_PAGE_SIZE),
+            context,
+        )?;
+        let articles = try_join_all(
+            local_articles
+                .items
+                .into_iter()
+                .map(ArticleWrapper)
+                .map(|a| UpdateArticle::new(a, context))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+        Ok(ApubArticleCollectionPage {
+            r#type: Default::default(),
+            id: collection_page_url(&collection_url, cursor.as_ref()),
+            next: local_articles
+                .next_page
+                .map(|next| collection_page_url(&collection_url, Some(&next))),
+            part_of: collection_url,
+            ordered_items: articles,
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl Collection for ArticleCollection {
     type Owner = ();
@@ -46,22 +107,13 @@ impl Collection for ArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
-        let local_articles = Article::read_all(Some(true), None, false, context)?;
-        let articles = try_join_all(
-            local_articles
-                .into_iter()
-                .map(ArticleWrapper)
-                .map(|a| UpdateArticle::new(a, context))
-                .collect::<Vec<_>>(),
-        )
-        .await?;
-        let collection = ApubArticleCollection {
+        let id: Url = local_articles_url(context.conf.federation_domain())?.into();
+        Ok(ApubArticleCollection {
             r#type: Default::default(),
-            id: local_articles_url(context.conf.federation_domain())?.into(),
-            total_items: articles.len().try_into()?,
-            items: articles,
-        };
-        Ok(collection)
+            first: Some(collection_page_url(&id, None)),
+            id,
+            items: vec![],
+        })
     }
 
     async fn verify(
@@ -78,20 +130,31 @@ impl Collection for ArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
-        let articles = apub
-            .items
-            .into_iter()
-            .filter(|i| !i.object.id.is_local(context))
-            .map(|update| async {
-                let id = update.object.id.clone();
-                UpdateArticle::verify(&update, context).await?;
-                let res = UpdateArticle::receive(update, context).await;
-                if let Err(e) = &res {
-                    warn!("Failed to synchronize article {id}: {e}");
-                }
-                res
-            });
-        join_all(articles).await;
+        let mut items = apub.items;
+        let mut next = apub.first;
+        loop {
+            let articles = items
+                .into_iter()
+                .filter(|i| !i.object.id.is_local(context))
+                .map(|update| async {
+                    let id = update.object.id.clone();
+                    UpdateArticle::verify(&update, context).await?;
+                    let res = UpdateArticle::receive(update, context).await;
+                    if let Err(e) = &res {
+                        warn!("Failed to synchronize article {id}: {e}");
+                    }
+                    res
+                });
+            join_all(articles).await;
+
+            let Some(url) = next else {
+                break;
+            };
+            verify_domains_match(&url, &apub.id)?;
+            let page: ApubArticleCollectionPage = fetch_object_http(&url, context).await?.object;
+            items = page.ordered_items;
+            next = page.next;
+        }
 
         Ok(ArticleCollection(()))
     }
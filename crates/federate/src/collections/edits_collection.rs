@@ -1,32 +1,90 @@
+use super::{COLLECTION_PAGE_SIZE, collection_page_url};
 use crate::objects::edit::{ApubEdit, EditWrapper};
 use activitypub_federation::{
     config::Data,
-    kinds::collection::OrderedCollectionType,
+    fetch::fetch_object_http,
+    kinds::collection::{OrderedCollectionPageType, OrderedCollectionType},
     protocol::verification::verify_domains_match,
     traits::{Collection, Object},
 };
 use futures::{future, future::try_join_all};
 use ibis_database::{
-    common::article::{Article, Edit},
-    error::BackendError,
-    impls::IbisContext,
+    common::{
+        article::{Article, Edit},
+        pagination::PaginationCursor,
+    },
+    error::{BackendError, BackendResult},
+    impls::{IbisContext, edit::ViewEditParams},
 };
 use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// Edits of an article. The items are served in pages starting at `first`, older versions
+/// without pagination send all of them in `items` instead.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApubEditCollection {
     pub r#type: OrderedCollectionType,
     pub id: Url,
-    pub total_items: i32,
+    #[serde(default, skip_serializing)]
     pub items: Vec<ApubEdit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first: Option<Url>,
+}
+
+/// A single page of the edits of an article, oldest first. Remote instances follow the `next`
+/// links to read the whole history.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubEditCollectionPage {
+    pub r#type: OrderedCollectionPageType,
+    pub id: Url,
+    pub part_of: Url,
+    pub ordered_items: Vec<ApubEdit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Url>,
 }
 
 #[derive(Clone, Debug)]
 pub struct EditCollection(pub Vec<EditWrapper>);
 
+impl EditCollection {
+    pub(crate) async fn read_local_page(
+        article: &Article,
+        cursor: Option<PaginationCursor>,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<ApubEditCollectionPage> {
+        let article = Article::read(article.id, context)?;
+        let collection_url: Url = article.edits_id()?.into();
+        let edits = Edit::list_views(
+            ViewEditParams::ArticleId(article.id),
+            &None,
+            cursor.clone(),
+            Some(COLLECTION_PAGE_SIZE),
+            context,
+        )?;
+        let items = future::try_join_all(
+            edits
+                .items
+                .into_iter()
+                .map(|e| EditWrapper(e.edit))
+                .map(|e| e.into_json(context))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+        Ok(ApubEditCollectionPage {
+            r#type: Default::default(),
+            id: collection_page_url(&collection_url, cursor.as_ref()),
+            next: edits
+                .next_page
+                .map(|next| collection_page_url(&collection_url, Some(&next))),
+            part_of: collection_url,
+            ordered_items: items,
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl Collection for EditCollection {
     type Owner = Article;
@@ -39,22 +97,13 @@ impl Collection for EditCollection {
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
         let article = Article::read(article.id, context)?;
-        let edits = Edit::list_for_article(article.id, context)?;
-        let edits = future::try_join_all(
-            edits
-                .into_iter()
-                .map(EditWrapper)
-                .map(|e| e.into_json(context))
-                .collect::<Vec<_>>(),
-        )
-        .await?;
-        let collection = ApubEditCollection {
+        let id: Url = article.edits_id()?.into();
+        Ok(ApubEditCollection {
             r#type: Default::default(),
-            id: article.edits_id()?.into(),
-            total_items: edits.len().try_into()?,
-            items: edits,
-        };
-        Ok(collection)
+            first: Some(collection_page_url(&id, None)),
+            id,
+            items: vec![],
+        })
     }
 
     async fn verify(
@@ -71,17 +120,32 @@ impl Collection for EditCollection {
         owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
-        let edits = try_join_all(
-            apub.items
-                .into_iter()
-                .map(|i| EditWrapper::from_json(i, context)),
-        )
-        .await
-        .map_err(|e| warn!("Failed to synchronize edits for {}: {e}", owner.ap_id))
-        .ok()
-        .into_iter()
-        .flatten()
-        .collect();
+        let mut edits = vec![];
+        let mut items = apub.items;
+        let mut next = apub.first;
+        loop {
+            let res = try_join_all(
+                items
+                    .into_iter()
+                    .map(|i| EditWrapper::from_json(i, context)),
+            )
+            .await;
+            match res {
+                Ok(page_edits) => edits.extend(page_edits),
+                Err(e) => {
+                    warn!("Failed to synchronize edits for {}: {e}", owner.ap_id);
+                    break;
+                }
+            }
+
+            let Some(url) = next else {
+                break;
+            };
+            verify_domains_match(&url, &apub.id)?;
+            let page: ApubEditCollectionPage = fetch_object_http(&url, context).await?.object;
+            items = page.ordered_items;
+            next = page.next;
+        }
         Ok(EditCollection(edits))
     }
 }
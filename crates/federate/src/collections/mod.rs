@@ -1,5 +1,37 @@
+use ibis_database::common::pagination::PaginationCursor;
+use serde::Deserialize;
+use url::Url;
+
 pub mod articles_collection;
 pub(crate) mod edits_collection;
 pub(crate) mod empty_outbox;
 pub mod instance_collection;
 pub(crate) mod instance_follower;
+
+/// Number of items in each page of the paginated collections.
+const COLLECTION_PAGE_SIZE: i64 = 50;
+
+/// Query parameters for a page of a paginated collection. Without them the collection itself
+/// is served, which links to the first page.
+#[derive(Deserialize, Debug)]
+pub(crate) struct CollectionPageParams {
+    #[serde(default)]
+    pub page: bool,
+    pub cursor: Option<PaginationCursor>,
+}
+
+impl CollectionPageParams {
+    pub fn is_page(&self) -> bool {
+        self.page || self.cursor.is_some()
+    }
+}
+
+/// Url of the collection page which starts after `cursor`, or of the first page.
+fn collection_page_url(collection: &Url, cursor: Option<&PaginationCursor>) -> Url {
+    let mut url = collection.clone();
+    url.query_pairs_mut().append_pair("page", "true");
+    if let Some(cursor) = cursor {
+        url.query_pairs_mut().append_pair("cursor", &cursor.0);
+    }
+    url
+}
@@ -0,0 +1,147 @@
+use crate::objects::{instance::InstanceWrapper, user::PersonWrapper};
+use activitypub_federation::{
+    activity_sending::SendActivityTask,
+    config::Data,
+    protocol::context::WithContext,
+    traits::Activity,
+};
+use anyhow::anyhow;
+use futures::future::join_all;
+use ibis_database::{
+    common::{delivery::DeliveryStatus, instance::Instance, user::Person, utils::extract_domain},
+    error::{BackendError, BackendResult},
+    impls::{
+        IbisContext,
+        delivery::{DeliveryQueueEntry, DeliveryQueueInsertForm},
+    },
+};
+use itertools::Itertools;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
+use url::Url;
+
+/// Maximum number of queued deliveries which are retried at once
+const RETRY_BATCH_SIZE: i64 = 100;
+
+/// Add the activity to the delivery queue for each inbox, and attempt the first delivery. Inboxes
+/// of dead instances are skipped.
+pub(crate) async fn queue_delivery(
+    activity_id: &Url,
+    actor_id: &Url,
+    json: String,
+    inboxes: Vec<Url>,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    let dead_domains = DeliveryStatus::read_dead_domains(context)?;
+    let forms: Vec<_> = inboxes
+        .into_iter()
+        .unique()
+        .map(|inbox| (extract_domain(&inbox), inbox))
+        .filter(|(domain, _)| domain != context.domain() && !dead_domains.contains(domain))
+        .map(|(domain, inbox)| DeliveryQueueInsertForm {
+            activity_id: activity_id.clone().into(),
+            actor_id: actor_id.clone().into(),
+            inbox: inbox.into(),
+            domain,
+        })
+        .collect();
+    if forms.is_empty() {
+        return Ok(());
+    }
+    let entries = DeliveryQueueEntry::create(forms, context)?;
+
+    let context = context.reset_request_count();
+    let join = tokio::spawn(async move {
+        join_all(entries.iter().map(|e| deliver(e, &json, &context))).await;
+    });
+
+    // In production do activity send in background to avoid slow api calls. For tests use
+    // synchronous federation.
+    if cfg!(debug_assertions) {
+        join.await?;
+    }
+    Ok(())
+}
+
+/// Periodically retry deliveries which failed before. Runs until the process is stopped.
+pub async fn retry_failed_deliveries(context: Data<IbisContext>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let entries = DeliveryQueueEntry::claim_due(RETRY_BATCH_SIZE, &context)
+            .inspect_err(|e| warn!("Failed to read delivery queue: {e}"))
+            .unwrap_or_default();
+        join_all(entries.iter().map(|(e, json)| deliver(e, json, &context))).await;
+    }
+}
+
+async fn deliver(entry: &DeliveryQueueEntry, json: &str, context: &Data<IbisContext>) {
+    let res = match send(entry, json, context).await {
+        Ok(()) => entry.delivered(context),
+        Err(e) => {
+            warn!(
+                "Failed to send activity {} to {}: {e}",
+                entry.activity_id, entry.inbox
+            );
+            entry.failed(&e.to_string(), context)
+        }
+    };
+    res.inspect_err(|e| warn!("Failed to update delivery queue: {e}"))
+        .ok();
+}
+
+async fn send(
+    entry: &DeliveryQueueEntry,
+    json: &str,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    let activity = WithContext::new_default(serde_json::from_str::<RawActivity>(json)?);
+    let inbox: Url = entry.inbox.clone().into();
+    // The actor is either a local user or the local instance
+    let tasks = if let Ok(person) = Person::read_from_ap_id(&entry.actor_id, context) {
+        let actor: PersonWrapper = person.into();
+        SendActivityTask::prepare(&activity, &actor, vec![inbox], context).await?
+    } else {
+        let actor: InstanceWrapper = Instance::read_from_ap_id(&entry.actor_id, context)?.into();
+        SendActivityTask::prepare(&activity, &actor, vec![inbox], context).await?
+    };
+    // No task is returned if the domain is blocked, in this case there is nothing to retry
+    for task in tasks {
+        task.sign_and_send(context).await?;
+    }
+    Ok(())
+}
+
+/// Activity as it was stored when it was first sent, so that it can be delivered again without
+/// knowing the concrete type.
+#[derive(Deserialize, Serialize, Debug)]
+struct RawActivity {
+    id: Url,
+    actor: Url,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+#[async_trait::async_trait]
+impl Activity for RawActivity {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        &self.actor
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Err(anyhow!("Raw activities are only used for sending").into())
+    }
+
+    async fn receive(self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Err(anyhow!("Raw activities are only used for sending").into())
+    }
+}
@@ -1,22 +1,23 @@
 use activities::announce::AnnounceActivity;
 use activitypub_federation::{
-    activity_queue::queue_activity,
     config::{Data, UrlVerifier},
     error::Error as ActivityPubError,
-    protocol::context::WithContext,
     traits::{Activity, Actor},
 };
 use async_trait::async_trait;
+use delivery::queue_delivery;
 use ibis_database::{
-    common::utils::http_protocol_str,
-    config::IbisConfig,
+    common::{
+        federation_list::FederationListEntry,
+        utils::{extract_domain, http_protocol_str},
+    },
     error::BackendResult,
     impls::{
         IbisContext,
         sent_activity::{SentActivity, SentActivityInsertForm},
     },
 };
-use log::{info, warn};
+use log::info;
 use objects::{instance::InstanceWrapper, user::PersonWrapper};
 use rand::{RngExt, distr::Alphanumeric, rng};
 use routes::AnnouncableActivities;
@@ -26,9 +27,11 @@ use url::Url;
 
 pub mod activities;
 pub mod collections;
+pub mod delivery;
 pub mod nodeinfo;
 pub mod objects;
 pub mod routes;
+pub mod search;
 pub mod validate;
 pub mod webfinger;
 
@@ -43,29 +46,14 @@ where
     ActorType: Actor + Sync + Clone,
     <A as Activity>::Error: From<activitypub_federation::error::Error>,
 {
+    let json = serde_json::to_string(&activity)?;
     let form = SentActivityInsertForm {
         id: activity.id().clone().into(),
-        json: serde_json::to_string(&activity)?,
+        json: json.clone(),
     };
     SentActivity::create(form, context)?;
     info!("Sending activity {}", activity.id());
-
-    let actor = actor.clone();
-    let context = context.reset_request_count();
-    let join = tokio::spawn(async move {
-        let activity = WithContext::new_default(activity);
-        queue_activity(&activity, &actor, recipients, &context)
-            .await
-            .inspect_err(|e| warn!("Failed to send activity: {e}"))
-            .ok();
-    });
-
-    // In production do activity send in background to avoid slow api calls. For tests use
-    // synchronous federation.
-    if cfg!(debug_assertions) {
-        join.await?;
-    }
-    Ok(())
+    queue_delivery(activity.id(), actor.id(), json, recipients, context).await
 }
 
 pub async fn send_activity_to_instance(
@@ -84,30 +72,15 @@ pub async fn send_activity_to_instance(
 }
 
 #[derive(Clone)]
-pub struct VerifyUrlData(pub IbisConfig);
+pub struct VerifyUrlData(pub IbisContext);
 
 #[async_trait]
 impl UrlVerifier for VerifyUrlData {
-    /// Check domain against allowlist and blocklist from config file.
+    /// Check domain against the federation allowlist and blocklist in the database.
     async fn verify(&self, url: &Url) -> Result<(), ActivityPubError> {
-        let domain = url.domain().expect("url has domain");
-        if let Some(allowlist) = &self.0.federation.allowlist {
-            let allowlist = allowlist.split(',').collect::<Vec<_>>();
-            if !allowlist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is not allowed"
-                )));
-            }
-        }
-        if let Some(blocklist) = &self.0.federation.blocklist {
-            let blocklist = blocklist.split(',').collect::<Vec<_>>();
-            if blocklist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is blocked"
-                )));
-            }
-        }
-        Ok(())
+        let domain = extract_domain(url);
+        FederationListEntry::check_domain(&domain, &self.0)
+            .map_err(|e| ActivityPubError::Other(e.to_string()))
     }
 }
 
@@ -7,7 +7,10 @@ use crate::{
 use activitypub_federation::{
     config::Data,
     fetch::{collection_id::CollectionId, object_id::ObjectId},
-    kinds::{object::ArticleType, public},
+    kinds::{
+        object::{ArticleType, ImageType},
+        public,
+    },
     protocol::{
         helpers::{deserialize_one_or_many, deserialize_skip_error},
         values::MediaTypeMarkdownOrHtml,
@@ -21,16 +24,18 @@ use ibis_database::{
     common::{
         article::{Article, EditVersion},
         instance::Instance,
+        media::{ALLOWED_MEDIA_TYPES, Media},
         user::Person,
+        utils::extract_domain,
     },
     error::BackendError,
-    impls::{IbisContext, article::DbArticleForm},
+    impls::{IbisContext, article::DbArticleForm, media::MediaInsertForm},
 };
-use ibis_markdown::render_article_markdown;
+use ibis_markdown::{extract_media_filenames, render_article_markdown};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::{cmp::Reverse, ops::Deref};
+use std::{cmp::Reverse, iter::once, ops::Deref};
 use url::Url;
 
 #[skip_serializing_none]
@@ -57,6 +62,21 @@ pub struct ApubArticle {
     pub posting_restricted_to_mods: Option<bool>,
     published: Option<DateTime<Utc>>,
     updated: Option<DateTime<Utc>>,
+    /// Previous ids of the article, if it was renamed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    also_known_as: Vec<Url>,
+    /// Uploaded images which are embedded in the article
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachment: Vec<ApubImage>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubImage {
+    #[serde(rename = "type")]
+    kind: ImageType,
+    url: Url,
+    media_type: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -114,6 +134,19 @@ impl Object for ArticleWrapper {
             posting_restricted_to_mods: Some(true),
             published: Some(self.published),
             updated: Some(self.updated),
+            also_known_as: self
+                .read_redirect_ap_ids(context)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            attachment: Media::read_from_filenames(&extract_media_filenames(&self.text), context)?
+                .into_iter()
+                .map(|m| ApubImage {
+                    kind: Default::default(),
+                    url: m.ap_id.into(),
+                    media_type: m.mime_type,
+                })
+                .collect(),
         })
     }
 
@@ -124,6 +157,9 @@ impl Object for ArticleWrapper {
     ) -> Result<(), Self::Error> {
         verify_domains_match(json.id.inner(), expected_domain)?;
         verify_is_remote_object(&json.id, context)?;
+        for former_id in &json.also_known_as {
+            verify_domains_match(former_id, expected_domain)?;
+        }
         Ok(())
     }
 
@@ -154,6 +190,39 @@ impl Object for ArticleWrapper {
             pending: false,
         };
         validate_article_title(&form.title)?;
+
+        // If the article was renamed, update the existing copy instead of creating a new one
+        let existing = once(form.ap_id.inner())
+            .chain(&json.also_known_as)
+            .find_map(|id| Article::read_from_ap_id(&id.clone().into(), context).ok());
+        if let Some(existing) = existing {
+            if existing.ap_id != form.ap_id {
+                Article::rename(existing.id, form.title.clone(), form.ap_id.clone(), context)?;
+            }
+        }
+
+        // Remember where embedded media is hosted, so that it can be displayed locally
+        let article_domain = extract_domain(form.ap_id.inner());
+        for image in json.attachment {
+            let is_valid = extract_domain(&image.url) == article_domain
+                && ALLOWED_MEDIA_TYPES.contains(&image.media_type.as_str());
+            let filename = image.url.path_segments().and_then(|mut p| p.next_back());
+            let Some(filename) =
+                filename.filter(|f| is_valid && Media::is_valid_url(f, &image.url))
+            else {
+                continue;
+            };
+            let form = MediaInsertForm {
+                filename: filename.to_string(),
+                ap_id: image.url.clone().into(),
+                instance_id: instance.id,
+                creator_id: None,
+                mime_type: image.media_type,
+                local: false,
+            };
+            Media::create(&form, context)?;
+        }
+
         let creator = json.attributed_to.dereference(context).await?;
         let article = Article::create_or_update(form, creator.id, context).await?;
 
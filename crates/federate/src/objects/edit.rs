@@ -41,6 +41,8 @@ pub struct ApubEdit {
     pub object: ObjectId<ArticleWrapper>,
     pub attributed_to: ObjectId<PersonWrapper>,
     pub published: DateTime<Utc>,
+    /// Edit was made by a bot through an API token, only included if true
+    pub bot: Option<bool>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -92,6 +94,7 @@ impl Object for EditWrapper {
             object: article.ap_id.into(),
             attributed_to: creator.ap_id.into(),
             published: self.published,
+            bot: self.bot.then_some(true),
         })
     }
 
@@ -118,7 +121,7 @@ impl Object for EditWrapper {
                 Person::ghost(context)?.into()
             }
         };
-        let form = DbEditForm {
+        let mut form = DbEditForm {
             creator_id: creator.id,
             ap_id: json.id.into(),
             diff: json.content,
@@ -128,8 +131,14 @@ impl Object for EditWrapper {
             previous_version_id: json.previous_version,
             published: json.published,
             pending: false,
+            awaiting_review: false,
+            bot: json.bot.unwrap_or_default(),
         };
-        let edit = Edit::create_or_update(&form, true, context).await?;
+        if Edit::requires_review(&article, creator.id, context)? {
+            form.pending = true;
+            form.awaiting_review = true;
+        }
+        let edit = Edit::create_or_update(&form, !form.awaiting_review, context).await?;
         Ok(edit.into())
     }
 }
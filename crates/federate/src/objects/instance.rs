@@ -15,7 +15,7 @@ use activitypub_federation::{
 };
 use chrono::{DateTime, Utc};
 use ibis_database::{
-    common::{instance::Instance, utils::extract_domain},
+    common::{delivery::DeliveryStatus, instance::Instance, utils::extract_domain},
     error::{BackendError, BackendResult},
     impls::{IbisContext, instance::DbInstanceForm},
 };
@@ -142,6 +142,7 @@ impl Object for InstanceWrapper {
             name: json.name,
         };
         let instance = Instance::create(&form, context)?;
+        DeliveryStatus::mark_alive(&instance.domain, context)?;
 
         // TODO: very inefficient to sync all articles every time
         let instance_ = instance.clone();
@@ -2,7 +2,6 @@ use super::{Endpoints, Source, read_from_string_or_source_opt};
 use activitypub_federation::{
     config::Data,
     fetch::object_id::ObjectId,
-    kinds::actor::PersonType,
     protocol::{
         helpers::deserialize_skip_error,
         public_key::PublicKey,
@@ -13,7 +12,7 @@ use activitypub_federation::{
 };
 use chrono::{DateTime, Utc};
 use ibis_database::{
-    common::user::Person,
+    common::{delivery::DeliveryStatus, user::Person, utils::extract_domain},
     error::BackendError,
     impls::{IbisContext, user::PersonInsertForm},
 };
@@ -28,7 +27,7 @@ use url::Url;
 #[serde(rename_all = "camelCase")]
 pub struct ApubUser {
     #[serde(rename = "type")]
-    kind: PersonType,
+    kind: UserKind,
     id: ObjectId<PersonWrapper>,
     preferred_username: String,
     /// displayname
@@ -44,6 +43,13 @@ pub struct ApubUser {
     pub(crate) endpoints: Option<Endpoints>,
 }
 
+/// Bots use the `Service` type, like in other Fediverse software.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+enum UserKind {
+    Person,
+    Service,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PersonWrapper(Person);
 
@@ -86,7 +92,11 @@ impl Object for PersonWrapper {
 
     async fn into_json(self, _context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
         Ok(ApubUser {
-            kind: Default::default(),
+            kind: if self.bot {
+                UserKind::Service
+            } else {
+                UserKind::Person
+            },
             id: self.ap_id.clone().into(),
             preferred_username: self.username.clone(),
             inbox: Url::parse(&self.inbox_url)?,
@@ -114,6 +124,7 @@ impl Object for PersonWrapper {
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
         let bio = read_from_string_or_source_opt(&json.summary, &json.media_type, &json.source);
+        DeliveryStatus::mark_alive(&extract_domain(json.id.inner()), context)?;
         let inbox_url = json.endpoints.map(|e| e.shared_inbox).unwrap_or(json.inbox);
         let form = PersonInsertForm {
             username: json.preferred_username,
@@ -125,6 +136,7 @@ impl Object for PersonWrapper {
             local: false,
             display_name: json.name,
             bio,
+            bot: json.kind == UserKind::Service,
         };
         Person::create(&form, context).map(Into::into)
     }
@@ -12,10 +12,12 @@ use crate::{
             delete_comment::DeleteComment,
             undo_delete_comment::UndoDeleteComment,
         },
+        flag::Flag,
         following::{accept::Accept, follow::Follow, undo_follow::UndoFollow},
         reject::RejectEdit,
     },
     collections::{
+        CollectionPageParams,
         articles_collection::ArticleCollection,
         edits_collection::EditCollection,
         empty_outbox::EmptyOutbox,
@@ -28,6 +30,7 @@ use crate::{
         instance::InstanceWrapper,
         user::PersonWrapper,
     },
+    search::FederatedSearchParams,
 };
 use activitypub_federation::{
     axum::{
@@ -39,8 +42,9 @@ use activitypub_federation::{
     traits::{Activity, Collection, Object},
 };
 use axum::{
+    Json,
     Router,
-    extract::Path,
+    extract::{Path, Query},
     response::IntoResponse,
     routing::{get, post},
 };
@@ -48,7 +52,7 @@ use axum_macros::debug_handler;
 use either::Either;
 use ibis_database::{
     common::{
-        article::Article,
+        article::{Article, ArticleSearchResult},
         comment::Comment,
         instance::Instance,
         newtypes::CommentId,
@@ -75,6 +79,7 @@ pub fn federation_routes() -> Router<()> {
         .route("/article/{title}", get(http_get_article))
         .route("/article/{title}/edits", get(http_get_article_edits))
         .route("/comment/{id}", get(http_get_comment))
+        .route("/search", get(http_get_search))
         .route("/inbox", post(http_post_inbox))
 }
 
@@ -137,9 +142,17 @@ async fn http_get_person_outbox(
 }
 
 #[debug_handler]
-async fn http_get_all_articles(context: Data<IbisContext>) -> BackendResult<impl IntoResponse> {
-    let collection = ArticleCollection::read_local(&(), &context).await?;
-    Ok(FederationJson(WithContext::new_default(collection)))
+async fn http_get_all_articles(
+    Query(params): Query<CollectionPageParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<impl IntoResponse> {
+    if params.is_page() {
+        let page = ArticleCollection::read_local_page(params.cursor, &context).await?;
+        Ok(FederationJson(WithContext::new_default(page)).into_response())
+    } else {
+        let collection = ArticleCollection::read_local(&(), &context).await?;
+        Ok(FederationJson(WithContext::new_default(collection)).into_response())
+    }
 }
 
 #[debug_handler]
@@ -164,12 +177,19 @@ async fn http_get_article(
 #[debug_handler]
 async fn http_get_article_edits(
     Path(title): Path<String>,
+    Query(params): Query<CollectionPageParams>,
     context: Data<IbisContext>,
 ) -> BackendResult<impl IntoResponse> {
     let title = title.replace("_", " ");
     let article = Article::read_view((&title, None), None, &context)?;
-    let json = EditCollection::read_local(&article.article, &context).await?;
-    Ok(FederationJson(WithContext::new_default(json)))
+    if params.is_page() {
+        let page =
+            EditCollection::read_local_page(&article.article, params.cursor, &context).await?;
+        Ok(FederationJson(WithContext::new_default(page)).into_response())
+    } else {
+        let collection = EditCollection::read_local(&article.article, &context).await?;
+        Ok(FederationJson(WithContext::new_default(collection)).into_response())
+    }
 }
 
 #[debug_handler]
@@ -182,6 +202,28 @@ async fn http_get_comment(
     Ok(FederationJson(WithContext::new_default(json)))
 }
 
+/// Full-text search over local articles, used by other instances for federated search.
+#[debug_handler]
+async fn http_get_search(
+    Query(params): Query<FederatedSearchParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<ArticleSearchResult>>> {
+    let results = Article::search(
+        &params.query,
+        None,
+        true,
+        None,
+        params.limit.clamp(1, 100),
+        &context,
+    )?
+    .items
+    .into_iter()
+    // don't expose local database ids
+    .map(|r| ArticleSearchResult { article: None, ..r })
+    .collect();
+    Ok(Json(results))
+}
+
 /// List of all activities which this actor can receive.
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(untagged)]
@@ -191,6 +233,7 @@ pub enum InboxActivities {
     UndoFollow(UndoFollow),
     Accept(Accept),
     RejectEdit(RejectEdit),
+    Flag(Flag),
     AnnounceActivity(AnnounceActivity),
     AnnouncableActivities(AnnouncableActivities),
 }
@@ -0,0 +1,203 @@
+use futures::future::join_all;
+use ibis_database::{
+    common::{article::ArticleSearchResult, instance::Instance, utils::extract_domain},
+    error::BackendResult,
+    impls::IbisContext,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Maximum time to wait for search results from a single remote instance
+const REMOTE_SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of remote instances which are queried for a single search
+const MAX_SEARCH_INSTANCES: i64 = 50;
+
+/// Parameters for the `/search` federation endpoint which each Ibis instance provides.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FederatedSearchParams {
+    pub query: String,
+    pub limit: i64,
+}
+
+/// Send the search query to known remote instances in parallel. This includes instances which
+/// were discovered through the `linked_instances` collection of other instances. Only the
+/// `MAX_SEARCH_INSTANCES` most recently refreshed instances are queried.
+///
+/// Returns the merged results, and the domains of instances which didn't respond in time.
+/// Instances which respond with an error are skipped. Ranks are scaled so that the best result
+/// of each instance has `max_rank`, see [normalize_ranks].
+pub async fn search_remote_instances(
+    params: &FederatedSearchParams,
+    max_rank: f32,
+    context: &IbisContext,
+) -> BackendResult<(Vec<ArticleSearchResult>, Vec<String>)> {
+    let instances = Instance::list_recently_refreshed(MAX_SEARCH_INSTANCES, context)?;
+    let requests = instances.iter().map(|instance| async move {
+        let res = timeout(
+            REMOTE_SEARCH_TIMEOUT,
+            search_instance(instance, params, context),
+        )
+        .await;
+        (instance, res)
+    });
+
+    let mut results = vec![];
+    let mut timed_out = vec![];
+    for (instance, res) in join_all(requests).await {
+        match res {
+            Ok(Ok(r)) => results.extend(normalize_ranks(r, max_rank)),
+            Ok(Err(e)) => warn!("Failed to search on {}: {e}", instance.domain),
+            Err(_) => timed_out.push(instance.domain.clone()),
+        }
+    }
+    Ok((results, timed_out))
+}
+
+async fn search_instance(
+    instance: &Instance,
+    params: &FederatedSearchParams,
+    context: &IbisContext,
+) -> BackendResult<Vec<ArticleSearchResult>> {
+    let mut url = instance.ap_id.inner().join("search")?;
+    url.query_pairs_mut()
+        .append_pair("query", &params.query)
+        .append_pair("limit", &params.limit.to_string());
+    let results: Vec<ArticleSearchResult> = context
+        .client
+        .get(url)
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    // Only accept articles which are hosted on the instance itself
+    Ok(results
+        .into_iter()
+        .filter(|r| extract_domain(r.ap_id.inner()) == instance.domain)
+        .map(|r| ArticleSearchResult {
+            origin: instance.domain.clone(),
+            article: None,
+            snippet: sanitize_snippet(&r.snippet),
+            ..r
+        })
+        .collect())
+}
+
+/// Remote instances can send arbitrary ranks, which would allow them to place their results
+/// above all others. So the ranks of each instance are scaled relative to its best result,
+/// which gets `max_rank`. Invalid ranks are set to zero.
+fn normalize_ranks(
+    mut results: Vec<ArticleSearchResult>,
+    max_rank: f32,
+) -> Vec<ArticleSearchResult> {
+    let valid = |rank: f32| rank.is_finite() && rank > 0.0;
+    let best = results
+        .iter()
+        .map(|r| r.rank)
+        .filter(|r| valid(*r))
+        .fold(0.0, f32::max);
+    for r in &mut results {
+        r.rank = if valid(r.rank) {
+            r.rank / best * max_rank
+        } else {
+            0.0
+        };
+    }
+    results
+}
+
+/// The snippet is rendered as html, so escape everything which a remote instance sends except
+/// for the `<b>` tags which highlight matches. The text between tags is already escaped by the
+/// remote instance, so entities are decoded first to avoid escaping them twice. Tags which were
+/// sent as entities like `&lt;b&gt;` stay escaped.
+fn sanitize_snippet(snippet: &str) -> String {
+    let mut sanitized = String::with_capacity(snippet.len());
+    let mut rest = snippet;
+    loop {
+        let next_tag = ["<b>", "</b>"]
+            .into_iter()
+            .filter_map(|tag| rest.find(tag).map(|i| (i, tag)))
+            .min();
+        let Some((i, tag)) = next_tag else {
+            sanitized.push_str(&escape_text(rest));
+            return sanitized;
+        };
+        sanitized.push_str(&escape_text(&rest[..i]));
+        sanitized.push_str(tag);
+        rest = &rest[i + tag.len()..];
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    let decoded = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let mut escaped = String::with_capacity(decoded.len());
+    for c in decoded.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use url::Url;
+
+    #[test]
+    fn test_normalize_ranks() -> BackendResult<()> {
+        let ap_id = Url::parse("https://example.com/article/Manu_Chao")?;
+        let result = |rank| ArticleSearchResult {
+            title: "Manu Chao".to_string(),
+            ap_id: ap_id.clone().into(),
+            origin: "example.com".to_string(),
+            snippet: String::new(),
+            rank,
+            article: None,
+        };
+        let results = vec![
+            result(1e30),
+            result(5e29),
+            result(f32::INFINITY),
+            result(f32::NAN),
+            result(-1.0),
+        ];
+        let ranks: Vec<_> = normalize_ranks(results, 0.6)
+            .into_iter()
+            .map(|r| r.rank)
+            .collect();
+        assert_eq!(vec![0.6, 0.3, 0.0, 0.0, 0.0], ranks);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize_snippet() {
+        assert_eq!(
+            "a <b>match</b> &lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;",
+            sanitize_snippet(r#"a <b>match</b> <script>alert("x")</script>"#)
+        );
+        // already escaped text is not escaped twice, and escaped tags are not turned into tags
+        assert_eq!(
+            "&lt;b onclick=&quot;x&quot;&gt; a &amp; b &lt;script&gt;",
+            sanitize_snippet(r#"<b onclick="x"> a &amp; b &lt;script&gt;"#)
+        );
+        assert_eq!(
+            "&lt;b&gt;bold&lt;/b&gt; <b>match</b>",
+            sanitize_snippet("&lt;b&gt;bold&lt;/b&gt; <b>match</b>")
+        );
+    }
+}
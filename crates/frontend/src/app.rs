@@ -1,7 +1,10 @@
 use crate::pages::{
     article::{
         actions::ArticleActions,
+        backlinks::ArticleBacklinks,
+        blame::ArticleBlame,
         comment_redirect::CommentRedirect,
+        compare::CompareVersions,
         create::CreateArticle,
         diff::EditDiff,
         discussion::ArticleDiscussion,
@@ -11,10 +14,20 @@ use crate::pages::{
     },
     instance::{
         about::About,
+        bans::BanList,
+        delivery::DeliveryStatus,
         details::InstanceDetails,
         explore::Explore,
+        federation::FederationList,
+        media::AdminMedia,
+        modlog::Modlog,
+        orphaned::OrphanedArticles,
+        recent_changes::RecentChanges,
+        reports::ReportQueue,
+        review::ReviewQueue,
         search::Search,
         settings::AdminSettings,
+        wanted::WantedArticles,
     },
     user::{
         edit_profile::UserEditProfile,
@@ -79,6 +92,12 @@ pub fn App() -> impl IntoView {
             <>
                 <Stylesheet id="ibis" href="/pkg/ibis.css" />
                 <Stylesheet id="katex" href="/katex.min.css" />
+                <Link
+                    rel="alternate"
+                    type_="application/atom+xml"
+                    href="/feed/atom/changes"
+                />
+                <Link rel="alternate" type_="application/rss+xml" href="/feed/rss/changes" />
                 <Router>
                     <Nav />
                     <main class="p-4 md:ml-64">
@@ -109,6 +128,11 @@ pub fn App() -> impl IntoView {
                                 view=ArticleDiscussion
                             />
                             <Route path=path!("/article/:title/history") view=ArticleHistory />
+                            <Route path=path!("/article/:title/blame") view=ArticleBlame />
+                            <Route
+                                path=path!("/article/:title/backlinks")
+                                view=ArticleBacklinks
+                            />
                             <IbisProtectedRoute
                                 path=path!("/article/:title/edit")
                                 view=EditArticle
@@ -118,14 +142,28 @@ pub fn App() -> impl IntoView {
                                 view=ArticleActions
                             />
                             <Route path=path!("/article/:title/diff/:hash") view=EditDiff />
+                            <Route
+                                path=path!("/article/:title/compare")
+                                view=CompareVersions
+                            />
                             <Route path=path!("/comment/:id") view=CommentRedirect />
                             <IbisProtectedRoute path=path!("/create-article") view=CreateArticle />
 
                             <Route path=path!("/explore") view=Explore />
                             <Route path=path!("/instance/:hostname") view=InstanceDetails />
                             <IbisProtectedRoute path=path!("/admin") view=AdminSettings />
+                            <IbisProtectedRoute path=path!("/admin/media") view=AdminMedia />
+                            <IbisProtectedRoute path=path!("/admin/review") view=ReviewQueue />
+                            <IbisProtectedRoute path=path!("/admin/bans") view=BanList />
+                            <IbisProtectedRoute path=path!("/admin/delivery") view=DeliveryStatus />
+                            <IbisProtectedRoute path=path!("/admin/reports") view=ReportQueue />
+                            <Route path=path!("/modlog") view=Modlog />
+                            <Route path=path!("/recent-changes") view=RecentChanges />
+                            <Route path=path!("/federation") view=FederationList />
                             <Route path=path!("/about") view=About />
                             <Route path=path!("/search") view=Search />
+                            <Route path=path!("/wanted-articles") view=WantedArticles />
+                            <Route path=path!("/orphaned-articles") view=OrphanedArticles />
 
                             <Route path=path!("/user/:name") view=UserProfile />
                             <Route path=path!("/login") view=Login />
@@ -1,14 +1,24 @@
 use crate::pages::article_resource;
 use ibis_api_client::{
     CLIENT,
-    article::{ForkArticleParams, ProtectArticleParams},
+    article::{
+        ForkArticleParams,
+        MoveArticleParams,
+        ProtectArticleParams,
+        RemoveArticleParams,
+        RequireReviewParams,
+    },
     errors::FrontendResultExt,
 };
-use ibis_database::common::{article::Article, newtypes::ArticleId};
+use ibis_database::common::{
+    article::{Article, can_edit_article},
+    newtypes::ArticleId,
+};
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
+    report_button::ReportButton,
     suspense_error::SuspenseError,
-    utils::{formatting::article_path, resources::is_admin},
+    utils::{formatting::article_path, resources::can_moderate},
 };
 use leptos::{ev::KeyboardEvent, prelude::*};
 use leptos_fluent::tr;
@@ -31,10 +41,26 @@ pub fn ArticleActions() -> impl IntoView {
                 .error_popup(|res| set_fork_response.set(Some(res.article)));
         }
     });
+    let (move_title, set_move_title) = signal(String::new());
+    let move_action = Action::new(move |(article_id, new_title): &(ArticleId, String)| {
+        let params = MoveArticleParams {
+            article_id: *article_id,
+            new_title: new_title.clone(),
+        };
+        async move {
+            CLIENT
+                .move_article(&params)
+                .await
+                .error_popup(|res| set_fork_response.set(Some(res.article)));
+        }
+    });
+    // optional reason for moderation actions, shown in the modlog
+    let (reason, set_reason) = signal(String::new());
     let protect_action = Action::new(move |(id, protected): &(ArticleId, bool)| {
         let params = ProtectArticleParams {
             article_id: *id,
             protected: !protected,
+            reason: Some(reason.get_untracked()),
         };
         async move {
             CLIENT
@@ -43,11 +69,28 @@ pub fn ArticleActions() -> impl IntoView {
                 .error_popup(|_| article.refetch());
         }
     });
+    let review_action = Action::new(move |(id, review_edits): &(ArticleId, bool)| {
+        let params = RequireReviewParams {
+            article_id: *id,
+            require_review: !review_edits,
+            reason: Some(reason.get_untracked()),
+        };
+        async move {
+            CLIENT
+                .require_review(&params)
+                .await
+                .error_popup(|_| article.refetch());
+        }
+    });
     let remove_action = Action::new(move |(id, removed): &(ArticleId, bool)| {
-        let (id, removed) = (*id, *removed);
+        let params = RemoveArticleParams {
+            article_id: *id,
+            remove: !removed,
+            reason: Some(reason.get_untracked()),
+        };
         async move {
             CLIENT
-                .remove_article(id, !removed)
+                .remove_article(&params)
                 .await
                 .error_popup(|_| article.refetch());
         }
@@ -59,9 +102,19 @@ pub fn ArticleActions() -> impl IntoView {
                 article
                     .await
                     .map(|article| {
+                        let article_ = article.article.clone();
                         view! {
                             <div>
-                                <Show when=move || { is_admin() && article.article.local }>
+                                <Show when=move || { can_moderate() && article.article.local }>
+                                    <div class="m-4">
+                                        <input
+                                            class="w-80 input input-bordered"
+                                            placeholder=tr!("moderation-reason")
+                                            on:keyup=move |ev: KeyboardEvent| {
+                                                set_reason.set(event_target_value(&ev));
+                                            }
+                                        />
+                                    </div>
                                     <div class="m-4">
                                         <button
                                             class="btn btn-secondary"
@@ -74,6 +127,18 @@ pub fn ArticleActions() -> impl IntoView {
                                             {tr!("toggle-article-protection")}
                                         </button>
                                     </div>
+                                    <div class="m-4">
+                                        <button
+                                            class="btn btn-secondary"
+                                            title=tr!("toggle-review-title")
+                                            on:click=move |_| {
+                                                review_action
+                                                    .dispatch((article.article.id, article.article.review_edits));
+                                            }
+                                        >
+                                            {tr!("toggle-review")}
+                                        </button>
+                                    </div>
                                     <div class="m-4">
                                         <button
                                             class="btn btn-secondary"
@@ -86,6 +151,28 @@ pub fn ArticleActions() -> impl IntoView {
                                         </button>
                                     </div>
                                 </Show>
+                                <Show when=move || {
+                                    article_.local && can_edit_article(&article_, can_moderate()).is_ok()
+                                }>
+                                    <input
+                                        class="input"
+                                        placeholder={tr!("new-title")}
+                                        on:keyup=move |ev: KeyboardEvent| {
+                                            let val = event_target_value(&ev);
+                                            set_move_title.update(|v| *v = val);
+                                        }
+                                    />
+                                    <button
+                                        class="btn"
+                                        disabled=move || move_title.get().is_empty()
+                                        on:click=move |_| {
+                                            move_action.dispatch((article.article.id, move_title.get()));
+                                        }
+                                    >
+                                        {tr!("move-article")}
+                                    </button>
+                                    <p>{tr!("move-article-description")}</p>
+                                </Show>
                                 <input
                                     class="input"
                                     placeholder={tr!("new-title")}
@@ -105,6 +192,10 @@ pub fn ArticleActions() -> impl IntoView {
                                     {tr!("fork-article")}
                                 </button>
                                 <p>{tr!("fork-article-description")}</p>
+                                <div class="m-4">
+                                    {tr!("report-article")} " "
+                                    <ReportButton article_id=article.article.id />
+                                </div>
                             </div>
                         }
                     })
@@ -0,0 +1,53 @@
+use crate::pages::article_resource;
+use ibis_api_client::{CLIENT, article::GetBacklinksParams};
+use ibis_frontend_components::{
+    article_nav::{ActiveTab, ArticleNav},
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::formatting::linked_article_link,
+};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+
+#[component]
+pub fn ArticleBacklinks() -> impl IntoView {
+    let article = article_resource();
+    let backlinks = Resource::new(
+        move || (article.get(), cursor_param()),
+        move |(_, cursor)| async move {
+            let params = GetBacklinksParams {
+                article_id: article.await?.article.id,
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.get_backlinks(&params).await
+        },
+    );
+
+    view! {
+        <ArticleNav article=article active_tab=ActiveTab::Backlinks />
+        <SuspenseError result=article>
+            {move || Suspend::new(async move {
+                backlinks
+                    .await
+                    .map(|backlinks| {
+                        let is_empty = backlinks.items.is_empty();
+                        let next_page = backlinks.next_page;
+                        view! {
+                            <Show when=move || is_empty>
+                                <p>{tr!("no-backlinks")}</p>
+                            </Show>
+                            <ul class="list-disc">
+                                {backlinks
+                                    .items
+                                    .iter()
+                                    .map(|a| view! { <li class="m-2">{linked_article_link(a)}</li> })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                            <NextPageLink next_page />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
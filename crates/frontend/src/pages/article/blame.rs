@@ -0,0 +1,71 @@
+use crate::pages::article_resource;
+use ibis_api_client::CLIENT;
+use ibis_database::common::article::{ArticleBlame, EditVersion};
+use ibis_frontend_components::{
+    article_nav::{ActiveTab, ArticleNav},
+    suspense_error::SuspenseError,
+    utils::formatting::{edit_path, edit_time, user_link},
+};
+use leptos::{either::Either, prelude::*};
+
+#[component]
+pub fn ArticleBlame() -> impl IntoView {
+    let article = article_resource();
+    let blame = Resource::new(
+        move || article.get(),
+        move |_| async move {
+            let id = article.await.map(|a| a.article.id);
+            CLIENT.get_article_blame(id?).await
+        },
+    );
+
+    view! {
+        <ArticleNav article=article active_tab=ActiveTab::Blame />
+        <SuspenseError result=article>
+            {move || Suspend::new(async move {
+                blame.await.map(|blame| view! { <BlameTable blame /> })
+            })}
+        </SuspenseError>
+    }
+}
+
+#[component]
+fn BlameTable(blame: ArticleBlame) -> impl IntoView {
+    let mut previous: Option<EditVersion> = None;
+    let rows = blame
+        .lines
+        .into_iter()
+        .map(|line| {
+            // Only show edit details for the first of consecutive lines from the same edit
+            let first = previous.as_ref() != Some(&line.edit);
+            previous = Some(line.edit.clone());
+            let edit = blame
+                .edits
+                .iter()
+                .find(|e| e.edit.hash == line.edit)
+                .filter(|_| first);
+            let edit_cell = match edit {
+                Some(edit) => Either::Left(view! {
+                    <a class="link link-primary" href=edit_path(&edit.edit, &edit.article)>
+                        {edit.edit.summary.clone()}
+                    </a>
+                    <div class="text-xs">
+                        {edit_time(edit.edit.published)} " by " {user_link(&edit.creator)}
+                    </div>
+                }),
+                None => Either::Right(()),
+            };
+            view! {
+                <tr class:border-t=first class="border-base-300">
+                    <td class="w-64 align-top">{edit_cell}</td>
+                    <td class="font-mono whitespace-pre-wrap">{line.text}</td>
+                </tr>
+            }
+        })
+        .collect::<Vec<_>>();
+    view! {
+        <table class="table table-xs">
+            <tbody>{rows}</tbody>
+        </table>
+    }
+}
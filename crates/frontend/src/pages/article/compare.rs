@@ -0,0 +1,149 @@
+use crate::pages::{article_edits_resource, article_resource};
+use ibis_api_client::{CLIENT, article::CompareVersionsParams, errors::FrontendError};
+use ibis_database::common::article::{ArticleDiff, DiffChangeKind, DiffGranularity, EditView};
+use ibis_frontend_components::{
+    article_nav::{ActiveTab, ArticleNav},
+    suspense_error::SuspenseError,
+    utils::formatting::{article_path, edit_path, edit_time, user_link},
+};
+use leptos::{
+    either::{Either, EitherOf3},
+    prelude::*,
+};
+use leptos_fluent::tr;
+use leptos_router::{hooks::use_query_map, params::ParamsMap};
+
+/// Compare two versions of an article, given as `from` and `to` query params.
+#[component]
+pub fn CompareVersions() -> impl IntoView {
+    let article = article_resource();
+    let edits = article_edits_resource(article);
+    let query = use_query_map();
+    let granularity = move || match query.read().get("granularity").as_deref() {
+        Some("word") => DiffGranularity::Word,
+        Some("rendered") => DiffGranularity::Rendered,
+        _ => DiffGranularity::Line,
+    };
+    let diff = Resource::new(
+        move || (article.get(), query.get()),
+        move |_| async move {
+            let edits = edits.await?;
+            let (from, to) = selected_edits(&edits, &query.read_untracked())
+                .ok_or(FrontendError::new(tr!("invalid-edit")))?;
+            let params = CompareVersionsParams {
+                article_id: to.edit.article_id,
+                from: from.edit.hash,
+                to: to.edit.hash,
+                granularity: granularity(),
+            };
+            CLIENT.compare_versions(&params).await
+        },
+    );
+
+    view! {
+        <ArticleNav article=article active_tab=ActiveTab::History />
+        <SuspenseError result=article>
+            {move || Suspend::new(async move {
+                let article_ = article.await?;
+                let edits = edits.await?;
+                let Some((from, to)) = selected_edits(&edits, &query.read()) else {
+                    return Ok::<_, FrontendError>(
+                        Either::Right(
+                            view! {
+                                <div class="alert alert-error w-fit">{tr!("invalid-edit")}</div>
+                            },
+                        ),
+                    );
+                };
+                let compare_path = format!(
+                    "{}/compare?from={}&to={}",
+                    article_path(&article_.article),
+                    from.edit.hash.0,
+                    to.edit.hash.0,
+                );
+                let tab = move |value: DiffGranularity, name: &'static str, label: String| {
+                    view! {
+                        <a
+                            role="tab"
+                            class="tab"
+                            class:tab-active=move || granularity() == value
+                            href=format!("{compare_path}&granularity={name}")
+                        >
+                            {label}
+                        </a>
+                    }
+                };
+                Ok(
+                    Either::Left(
+                        view! {
+                            <div class="flex gap-4 my-2">
+                                <EditSummary edit=from />
+                                <span class="text-xl">"→"</span>
+                                <EditSummary edit=to />
+                            </div>
+                            <div role="tablist" class="my-4 tabs tabs-border">
+                                {tab(DiffGranularity::Line, "line", tr!("compare-lines"))}
+                                {tab(DiffGranularity::Word, "word", tr!("compare-words"))}
+                                {tab(DiffGranularity::Rendered, "rendered", tr!("compare-rendered"))}
+                            </div>
+                            <SuspenseError result=diff>
+                                {move || Suspend::new(async move {
+                                    diff.await.map(|diff| view! { <DiffView diff /> })
+                                })}
+                            </SuspenseError>
+                        },
+                    ),
+                )
+            })}
+        </SuspenseError>
+    }
+}
+
+/// Find the edits for `from` and `to` query params.
+fn selected_edits(edits: &[EditView], query: &ParamsMap) -> Option<(EditView, EditView)> {
+    let find = |key: &str| {
+        let hash = query.get(key)?;
+        edits
+            .iter()
+            .find(|e| e.edit.hash.0.to_string() == hash)
+            .cloned()
+    };
+    Some((find("from")?, find("to")?))
+}
+
+#[component]
+fn EditSummary(edit: EditView) -> impl IntoView {
+    view! {
+        <div class="flex-1">
+            <a class="text-lg link link-primary" href=edit_path(&edit.edit, &edit.article)>
+                {edit.edit.summary.clone()}
+            </a>
+            <p>{edit_time(edit.edit.published)} " by " {user_link(&edit.creator)}</p>
+        </div>
+    }
+}
+
+#[component]
+fn DiffView(diff: ArticleDiff) -> impl IntoView {
+    match diff.html {
+        Some(html) => Either::Left(
+            view! { <div class="max-w-full diff prose prose-slate" inner_html=html /> },
+        ),
+        None => {
+            let chunks = diff
+                .chunks
+                .into_iter()
+                .map(|chunk| match chunk.kind {
+                    DiffChangeKind::Equal => EitherOf3::A(view! { <span>{chunk.text}</span> }),
+                    DiffChangeKind::Insert => EitherOf3::B(view! { <ins>{chunk.text}</ins> }),
+                    DiffChangeKind::Delete => EitherOf3::C(view! { <del>{chunk.text}</del> }),
+                })
+                .collect::<Vec<_>>();
+            Either::Right(view! {
+                <pre class="whitespace-pre-wrap diff">
+                    <code>{chunks}</code>
+                </pre>
+            })
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use ibis_api_client::{
     CLIENT,
     article::{CreateArticleParams, ImportArticleParams},
+    instance::ListInstancesParams,
 };
 use ibis_database::common::{article::ArticleView, newtypes::InstanceId};
 use ibis_frontend_components::{
@@ -86,7 +87,16 @@ pub fn CreateArticle() -> impl IntoView {
             }
         }
     });
-    let instances = Resource::new(move || (), |_| async move { CLIENT.list_instances().await });
+    let instances = Resource::new(
+        || (),
+        |_| async move {
+            let params = ListInstancesParams {
+                limit: Some(100),
+                ..Default::default()
+            };
+            CLIENT.list_instances(&params).await.map(|i| i.items)
+        },
+    );
 
     view! {
         <IbisTitle key="create-article" />
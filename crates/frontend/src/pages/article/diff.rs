@@ -1,26 +1,52 @@
 use crate::pages::{article_edits_resource, article_resource};
+use ibis_api_client::{CLIENT, article::RevertArticleParams, errors::FrontendResultExt};
+use ibis_database::common::article::{ArticleView, EditVersion, can_edit_article};
 use ibis_frontend_components::{
     Pending,
     article_nav::{ActiveTab, ArticleNav},
+    report_button::ReportButton,
     suspense_error::SuspenseError,
-    utils::formatting::{edit_time, user_link},
+    utils::{
+        formatting::{article_path, edit_time, user_link},
+        resources::{can_moderate, is_logged_in},
+    },
 };
 use leptos::{either::Either, prelude::*};
 use leptos_fluent::tr;
 use leptos_meta::Title;
-use leptos_router::hooks::use_params_map;
+use leptos_router::{components::Redirect, hooks::use_params_map};
 
 #[component]
 pub fn EditDiff() -> impl IntoView {
     let params = use_params_map();
     let article = article_resource();
     let edits = article_edits_resource(article);
+    let (redirect, set_redirect) = signal(Option::<String>::None);
+    let revert_action = Action::new(move |(article, version): &(ArticleView, EditVersion)| {
+        let params = RevertArticleParams {
+            article_id: article.article.id,
+            version: version.clone(),
+            reason: None,
+        };
+        let path = article_path(&article.article);
+        async move {
+            CLIENT.revert_article(&params).await.error_popup(|res| {
+                // On conflict, let the user resolve it in the editor
+                let path = match res {
+                    Some(conflict) => format!("{path}/edit?conflict_id={}", conflict.id.0),
+                    None => path,
+                };
+                set_redirect.set(Some(path));
+            });
+        }
+    });
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
         <SuspenseError result=article>
             {move || Suspend::new(async move {
-                let article_title = article.await.map(|a| a.article.title()).unwrap_or_default();
+                let article_ = article.await?;
+                let article_title = article_.article.title();
                 edits
                     .await
                     .map(|edits| {
@@ -28,6 +54,12 @@ pub fn EditDiff() -> impl IntoView {
                         let edit = edits.iter().find(|e| Some(e.edit.hash.0.to_string()) == hash);
                         if let Some(edit) = edit {
                             let pending = edit.edit.pending;
+                            let version = edit.edit.hash.clone();
+                            let article_ = article_.clone();
+                            let article_id = article_.article.id;
+                            let can_revert = !pending
+                                && can_moderate()
+                                && can_edit_article(&article_.article, true).is_ok();
                             let title = format!(
                                 "Diff {} — {}",
                                 &edit.edit.summary,
@@ -42,8 +74,30 @@ pub fn EditDiff() -> impl IntoView {
                                             {edit_time(edit.edit.published)} ")"
                                         </h2>
                                         <Pending pending />
+                                        <Show when=move || is_logged_in() && can_revert>
+                                            <button
+                                                class="btn btn-secondary btn-sm"
+                                                title=tr!("revert-to-version-title")
+                                                on:click={
+                                                    let article_ = article_.clone();
+                                                    let version = version.clone();
+                                                    move |_| {
+                                                        revert_action
+                                                            .dispatch((article_.clone(), version.clone()));
+                                                    }
+                                                }
+                                            >
+                                                {tr!("revert-to-version")}
+                                            </button>
+                                        </Show>
                                     </div>
-                                    <p>"by " {user_link(&edit.creator)}</p>
+                                    <p>
+                                        "by " {user_link(&edit.creator)} " "
+                                        <ReportButton
+                                            article_id=article_id
+                                            edit_id=edit.edit.id
+                                        />
+                                    </p>
                                     <div class="max-w-full prose prose-slate">
                                         <pre class="text-wrap">
                                             <code>{edit.edit.diff.clone()}</code>
@@ -62,7 +116,7 @@ pub fn EditDiff() -> impl IntoView {
                         }
                     })
             })}
-
+            {move || redirect.get().map(|path| view! { <Redirect path /> })}
         </SuspenseError>
     }
 }
@@ -1,10 +1,18 @@
 use crate::pages::article_resource;
-use ibis_database::common::{comment::CommentView, newtypes::CommentId};
+use ibis_api_client::{CLIENT, comment::ListCommentsParams};
+use ibis_database::common::{
+    comment::CommentView,
+    newtypes::CommentId,
+    pagination::PaginatedResponse,
+};
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
     comment::CommentView,
     comment_editor::CommentEditorView,
+    feed_links::FeedLinks,
+    pagination::{NextPageLink, cursor_param},
     suspense_error::SuspenseError,
+    utils::formatting::article_path,
 };
 use leptos::prelude::*;
 use std::collections::HashMap;
@@ -12,6 +20,27 @@ use std::collections::HashMap;
 #[component]
 pub fn ArticleDiscussion() -> impl IntoView {
     let article = article_resource();
+    // Newest comments are included in the article, older ones are read separately
+    let comments = Resource::new(
+        move || (article.get(), cursor_param()),
+        move |(_, cursor)| async move {
+            let article = article.await?;
+            match cursor {
+                Some(cursor) => {
+                    let params = ListCommentsParams {
+                        article_id: article.article.id,
+                        cursor: Some(cursor),
+                        limit: None,
+                    };
+                    CLIENT.list_comments(&params).await
+                }
+                None => Ok(PaginatedResponse {
+                    items: article.comments,
+                    next_page: article.comments_next_page,
+                }),
+            }
+        },
+    );
 
     let show_editor = signal(CommentId(-1));
 
@@ -19,15 +48,21 @@ pub fn ArticleDiscussion() -> impl IntoView {
         <ArticleNav article=article active_tab=ActiveTab::Discussion />
         <SuspenseError result=article>
             {move || Suspend::new(async move {
-                let article2 = article.await;
+                let feed_path = article
+                    .await
+                    .ok()
+                    .map(|a| format!("{}/comments", article_path(&a.article)));
+                let comments = comments.await;
+                let next_page = comments.as_ref().ok().and_then(|c| c.next_page.clone());
                 view! {
                     <CommentEditorView article=article />
+                    {feed_path.map(|path| view! { <FeedLinks path /> })}
                     <div>
                         <For
                             each=move || {
-                                article2
+                                comments
                                     .clone()
-                                    .map(|a| build_comments_tree(a.comments))
+                                    .map(|c| build_comments_tree(c.items))
                                     .unwrap_or_default()
                             }
                             key=|comment| comment.comment.id
@@ -36,6 +71,7 @@ pub fn ArticleDiscussion() -> impl IntoView {
                             }
                         />
                     </div>
+                    <NextPageLink next_page />
                 }
             })}
         </SuspenseError>
@@ -81,8 +117,12 @@ fn build_comments_tree(comments: Vec<CommentView>) -> Vec<CommentView> {
             .get(&view.comment.id)
             .expect("get comment by id")
             .clone();
-        if let Some(parent_id) = &view.comment.parent_id {
-            let parent = map.get_mut(parent_id).expect("get parent comment by id");
+        // The parent may be missing if it is on another page, then show the reply at the top level
+        let parent = view
+            .comment
+            .parent_id
+            .and_then(|parent_id| map.get_mut(&parent_id));
+        if let Some(parent) = parent {
             parent.children.push(child);
         } else {
             tree.push(child);
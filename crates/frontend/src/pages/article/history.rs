@@ -1,26 +1,65 @@
 use crate::pages::{article_edits_resource, article_resource};
+use ibis_database::common::article::EditVersion;
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
     edit_list::EditList,
+    feed_links::FeedLinks,
     suspense_error::SuspenseError,
+    utils::formatting::article_path,
 };
 use leptos::prelude::*;
+use leptos_fluent::tr;
 
 #[component]
 pub fn ArticleHistory() -> impl IntoView {
     let article = article_resource();
     let edits = article_edits_resource(article);
+    let compare = RwSignal::new(Vec::<EditVersion>::new());
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
         <SuspenseError result=article>
             {move || Suspend::new(async move {
+                let article_ = article.await?;
+                let feed_path = article_path(&article_.article);
                 edits
                     .await
                     .map(|edits| {
+                        // Link to compare selected versions, with older version first
+                        let compare_path = {
+                            let edits = edits.clone();
+                            move || {
+                                let mut selected: Vec<_> = edits
+                                    .iter()
+                                    .filter(|e| compare.read().contains(&e.edit.hash))
+                                    .collect();
+                                if selected.len() != 2 {
+                                    return None;
+                                }
+                                let to = selected.pop()?;
+                                let from = selected.pop()?;
+                                Some(
+                                    format!(
+                                        "{}/compare?from={}&to={}",
+                                        article_path(&article_.article),
+                                        from.edit.hash.0,
+                                        to.edit.hash.0,
+                                    ),
+                                )
+                            }
+                        };
+                        let compare_path_ = compare_path.clone();
                         view! {
+                            <a
+                                class="m-2 btn btn-sm btn-primary"
+                                class:btn-disabled=move || compare_path_().is_none()
+                                href=move || compare_path().unwrap_or_default()
+                            >
+                                {tr!("compare-selected")}
+                            </a>
+                            <FeedLinks path=feed_path />
                             // TODO: move edits resource here? but leads to strange crash
-                            <EditList edits=edits for_article=true />
+                            <EditList edits=edits for_article=true compare />
                         }
                     })
             })}
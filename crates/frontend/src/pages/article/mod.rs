@@ -1,5 +1,8 @@
 pub mod actions;
+pub mod backlinks;
+pub mod blame;
 pub mod comment_redirect;
+pub mod compare;
 pub mod create;
 pub mod diff;
 pub mod discussion;
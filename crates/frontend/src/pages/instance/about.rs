@@ -20,6 +20,28 @@ pub fn About() -> impl IntoView {
                         view! {
                             <div>{tr!("topic", {"topic" => site.instance.topic})}</div>
                             <div>"Administrated by:" {user_link(&site.admin)}</div>
+                            <ul class="my-4">
+                                <li>
+                                    <a class="link" href="/wanted-articles">
+                                        {tr!("wanted-articles")}
+                                    </a>
+                                </li>
+                                <li>
+                                    <a class="link" href="/orphaned-articles">
+                                        {tr!("orphaned-articles")}
+                                    </a>
+                                </li>
+                                <li>
+                                    <a class="link" href="/modlog">
+                                        {tr!("modlog")}
+                                    </a>
+                                </li>
+                                <li>
+                                    <a class="link" href="/federation">
+                                        {tr!("federation")}
+                                    </a>
+                                </li>
+                            </ul>
                         }
                     })
             })}
@@ -0,0 +1,98 @@
+use ibis_api_client::{
+    CLIENT,
+    errors::FrontendResultExt,
+    user::{ListBansParams, UnbanPersonParams},
+};
+use ibis_database::common::newtypes::PersonId;
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{
+        formatting::{edit_time, user_link},
+        i18n::IbisTitle,
+    },
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Users which are currently banned from this instance.
+#[component]
+pub fn BanList() -> impl IntoView {
+    let bans = Resource::new(cursor_param, |cursor| async move {
+        let params = ListBansParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_bans(&params).await
+    });
+
+    let unban_action = Action::new(move |person_id: &PersonId| {
+        let params = UnbanPersonParams {
+            person_id: *person_id,
+            reason: None,
+        };
+        async move {
+            CLIENT
+                .unban_person(&params)
+                .await
+                .error_popup(|_| bans.refetch());
+        }
+    });
+
+    view! {
+        <IbisTitle key="banned-users" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("banned-users")}</h1>
+        <SuspenseError result=bans>
+            {move || Suspend::new(async move {
+                bans.await
+                    .map(|bans| {
+                        if bans.items.is_empty() {
+                            return Either::Left(view! { <p>{tr!("no-banned-users")}</p> });
+                        }
+                        let next_page = bans.next_page;
+                        Either::Right(view! {
+                            {bans
+                                .items
+                                .into_iter()
+                                .map(|ban| {
+                                    let person_id = ban.person.id;
+                                    view! {
+                                        <div class="my-4 card card-compact bg-base-100 card-bordered">
+                                            <div class="card-body">
+                                                <div class="flex w-full">
+                                                    <h2 class="text-lg grow">{user_link(&ban.person)}</h2>
+                                                    <button
+                                                        class="btn btn-sm"
+                                                        on:click=move |_| {
+                                                            unban_action.dispatch(person_id);
+                                                        }
+                                                    >
+                                                        {tr!("unban")}
+                                                    </button>
+                                                </div>
+                                                <p>{ban.ban.reason.clone()}</p>
+                                                <p>
+                                                    {edit_time(ban.ban.published)} " "
+                                                    {tr!("banned-by")} " "
+                                                    {user_link(&ban.moderator)} ", "
+                                                    {match ban.ban.expires {
+                                                        Some(e) => {
+                                                            Either::Left(
+                                                                view! { {tr!("until")} " " {edit_time(e)} },
+                                                            )
+                                                        }
+                                                        None => Either::Right(tr!("permanent")),
+                                                    }}
+                                                </p>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                            <NextPageLink next_page />
+                        })
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -0,0 +1,87 @@
+use ibis_api_client::{CLIENT, instance::ListDeliveryStatusParams};
+use ibis_database::common::delivery::DeliveryStatusView;
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{formatting::edit_time, i18n::IbisTitle},
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Delivery of outgoing activities to remote instances, for admins to spot unreachable
+/// instances.
+#[component]
+pub fn DeliveryStatus() -> impl IntoView {
+    let statuses = Resource::new(cursor_param, |cursor| async move {
+        let params = ListDeliveryStatusParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_delivery_status(&params).await
+    });
+
+    view! {
+        <IbisTitle key="delivery-status" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("delivery-status")}</h1>
+        <SuspenseError result=statuses>
+            {move || Suspend::new(async move {
+                statuses
+                    .await
+                    .map(|statuses| {
+                        if statuses.items.is_empty() {
+                            return Either::Left(
+                                view! { <p class="my-4">{tr!("delivery-status-empty")}</p> },
+                            );
+                        }
+                        let next_page = statuses.next_page;
+                        Either::Right(
+                            view! {
+                                <table class="table my-4">
+                                    <thead>
+                                        <tr>
+                                            <th>{tr!("domain")}</th>
+                                            <th>{tr!("delivery-queue-depth")}</th>
+                                            <th>{tr!("delivery-fail-count")}</th>
+                                            <th>{tr!("delivery-last-success")}</th>
+                                            <th>{tr!("delivery-last-error")}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {statuses
+                                            .items
+                                            .into_iter()
+                                            .map(status_row)
+                                            .collect::<Vec<_>>()}
+                                    </tbody>
+                                </table>
+                                <NextPageLink next_page />
+                            },
+                        )
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn status_row(view: DeliveryStatusView) -> impl IntoView {
+    let status = view.status;
+    view! {
+        <tr>
+            <td>
+                {status.domain}
+                <Show when=move || view.dead>
+                    <span class="ml-2 badge badge-error">{tr!("delivery-dead")}</span>
+                </Show>
+            </td>
+            <td>{view.queue_depth}</td>
+            <td>{status.fail_count}</td>
+            <td>
+                {match status.last_success {
+                    Some(s) => Either::Left(edit_time(s)),
+                    None => Either::Right(tr!("never")),
+                }}
+            </td>
+            <td class="break-all">{status.last_error}</td>
+        </tr>
+    }
+}
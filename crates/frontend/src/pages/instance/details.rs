@@ -6,6 +6,7 @@ use ibis_api_client::{
 };
 use ibis_frontend_components::{
     instance_follow_button::InstanceFollowButton,
+    pagination::{NextPageLink, cursor_param},
     suspense_error::SuspenseError,
     utils::formatting::{article_path, instance_title_with_domain, instance_updated},
 };
@@ -34,13 +35,15 @@ pub fn InstanceDetails() -> impl IntoView {
                     .await
                     .map(|instance_| {
                         let articles = Resource::new(
-                            move || instance_.instance.id,
-                            |instance_id| async move {
+                            move || (instance_.instance.id, cursor_param()),
+                            |(instance_id, cursor)| async move {
                                 CLIENT
                                     .list_articles(ListArticlesParams {
                                         only_local: None,
                                         instance_id: Some(instance_id),
                                         include_removed: None,
+                                        cursor,
+                                        limit: None,
                                     })
                                     .await
                             },
@@ -79,17 +82,23 @@ pub fn InstanceDetails() -> impl IntoView {
                                             articles
                                                 .await
                                                 .map(|a| {
-                                                    a.into_iter()
-                                                        .map(|a| {
-                                                            view! {
-                                                                <li>
-                                                                    <a class="text-lg link" href=article_path(&a)>
-                                                                        {a.title()}
-                                                                    </a>
-                                                                </li>
-                                                            }
-                                                        })
-                                                        .collect::<Vec<_>>()
+                                                    let next_page = a.next_page;
+                                                    view! {
+                                                        {a
+                                                            .items
+                                                            .into_iter()
+                                                            .map(|a| {
+                                                                view! {
+                                                                    <li>
+                                                                        <a class="text-lg link" href=article_path(&a)>
+                                                                            {a.title()}
+                                                                        </a>
+                                                                    </li>
+                                                                }
+                                                            })
+                                                            .collect::<Vec<_>>()}
+                                                        <NextPageLink next_page />
+                                                    }
                                                 })
                                         })}
                                     </SuspenseError>
@@ -2,9 +2,15 @@ use codee::string::JsonSerdeCodec;
 use ibis_api_client::{
     CLIENT,
     errors::{FrontendResult, FrontendResultExt},
+    instance::ListInstancesParams,
+};
+use ibis_database::common::{
+    article::Article,
+    instance::InstanceView,
+    pagination::PaginatedResponse,
 };
-use ibis_database::common::{article::Article, instance::InstanceView};
 use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
     suspense_error::SuspenseError,
     utils::{
         formatting::{article_link, instance_title_with_domain, instance_updated},
@@ -16,7 +22,13 @@ use url::Url;
 
 #[component]
 pub fn Explore() -> impl IntoView {
-    let instances = Resource::new(move || (), |_| async move { CLIENT.list_instances().await });
+    let instances = Resource::new(cursor_param, |cursor| async move {
+        let params = ListInstancesParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_instances(&params).await
+    });
 
     view! {
         <IbisTitle key="explore" />
@@ -24,17 +36,22 @@ pub fn Explore() -> impl IntoView {
         <SuspenseError result=instances>
             {move || Suspend::new(async move {
                 let instances_ = instances.await;
-                let is_empty = instances_.as_ref().map(|i| i.len() <= 1).unwrap_or(true);
+                let is_empty = instances_
+                    .as_ref()
+                    .map(|i| i.items.len() <= 1 && cursor_param().is_none())
+                    .unwrap_or(true);
+                let next_page = instances_.as_ref().ok().and_then(|i| i.next_page.clone());
                 view! {
                     <ul class="my-4 list-none">
                         {instances_
                             .clone()
                             .ok()
                             .into_iter()
-                            .flatten()
+                            .flat_map(|i| i.items)
                             .map(instance_card)
                             .collect::<Vec<_>>()}
                     </ul>
+                    <NextPageLink next_page />
                     <Show when=move || is_empty>
                         <ConnectView res=instances />
                     </Show>
@@ -72,7 +89,9 @@ pub fn instance_card(i: InstanceView) -> impl IntoView {
 }
 
 #[component]
-fn ConnectView(res: Resource<FrontendResult<Vec<InstanceView>>, JsonSerdeCodec>) -> impl IntoView {
+fn ConnectView(
+    res: Resource<FrontendResult<PaginatedResponse<InstanceView>>, JsonSerdeCodec>,
+) -> impl IntoView {
     let connect_ibis_wiki = Action::new(move |_: &()| async move {
         CLIENT
             .resolve_instance(Url::parse("https://ibis.wiki").expect("parse ibis.wiki url"))
@@ -0,0 +1,230 @@
+use chrono::{Days, Utc};
+use ibis_api_client::{
+    CLIENT,
+    errors::{FrontendError, FrontendResultExt},
+    instance::{
+        BlockedContent,
+        ListFederationParams,
+        RemoveFederationListParams,
+        UpdateFederationListParams,
+    },
+};
+use ibis_database::common::{
+    federation_list::{FederationListEntry, FederationMode},
+    pagination::PaginatedResponse,
+};
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{formatting::edit_time, i18n::IbisTitle, resources::is_admin},
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Instances which are allowed or blocked for federation. The list is public, admins can add and
+/// remove entries.
+#[component]
+pub fn FederationList() -> impl IntoView {
+    let entries = Resource::new(cursor_param, |cursor| async move {
+        let params = ListFederationParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_federation(&params).await
+    });
+
+    let remove_action = Action::new(move |domain: &String| {
+        let params = RemoveFederationListParams {
+            domain: domain.clone(),
+        };
+        async move {
+            CLIENT
+                .remove_federation_list(&params)
+                .await
+                .error_popup(|_| entries.refetch());
+        }
+    });
+
+    view! {
+        <IbisTitle key="federation" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("federation")}</h1>
+        <p>{tr!("federation-description")}</p>
+        <Show when=is_admin>
+            <FederationForm entries />
+        </Show>
+        <SuspenseError result=entries>
+            {move || Suspend::new(async move {
+                entries
+                    .await
+                    .map(|entries| {
+                        if entries.items.is_empty() {
+                            return Either::Left(
+                                view! { <p class="my-4">{tr!("federation-list-empty")}</p> },
+                            );
+                        }
+                        let next_page = entries.next_page;
+                        Either::Right(
+                            view! {
+                                <table class="table my-4">
+                                    <thead>
+                                        <tr>
+                                            <th>{tr!("domain")}</th>
+                                            <th>{tr!("federation-mode")}</th>
+                                            <th>{tr!("reason")}</th>
+                                            <th>{tr!("expires")}</th>
+                                            <Show when=is_admin>
+                                                <th></th>
+                                            </Show>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {entries
+                                            .items
+                                            .into_iter()
+                                            .map(|entry| entry_row(entry, remove_action))
+                                            .collect::<Vec<_>>()}
+                                    </tbody>
+                                </table>
+                                <NextPageLink next_page />
+                            },
+                        )
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn entry_row(entry: FederationListEntry, remove_action: Action<String, ()>) -> impl IntoView {
+    let domain = entry.domain.clone();
+    view! {
+        <tr>
+            <td>{entry.domain}</td>
+            <td>{mode_name(entry.mode)}</td>
+            <td>{entry.reason}</td>
+            <td>
+                {match entry.expires {
+                    Some(e) => Either::Left(edit_time(e)),
+                    None => Either::Right(tr!("permanent")),
+                }}
+            </td>
+            <Show when=is_admin>
+                <td>
+                    <button
+                        class="btn btn-sm"
+                        on:click={
+                            let domain = domain.clone();
+                            move |_| {
+                                remove_action.dispatch(domain.clone());
+                            }
+                        }
+                    >
+                        {tr!("remove")}
+                    </button>
+                </td>
+            </Show>
+        </tr>
+    }
+}
+
+#[component]
+fn FederationForm(
+    entries: Resource<Result<PaginatedResponse<FederationListEntry>, FrontendError>>,
+) -> impl IntoView {
+    let domain = signal(String::new());
+    let reason = signal(String::new());
+    let days = signal(String::new());
+    let mode = signal(FederationMode::Block);
+    let content = signal(BlockedContent::Keep);
+    let submit_action = Action::new(move |params: &UpdateFederationListParams| {
+        let params = params.clone();
+        async move {
+            CLIENT
+                .update_federation_list(&params)
+                .await
+                .error_popup(|_| entries.refetch());
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-2 my-4 w-80">
+            <input
+                type="text"
+                class="input input-sm input-bordered"
+                placeholder=tr!("domain")
+                bind:value=domain
+            />
+            <select
+                class="select select-sm select-bordered"
+                on:change=move |ev| {
+                    if let Ok(m) = event_target_value(&ev).parse() {
+                        mode.1.set(m);
+                    }
+                }
+            >
+                <option value=FederationMode::Block.as_str()>{mode_name(FederationMode::Block)}</option>
+                <option value=FederationMode::Allow.as_str()>{mode_name(FederationMode::Allow)}</option>
+            </select>
+            <input
+                type="text"
+                class="input input-sm input-bordered"
+                placeholder=tr!("reason")
+                bind:value=reason
+            />
+            <input
+                type="number"
+                min="1"
+                class="input input-sm input-bordered"
+                placeholder=tr!("federation-days")
+                bind:value=days
+            />
+            <Show when=move || mode.0.get() == FederationMode::Block>
+                <select
+                    class="select select-sm select-bordered"
+                    on:change=move |ev| {
+                        let value = match event_target_value(&ev).as_str() {
+                            "hide" => BlockedContent::Hide,
+                            "purge" => BlockedContent::Purge,
+                            _ => BlockedContent::Keep,
+                        };
+                        content.1.set(value);
+                    }
+                >
+                    <option value="keep">{tr!("blocked-content-keep")}</option>
+                    <option value="hide">{tr!("blocked-content-hide")}</option>
+                    <option value="purge">{tr!("blocked-content-purge")}</option>
+                </select>
+            </Show>
+            <button
+                class="btn btn-sm btn-primary"
+                disabled=move || {
+                    domain.0.get().trim().is_empty() || reason.0.get().trim().is_empty()
+                }
+                on:click=move |_| {
+                    let expires = days
+                        .0
+                        .get()
+                        .parse()
+                        .ok()
+                        .and_then(|d| Utc::now().checked_add_days(Days::new(d)));
+                    submit_action
+                        .dispatch(UpdateFederationListParams {
+                            domain: domain.0.get(),
+                            mode: mode.0.get(),
+                            reason: reason.0.get(),
+                            expires,
+                            content: Some(content.0.get()),
+                        });
+                }
+            >
+                {tr!("submit")}
+            </button>
+        </div>
+    }
+}
+
+fn mode_name(mode: FederationMode) -> String {
+    match mode {
+        FederationMode::Allow => tr!("federation-allow"),
+        FederationMode::Block => tr!("federation-block"),
+    }
+}
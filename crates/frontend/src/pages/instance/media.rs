@@ -0,0 +1,73 @@
+use ibis_api_client::{CLIENT, errors::FrontendResultExt, media::ListMediaParams};
+use ibis_database::common::newtypes::MediaId;
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::i18n::IbisTitle,
+};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+
+/// Lists media uploaded to this instance, newest first, so that admins can delete it.
+#[component]
+pub fn AdminMedia() -> impl IntoView {
+    let media = Resource::new(cursor_param, |cursor| async move {
+        let params = ListMediaParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_media(&params).await
+    });
+
+    let delete_action = Action::new(move |id: &MediaId| {
+        let id = *id;
+        async move {
+            CLIENT
+                .delete_media(id)
+                .await
+                .error_popup(|_| media.refetch());
+        }
+    });
+
+    view! {
+        <IbisTitle key="uploaded-media" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("uploaded-media")}</h1>
+        <SuspenseError result=media>
+            {move || Suspend::new(async move {
+                media
+                    .await
+                    .map(|media| {
+                        let next_page = media.next_page;
+                        view! {
+                            <div class="flex flex-wrap gap-4">
+                                {media
+                                    .items
+                                    .into_iter()
+                                    .map(|m| {
+                                        let id = m.id;
+                                        view! {
+                                            <div class="flex flex-col items-center w-64">
+                                                <a href=m.path() target="_blank">
+                                                    <img src=m.thumbnail_path() />
+                                                </a>
+                                                <code class="text-xs break-all">{m.path()}</code>
+                                                <button
+                                                    class="mt-2 btn btn-error btn-sm"
+                                                    on:click=move |_| {
+                                                        delete_action.dispatch(id);
+                                                    }
+                                                >
+                                                    {tr!("delete")}
+                                                </button>
+                                            </div>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </div>
+                            <NextPageLink next_page />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
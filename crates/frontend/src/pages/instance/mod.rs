@@ -1,5 +1,15 @@
 pub mod about;
+pub mod bans;
+pub mod delivery;
 pub mod details;
 pub mod explore;
+pub mod federation;
+pub mod media;
+pub mod modlog;
+pub mod orphaned;
+pub mod recent_changes;
+pub mod reports;
+pub mod review;
 pub mod search;
 pub mod settings;
+pub mod wanted;
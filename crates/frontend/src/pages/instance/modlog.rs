@@ -0,0 +1,216 @@
+use ibis_api_client::{CLIENT, modlog::ListModlogParams};
+use ibis_database::common::{
+    modlog::ModAction,
+    newtypes::{ArticleId, PersonId},
+    pagination::PaginationCursor,
+};
+use ibis_frontend_components::{
+    suspense_error::SuspenseError,
+    utils::{
+        formatting::{edit_time, modlog_article_link, user_link},
+        i18n::IbisTitle,
+    },
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+use leptos_router::hooks::{use_navigate, use_query_map};
+
+const MODLOG_LIMIT: i64 = 50;
+
+/// Public log of all moderation actions on this instance. Can be filtered by moderator, article
+/// and action type, which are passed as query parameters.
+#[component]
+pub fn Modlog() -> impl IntoView {
+    let query = use_query_map();
+    let params = move || {
+        let query = query.get();
+        ListModlogParams {
+            moderator_id: query
+                .get("moderator_id")
+                .and_then(|m| m.parse().ok())
+                .map(PersonId),
+            article_id: query
+                .get("article_id")
+                .and_then(|a| a.parse().ok())
+                .map(ArticleId),
+            action: query.get("action").and_then(|a| a.parse().ok()),
+            cursor: query.get("cursor").map(PaginationCursor),
+            limit: Some(MODLOG_LIMIT),
+        }
+    };
+    let entries = Resource::new(
+        params,
+        |params| async move { CLIENT.list_modlog(&params).await },
+    );
+    let navigate = use_navigate();
+    let set_action = move |action: String| {
+        let params = ListModlogParams {
+            action: action.parse().ok(),
+            cursor: None,
+            ..params()
+        };
+        navigate(&modlog_link(&params), Default::default());
+    };
+
+    view! {
+        <IbisTitle key="modlog" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("modlog")}</h1>
+        <div class="flex gap-4 items-center">
+            <select
+                class="select select-sm select-bordered"
+                on:change=move |ev| set_action(event_target_value(&ev))
+            >
+                <option value="" selected=move || params().action.is_none()>
+                    {tr!("modlog-all-actions")}
+                </option>
+                {ModAction::ALL
+                    .into_iter()
+                    .map(|a| {
+                        view! {
+                            <option value=a.as_str() selected=move || params().action == Some(a)>
+                                {action_name(a)}
+                            </option>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </select>
+            <Show when=move || params() != ListModlogParams {
+                limit: Some(MODLOG_LIMIT),
+                ..Default::default()
+            }>
+                <a class="link" href="/modlog">
+                    {tr!("modlog-clear-filters")}
+                </a>
+            </Show>
+        </div>
+        <SuspenseError result=entries>
+            {move || Suspend::new(async move {
+                entries
+                    .await
+                    .map(|entries| {
+                        if entries.items.is_empty() {
+                            return Either::Left(view! { <p class="my-4">{tr!("modlog-empty")}</p> });
+                        }
+                        let next_page_link = entries
+                            .next_page
+                            .map(|cursor| {
+                                modlog_link(
+                                    &ListModlogParams {
+                                        cursor: Some(cursor),
+                                        ..params()
+                                    },
+                                )
+                            });
+                        Either::Right(
+                            view! {
+                                <table class="table my-4">
+                                    <tbody>
+                                        {entries
+                                            .items
+                                            .into_iter()
+                                            .map(|entry| {
+                                                let moderator_filter = modlog_link(
+                                                    &ListModlogParams {
+                                                        moderator_id: Some(entry.moderator.id),
+                                                        ..Default::default()
+                                                    },
+                                                );
+                                                let article_filter = entry
+                                                    .article
+                                                    .as_ref()
+                                                    .map(|a| {
+                                                        modlog_link(
+                                                            &ListModlogParams {
+                                                                article_id: Some(a.id),
+                                                                ..Default::default()
+                                                            },
+                                                        )
+                                                    });
+                                                view! {
+                                                    <tr>
+                                                        <td>{edit_time(entry.modlog.published)}</td>
+                                                        <td>
+                                                            {user_link(&entry.moderator)} " "
+                                                            <a class="link text-xs" href=moderator_filter>
+                                                                "(" {tr!("modlog-filter")} ")"
+                                                            </a>
+                                                        </td>
+                                                        <td>{action_name(entry.modlog.action)}</td>
+                                                        <td>
+                                                            {entry
+                                                                .article
+                                                                .as_ref()
+                                                                .map(|a| {
+                                                                    view! {
+                                                                        {modlog_article_link(a)}
+                                                                        " "
+                                                                        <a class="link text-xs" href=article_filter.clone()>
+                                                                            "(" {tr!("modlog-filter")} ")"
+                                                                        </a>
+                                                                    }
+                                                                })}
+                                                            {entry.target_person.as_ref().map(user_link)}
+                                                            {entry.modlog.target_domain.clone()}
+                                                        </td>
+                                                        <td>{entry.modlog.reason.clone()}</td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </tbody>
+                                </table>
+                                {next_page_link
+                                    .map(|href| {
+                                        view! {
+                                            <a class="link my-4 block" href=href>
+                                                {tr!("next-page")}
+                                            </a>
+                                        }
+                                    })}
+                            },
+                        )
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn modlog_link(params: &ListModlogParams) -> String {
+    let mut query = vec![];
+    if let Some(moderator_id) = params.moderator_id {
+        query.push(format!("moderator_id={}", moderator_id.0));
+    }
+    if let Some(article_id) = params.article_id {
+        query.push(format!("article_id={}", article_id.0));
+    }
+    if let Some(action) = params.action {
+        query.push(format!("action={}", action.as_str()));
+    }
+    if let Some(cursor) = &params.cursor {
+        query.push(format!("cursor={}", cursor.0));
+    }
+    format!("/modlog?{}", query.join("&"))
+}
+
+fn action_name(action: ModAction) -> String {
+    match action {
+        ModAction::ProtectArticle => tr!("modlog-protect-article"),
+        ModAction::UnprotectArticle => tr!("modlog-unprotect-article"),
+        ModAction::RemoveArticle => tr!("modlog-remove-article"),
+        ModAction::RestoreArticle => tr!("modlog-restore-article"),
+        ModAction::RevertArticle => tr!("modlog-revert-article"),
+        ModAction::EnableReview => tr!("modlog-enable-review"),
+        ModAction::DisableReview => tr!("modlog-disable-review"),
+        ModAction::ApproveEdit => tr!("modlog-approve-edit"),
+        ModAction::RejectEdit => tr!("modlog-reject-edit"),
+        ModAction::SetRole => tr!("modlog-set-role"),
+        ModAction::BanUser => tr!("modlog-ban-user"),
+        ModAction::UnbanUser => tr!("modlog-unban-user"),
+        ModAction::UpdateInstance => tr!("modlog-update-instance"),
+        ModAction::ResolveReport => tr!("modlog-resolve-report"),
+        ModAction::DismissReport => tr!("modlog-dismiss-report"),
+        ModAction::AllowDomain => tr!("modlog-allow-domain"),
+        ModAction::BlockDomain => tr!("modlog-block-domain"),
+        ModAction::RemoveFederationListEntry => tr!("modlog-remove-federation-list-entry"),
+    }
+}
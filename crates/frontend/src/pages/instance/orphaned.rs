@@ -0,0 +1,44 @@
+use ibis_api_client::{CLIENT, article::ListOrphanedArticlesParams};
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{formatting::linked_article_link, i18n::IbisTitle},
+};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+
+/// Local articles which are not linked from any other article
+#[component]
+pub fn OrphanedArticles() -> impl IntoView {
+    let orphaned = Resource::new(cursor_param, |cursor| async move {
+        let params = ListOrphanedArticlesParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_orphaned_articles(&params).await
+    });
+
+    view! {
+        <IbisTitle key="orphaned-articles" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("orphaned-articles")}</h1>
+        <SuspenseError result=orphaned>
+            {move || Suspend::new(async move {
+                orphaned
+                    .await
+                    .map(|orphaned| {
+                        let next_page = orphaned.next_page;
+                        view! {
+                            <ul class="list-disc">
+                                {orphaned
+                                    .items
+                                    .iter()
+                                    .map(|a| view! { <li class="m-2">{linked_article_link(a)}</li> })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                            <NextPageLink next_page />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -0,0 +1,319 @@
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use ibis_api_client::{CLIENT, article::ListRecentChangesParams, instance::ListInstancesParams};
+use ibis_database::common::{
+    newtypes::{InstanceId, PersonId},
+    pagination::PaginationCursor,
+};
+use ibis_frontend_components::{
+    feed_links::FeedLinks,
+    suspense_error::SuspenseError,
+    utils::{
+        formatting::{article_link, edit_path, edit_time, user_link},
+        i18n::IbisTitle,
+        resources::can_moderate,
+    },
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+use leptos_router::hooks::{use_navigate, use_query_map};
+
+const RECENT_CHANGES_LIMIT: i64 = 50;
+
+/// Filters of the recent changes page, which are passed as query parameters.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct RecentChangesFilter {
+    instance_id: Option<InstanceId>,
+    person_id: Option<PersonId>,
+    local_only: bool,
+    hide_bots: bool,
+    unreviewed_only: bool,
+    /// First day to include
+    from: Option<NaiveDate>,
+    /// Last day to include
+    to: Option<NaiveDate>,
+    cursor: Option<PaginationCursor>,
+}
+
+impl RecentChangesFilter {
+    fn params(&self) -> ListRecentChangesParams {
+        let start_of_day = |date: NaiveDate| -> Option<DateTime<Utc>> {
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+        };
+        ListRecentChangesParams {
+            instance_id: self.instance_id,
+            person_id: self.person_id,
+            local_only: Some(self.local_only),
+            since: self.from.and_then(start_of_day),
+            until: self
+                .to
+                .and_then(|to| to.checked_add_days(Days::new(1)))
+                .and_then(start_of_day),
+            hide_bots: Some(self.hide_bots),
+            unreviewed_only: Some(self.unreviewed_only),
+            cursor: self.cursor.clone(),
+            limit: Some(RECENT_CHANGES_LIMIT),
+        }
+    }
+
+    fn link(&self) -> String {
+        let mut query = vec![];
+        if let Some(instance_id) = self.instance_id {
+            query.push(format!("instance_id={}", instance_id.0));
+        }
+        if let Some(person_id) = self.person_id {
+            query.push(format!("person_id={}", person_id.0));
+        }
+        if self.local_only {
+            query.push("local_only=true".to_string());
+        }
+        if self.hide_bots {
+            query.push("hide_bots=true".to_string());
+        }
+        if self.unreviewed_only {
+            query.push("unreviewed_only=true".to_string());
+        }
+        if let Some(from) = self.from {
+            query.push(format!("from={from}"));
+        }
+        if let Some(to) = self.to {
+            query.push(format!("to={to}"));
+        }
+        if let Some(cursor) = &self.cursor {
+            query.push(format!("cursor={}", cursor.0));
+        }
+        format!("/recent-changes?{}", query.join("&"))
+    }
+}
+
+/// Edits to all articles which are known to this instance, newest first.
+#[component]
+pub fn RecentChanges() -> impl IntoView {
+    let query = use_query_map();
+    let filter = move || {
+        let query = query.get();
+        let flag = |name: &str| query.get(name).is_some_and(|v| v == "true");
+        RecentChangesFilter {
+            instance_id: query
+                .get("instance_id")
+                .and_then(|i| i.parse().ok())
+                .map(InstanceId),
+            person_id: query
+                .get("person_id")
+                .and_then(|p| p.parse().ok())
+                .map(PersonId),
+            local_only: flag("local_only"),
+            hide_bots: flag("hide_bots"),
+            unreviewed_only: flag("unreviewed_only"),
+            from: query.get("from").and_then(|d| d.parse().ok()),
+            to: query.get("to").and_then(|d| d.parse().ok()),
+            cursor: query.get("cursor").map(PaginationCursor),
+        }
+    };
+    let edits = Resource::new(
+        move || filter().params(),
+        |params| async move { CLIENT.list_recent_changes(&params).await },
+    );
+    let instances = Resource::new(
+        || (),
+        |_| async move {
+            let params = ListInstancesParams {
+                limit: Some(100),
+                ..Default::default()
+            };
+            CLIENT.list_instances(&params).await.map(|i| i.items)
+        },
+    );
+    let navigate = use_navigate();
+    let set_filter = move |update: &dyn Fn(&mut RecentChangesFilter)| {
+        let mut filter = filter();
+        update(&mut filter);
+        filter.cursor = None;
+        navigate(&filter.link(), Default::default());
+    };
+    let set_filter_ = set_filter.clone();
+    let set_instance = move |value: String| {
+        set_filter_(&|f| f.instance_id = value.parse().ok().map(InstanceId));
+    };
+    let set_filter_ = set_filter.clone();
+    let set_local_only = move |value: bool| set_filter_(&|f| f.local_only = value);
+    let set_filter_ = set_filter.clone();
+    let set_hide_bots = move |value: bool| set_filter_(&|f| f.hide_bots = value);
+    let set_filter_ = set_filter.clone();
+    let set_unreviewed_only = move |value: bool| set_filter_(&|f| f.unreviewed_only = value);
+    let set_filter_ = set_filter.clone();
+    let set_from = move |value: String| set_filter_(&|f| f.from = value.parse().ok());
+    let set_to = move |value: String| set_filter(&|f| f.to = value.parse().ok());
+
+    view! {
+        <IbisTitle key="recent-changes" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("recent-changes")}</h1>
+        <div class="flex flex-wrap gap-4 items-center">
+            <Suspense>
+                {move || {
+                    let set_instance = set_instance.clone();
+                    Suspend::new(async move {
+                    let instances = instances.await.unwrap_or_default();
+                    view! {
+                        <select
+                            class="select select-sm select-bordered"
+                            on:change=move |ev| set_instance(event_target_value(&ev))
+                        >
+                            <option value="" selected=move || filter().instance_id.is_none()>
+                                {tr!("recent-changes-all-instances")}
+                            </option>
+                            {instances
+                                .into_iter()
+                                .map(|i| {
+                                    let id = i.instance.id;
+                                    view! {
+                                        <option
+                                            value=id.0
+                                            selected=move || filter().instance_id == Some(id)
+                                        >
+                                            {i.instance.domain}
+                                        </option>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                        </select>
+                    }
+                })}}
+            </Suspense>
+            <label class="flex gap-2 items-center">
+                <input
+                    type="checkbox"
+                    class="checkbox checkbox-sm"
+                    prop:checked=move || filter().local_only
+                    on:change=move |ev| set_local_only(event_target_checked(&ev))
+                />
+                {tr!("recent-changes-local-only")}
+            </label>
+            <label class="flex gap-2 items-center">
+                <input
+                    type="checkbox"
+                    class="checkbox checkbox-sm"
+                    prop:checked=move || filter().hide_bots
+                    on:change=move |ev| set_hide_bots(event_target_checked(&ev))
+                />
+                {tr!("recent-changes-hide-bots")}
+            </label>
+            <Show when=can_moderate>
+                <label class="flex gap-2 items-center">
+                    <input
+                        type="checkbox"
+                        class="checkbox checkbox-sm"
+                        prop:checked=move || filter().unreviewed_only
+                        on:change={
+                            let set_unreviewed_only = set_unreviewed_only.clone();
+                            move |ev| set_unreviewed_only(event_target_checked(&ev))
+                        }
+                    />
+                    {tr!("recent-changes-unreviewed-only")}
+                </label>
+            </Show>
+            <label class="flex gap-2 items-center">
+                {tr!("recent-changes-from")}
+                <input
+                    type="date"
+                    class="input input-sm input-bordered"
+                    prop:value=move || filter().from.map(|d| d.to_string()).unwrap_or_default()
+                    on:change=move |ev| set_from(event_target_value(&ev))
+                />
+            </label>
+            <label class="flex gap-2 items-center">
+                {tr!("recent-changes-to")}
+                <input
+                    type="date"
+                    class="input input-sm input-bordered"
+                    prop:value=move || filter().to.map(|d| d.to_string()).unwrap_or_default()
+                    on:change=move |ev| set_to(event_target_value(&ev))
+                />
+            </label>
+            <Show when=move || filter() != RecentChangesFilter::default()>
+                <a class="link" href="/recent-changes">
+                    {tr!("modlog-clear-filters")}
+                </a>
+            </Show>
+        </div>
+        <FeedLinks path="/changes".to_string() />
+        <SuspenseError result=edits>
+            {move || Suspend::new(async move {
+                edits
+                    .await
+                    .map(|edits| {
+                        if edits.items.is_empty() {
+                            return Either::Left(
+                                view! { <p class="my-4">{tr!("recent-changes-empty")}</p> },
+                            );
+                        }
+                        let next_page_link = edits
+                            .next_page
+                            .map(|cursor| {
+                                RecentChangesFilter {
+                                    cursor: Some(cursor),
+                                    ..filter()
+                                }
+                                    .link()
+                            });
+                        Either::Right(
+                            view! {
+                                <table class="table my-4">
+                                    <tbody>
+                                        {edits
+                                            .items
+                                            .into_iter()
+                                            .map(|edit| {
+                                                let delta = edit.edit.size_delta();
+                                                let delta_class = match delta.signum() {
+                                                    1 => "font-mono text-success",
+                                                    -1 => "font-mono text-error",
+                                                    _ => "font-mono",
+                                                };
+                                                let person_filter = RecentChangesFilter {
+                                                    person_id: Some(edit.creator.id),
+                                                    ..Default::default()
+                                                }
+                                                    .link();
+                                                view! {
+                                                    <tr>
+                                                        <td>{edit_time(edit.edit.published)}</td>
+                                                        <td>
+                                                            {article_link(&edit.article)} " "
+                                                            <a
+                                                                class="link text-xs"
+                                                                href=edit_path(&edit.edit, &edit.article)
+                                                            >
+                                                                "(" {tr!("diff")} ")"
+                                                            </a>
+                                                        </td>
+                                                        <td class=delta_class>
+                                                            {format!("{delta:+}")}
+                                                        </td>
+                                                        <td>
+                                                            {user_link(&edit.creator)} " "
+                                                            <a class="link text-xs" href=person_filter>
+                                                                "(" {tr!("modlog-filter")} ")"
+                                                            </a>
+                                                        </td>
+                                                        <td>{edit.edit.summary.clone()}</td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </tbody>
+                                </table>
+                                {next_page_link
+                                    .map(|href| {
+                                        view! {
+                                            <a class="link my-4 block" href=href>
+                                                {tr!("next-page")}
+                                            </a>
+                                        }
+                                    })}
+                            },
+                        )
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -0,0 +1,130 @@
+use ibis_api_client::{
+    CLIENT,
+    errors::FrontendResultExt,
+    report::{ListReportsParams, ResolveReportParams},
+};
+use ibis_database::common::{newtypes::ReportId, report::ReportView};
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{
+        formatting::{article_link, comment_path, edit_path, edit_time, user_link},
+        i18n::IbisTitle,
+    },
+};
+use leptos::{
+    either::{Either, EitherOf3},
+    prelude::*,
+};
+use leptos_fluent::tr;
+
+/// Open reports about articles, edits and comments, oldest first.
+#[component]
+pub fn ReportQueue() -> impl IntoView {
+    let reports = Resource::new(cursor_param, |cursor| async move {
+        let params = ListReportsParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_reports(&params).await
+    });
+
+    let resolve_action = Action::new(move |(report_id, dismiss): &(ReportId, bool)| {
+        let params = ResolveReportParams {
+            report_id: *report_id,
+            dismiss: *dismiss,
+            reason: None,
+        };
+        async move {
+            CLIENT
+                .resolve_report(&params)
+                .await
+                .error_popup(|_| reports.refetch());
+        }
+    });
+
+    view! {
+        <IbisTitle key="reports" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("reports")}</h1>
+        <SuspenseError result=reports>
+            {move || Suspend::new(async move {
+                reports
+                    .await
+                    .map(|reports| {
+                        if reports.items.is_empty() {
+                            return Either::Left(view! { <p>{tr!("no-open-reports")}</p> });
+                        }
+                        let next_page = reports.next_page;
+                        Either::Right(view! {
+                            {reports
+                                .items
+                                .into_iter()
+                                .map(|report| {
+                                    let report_id = report.report.id;
+                                    view! {
+                                        <div class="my-4 card card-compact bg-base-100 card-bordered">
+                                            <div class="card-body">
+                                                <div class="flex w-full">
+                                                    <h2 class="text-lg grow">
+                                                        {article_link(&report.article)}
+                                                    </h2>
+                                                    <div class="flex gap-2">
+                                                        <button
+                                                            class="btn btn-sm btn-primary"
+                                                            on:click=move |_| {
+                                                                resolve_action.dispatch((report_id, false));
+                                                            }
+                                                        >
+                                                            {tr!("resolve")}
+                                                        </button>
+                                                        <button
+                                                            class="btn btn-sm"
+                                                            on:click=move |_| {
+                                                                resolve_action.dispatch((report_id, true));
+                                                            }
+                                                        >
+                                                            {tr!("dismiss")}
+                                                        </button>
+                                                    </div>
+                                                </div>
+                                                {reported_content(&report)}
+                                                <p>{report.report.reason.clone()}</p>
+                                                <p>
+                                                    {edit_time(report.report.published)} " "
+                                                    {tr!("reported-by")} " "
+                                                    {user_link(&report.creator)}
+                                                </p>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                            <NextPageLink next_page />
+                        })
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn reported_content(report: &ReportView) -> impl IntoView {
+    match (&report.edit, &report.comment) {
+        (Some(edit), _) => EitherOf3::A(view! {
+            <p>
+                {tr!("reported-edit")} ": "
+                <a class="link" href=edit_path(edit, &report.article)>
+                    {edit.summary.clone()}
+                </a>
+            </p>
+        }),
+        (_, Some(comment)) => EitherOf3::B(view! {
+            <p>
+                {tr!("reported-comment")} ": "
+                <a class="link" href=comment_path(comment, &report.article)>
+                    {comment.content.clone()}
+                </a>
+            </p>
+        }),
+        _ => EitherOf3::C(view! { <p>{tr!("reported-article")}</p> }),
+    }
+}
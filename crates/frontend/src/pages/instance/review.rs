@@ -0,0 +1,102 @@
+use ibis_api_client::{
+    CLIENT,
+    article::{ListEditsAwaitingReviewParams, ReviewEditParams},
+    errors::FrontendResultExt,
+};
+use ibis_database::common::newtypes::EditId;
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::{
+        formatting::{article_link, edit_time, user_link},
+        i18n::IbisTitle,
+    },
+};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Edits which need to be approved by a reviewer before they are applied to an article.
+#[component]
+pub fn ReviewQueue() -> impl IntoView {
+    let edits = Resource::new(cursor_param, |cursor| async move {
+        let params = ListEditsAwaitingReviewParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_edits_awaiting_review(&params).await
+    });
+
+    let review_action = Action::new(move |(id, approve): &(EditId, bool)| {
+        let params = ReviewEditParams {
+            edit_id: *id,
+            approve: *approve,
+            reason: None,
+        };
+        async move {
+            CLIENT
+                .review_edit(&params)
+                .await
+                .error_popup(|_| edits.refetch());
+        }
+    });
+
+    view! {
+        <IbisTitle key="review-queue" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("review-queue")}</h1>
+        <SuspenseError result=edits>
+            {move || Suspend::new(async move {
+                edits
+                    .await
+                    .map(|edits| {
+                        if edits.items.is_empty() {
+                            return Either::Left(view! { <p>{tr!("review-queue-empty")}</p> });
+                        }
+                        let next_page = edits.next_page;
+                        Either::Right(view! {
+                            {edits
+                                .items
+                                .into_iter()
+                                .map(|edit| {
+                                    let id = edit.edit.id;
+                                    view! {
+                                        <div class="my-4 card card-compact bg-base-100 card-bordered">
+                                            <div class="card-body">
+                                                <div class="flex w-full">
+                                                    <h2 class="text-lg grow">{edit.edit.summary.clone()}</h2>
+                                                    <button
+                                                        class="mr-2 btn btn-success btn-sm"
+                                                        on:click=move |_| {
+                                                            review_action.dispatch((id, true));
+                                                        }
+                                                    >
+                                                        {tr!("approve")}
+                                                    </button>
+                                                    <button
+                                                        class="btn btn-error btn-sm"
+                                                        on:click=move |_| {
+                                                            review_action.dispatch((id, false));
+                                                        }
+                                                    >
+                                                        {tr!("reject")}
+                                                    </button>
+                                                </div>
+                                                <p>
+                                                    {edit_time(edit.edit.published)} " by "
+                                                    {user_link(&edit.creator)} " on "
+                                                    {article_link(&edit.article)}
+                                                </p>
+                                                <pre class="text-wrap">
+                                                    <code>{edit.edit.diff.clone()}</code>
+                                                </pre>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                            <NextPageLink next_page />
+                        })
+                    })
+            })}
+        </SuspenseError>
+    }
+}
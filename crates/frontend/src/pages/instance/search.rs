@@ -1,17 +1,24 @@
-use ibis_api_client::{CLIENT, instance::SearchArticleParams};
-use ibis_database::common::{article::Article, instance::Instance};
+use ibis_api_client::{CLIENT, errors::FrontendResultExt, instance::SearchArticleParams};
+use ibis_database::common::{
+    article::ArticleSearchResult,
+    instance::Instance,
+    pagination::PaginationCursor,
+    utils::extract_domain,
+};
 use ibis_frontend_components::utils::formatting::article_path;
 use leptos::prelude::*;
 use leptos_fluent::tr;
 use leptos_meta::Title;
-use leptos_router::hooks::use_query_map;
+use leptos_router::{components::Redirect, hooks::use_query_map, location};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 #[derive(Default, Clone, Deserialize, Serialize, Debug)]
 struct SearchResults {
-    articles: Vec<Article>,
+    articles: Vec<ArticleSearchResult>,
     instance: Option<Instance>,
+    next_page: Option<PaginationCursor>,
+    timed_out: Vec<String>,
 }
 
 impl SearchResults {
@@ -20,28 +27,61 @@ impl SearchResults {
     }
 }
 
+const SEARCH_LIMIT: i64 = 20;
+
 #[component]
 pub fn Search() -> impl IntoView {
     let params = use_query_map();
+    let query = move || params.get().get("query").unwrap_or_default();
+    let cursor = move || params.get().get("cursor").map(PaginationCursor);
+    let federated = move || params.get().get("federated").is_some_and(|f| f == "true");
+    let page_link = move |cursor: Option<&PaginationCursor>, federated: bool| {
+        let cursor = cursor
+            .map(|c| format!("&cursor={}", location::Url::escape(&c.0)))
+            .unwrap_or_default();
+        format!(
+            "/search?query={}{cursor}&federated={federated}",
+            location::Url::escape(&query())
+        )
+    };
     let (error, set_error) = signal(None::<String>);
     let search_results = Resource::new(
-        move || params.get().get("query").unwrap_or_default(),
-        move |query| async move {
+        move || (query(), cursor(), federated()),
+        move |(query, cursor, federated)| async move {
             set_error.set(None);
             let mut search_results = SearchResults::default();
-            let url = Url::parse(&query);
-            let search_data = SearchArticleParams { query };
+            // only resolve urls on the first page
+            let url = Url::parse(&query).ok().filter(|_| cursor.is_none());
+            let search_data = SearchArticleParams {
+                query,
+                instance_id: None,
+                only_local: None,
+                cursor,
+                limit: Some(SEARCH_LIMIT),
+                federated: Some(federated),
+            };
             let search = CLIENT.search(&search_data);
 
             match search.await {
-                Ok(mut a) => search_results.articles.append(&mut a),
+                Ok(mut a) => {
+                    search_results.articles.append(&mut a.results);
+                    search_results.next_page = a.next_page;
+                    search_results.timed_out = a.timed_out;
+                }
                 Err(e) => set_error.set(Some(e.to_string())),
             }
 
             // If its a valid url, also attempt to resolve as federation object
-            if let Ok(url) = url {
+            if let Some(url) = url {
                 match CLIENT.resolve_article(url.clone()).await {
-                    Ok(a) => search_results.articles.push(a.article),
+                    Ok(a) => search_results.articles.push(ArticleSearchResult {
+                        title: a.article.title.clone(),
+                        ap_id: a.article.ap_id.clone(),
+                        origin: extract_domain(a.article.ap_id.inner()),
+                        snippet: String::new(),
+                        rank: 0.0,
+                        article: Some(a.article),
+                    }),
                     Err(e) => set_error.set(Some(e.to_string())),
                 }
                 match CLIENT.resolve_instance(url).await {
@@ -54,12 +94,24 @@ pub fn Search() -> impl IntoView {
     );
 
     view! {
-        <Title text=move || {
-            format!("{} - {}", tr!("search"), params.get().get("query").unwrap_or_default())
-        } />
+        <Title text=move || { format!("{} - {}", tr!("search"), query()) } />
         <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
-            "Search results for " {move || params.get().get("query").unwrap_or_default()}
+            "Search results for " {query}
         </h1>
+        <Show
+            when=federated
+            fallback=move || {
+                view! {
+                    <a class="link" href=move || page_link(None, true)>
+                        {tr!("search-federated")}
+                    </a>
+                }
+            }
+        >
+            <a class="link" href=move || page_link(None, false)>
+                {tr!("search-local")}
+            </a>
+        </Show>
         <Suspense fallback=|| {
             view! { {tr!("loading")} }
         }>
@@ -68,7 +120,20 @@ pub fn Search() -> impl IntoView {
                     .get()
                     .map(move |search_results| {
                         let is_empty = search_results.is_empty();
+                        let next_page_link = search_results
+                            .next_page
+                            .as_ref()
+                            .map(|cursor| page_link(Some(cursor), federated()));
+                        let timed_out = search_results.timed_out.join(", ");
                         view! {
+                            <Show when={
+                                let timed_out = timed_out.clone();
+                                move || !timed_out.is_empty()
+                            }>
+                                <p class="my-2 text-warning">
+                                    {tr!("search-timed-out", {"instances" => timed_out.clone()})}
+                                </p>
+                            </Show>
                             <Show
                                 when=move || !is_empty
                                 fallback=move || {
@@ -86,7 +151,7 @@ pub fn Search() -> impl IntoView {
                                 }
                             >
 
-                                <ul>
+                                <ul class="my-4">
 
                                     // render resolved instance
                                     {if let Some(instance) = &search_results.instance {
@@ -106,18 +171,19 @@ pub fn Search() -> impl IntoView {
                                     {search_results
                                         .articles
                                         .iter()
-                                        .map(|a| {
-                                            view! {
-                                                <li>
-                                                    <a class="text-lg link" href=article_path(a)>
-                                                        {a.title()}
-                                                    </a>
-                                                </li>
-                                            }
-                                        })
+                                        .map(|a| view! { <SearchResult result=a.clone() /> })
                                         .collect::<Vec<_>>()}
 
                                 </ul>
+                                {next_page_link
+                                    .clone()
+                                    .map(|href| {
+                                        view! {
+                                            <a class="link my-4 block" href=href>
+                                                {tr!("next-page")}
+                                            </a>
+                                        }
+                                    })}
                             </Show>
                         }
                     })
@@ -126,3 +192,49 @@ pub fn Search() -> impl IntoView {
         </Suspense>
     }
 }
+
+/// Single search result. Articles from remote instances are only fetched when clicked.
+#[component]
+fn SearchResult(result: ArticleSearchResult) -> impl IntoView {
+    let (redirect, set_redirect) = signal(Option::<String>::None);
+    let resolve_action = Action::new(move |ap_id: &Url| {
+        let ap_id = ap_id.clone();
+        async move {
+            CLIENT
+                .resolve_article(ap_id)
+                .await
+                .error_popup(|res| set_redirect.set(Some(article_path(&res.article))));
+        }
+    });
+    let title = match &result.article {
+        Some(article) => view! {
+            <a class="text-lg link" href=article_path(article)>
+                {article.title()}
+            </a>
+        }
+        .into_any(),
+        None => {
+            let ap_id = result.ap_id.inner().clone();
+            view! {
+                <button
+                    class="text-lg link"
+                    on:click=move |_| {
+                        resolve_action.dispatch(ap_id.clone());
+                    }
+                >
+                    {result.title.clone()}
+                </button>
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <li class="mb-4">
+            {title}
+            <span class="ml-2 text-sm opacity-60">{result.origin.clone()}</span>
+            <p class="text-sm" inner_html=result.snippet.clone()></p>
+            {move || redirect.get().map(|path| view! { <Redirect path /> })}
+        </li>
+    }
+}
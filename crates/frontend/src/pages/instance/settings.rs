@@ -34,6 +34,13 @@ pub fn AdminSettings() -> impl IntoView {
                     .map(|site| {
                         let (name, set_name) = signal(site.instance.name.unwrap_or_default());
                         let (topic, set_topic) = signal(site.instance.topic.unwrap_or_default());
+                        let review_all_edits = RwSignal::new(site.instance.review_all_edits);
+                        let review_new_user_edits = RwSignal::new(
+                            site.instance.review_new_user_edits.to_string(),
+                        );
+                        let require_totp_for_moderators = RwSignal::new(
+                            site.instance.require_totp_for_moderators,
+                        );
                         view! {
                             <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
                                 {tr!("admin-settings")}
@@ -60,12 +67,61 @@ pub fn AdminSettings() -> impl IntoView {
                                     bind:value=(topic, set_topic)
                                 />
                             </div>
+                            <h2 class="my-4 font-serif text-2xl font-bold">
+                                {tr!("edit-review")}
+                            </h2>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-80" for="review_all_edits">
+                                    {tr!("review-all-edits")}
+                                </label>
+                                <input
+                                    type="checkbox"
+                                    id="review_all_edits"
+                                    class="checkbox"
+                                    bind:checked=review_all_edits
+                                />
+                            </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-80" for="review_new_user_edits">
+                                    {tr!("review-new-user-edits")}
+                                </label>
+                                <input
+                                    type="number"
+                                    min="0"
+                                    id="review_new_user_edits"
+                                    class="w-24 input input-secondary input-bordered"
+                                    bind:value=review_new_user_edits
+                                />
+                            </div>
+                            <h2 class="my-4 font-serif text-2xl font-bold">
+                                {tr!("security")}
+                            </h2>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-80" for="require_totp_for_moderators">
+                                    {tr!("require-totp-for-moderators")}
+                                </label>
+                                <input
+                                    type="checkbox"
+                                    id="require_totp_for_moderators"
+                                    class="checkbox"
+                                    bind:checked=require_totp_for_moderators
+                                />
+                            </div>
                             <button
                                 class="btn btn-primary"
                                 on:click=move |_| {
                                     let form = UpdateInstanceParams {
                                         name: Some(name.get()),
                                         topic: Some(topic.get()),
+                                        review_all_edits: Some(review_all_edits.get()),
+                                        review_new_user_edits: review_new_user_edits
+                                            .get()
+                                            .parse()
+                                            .ok(),
+                                        require_totp_for_moderators: Some(
+                                            require_totp_for_moderators.get(),
+                                        ),
+                                        reason: None,
                                     };
                                     submit_action.dispatch(form);
                                 }
@@ -73,6 +129,22 @@ pub fn AdminSettings() -> impl IntoView {
                                 Submit
                             </button>
 
+                            <p class="my-4">
+                                <a class="link" href="/admin/media">
+                                    {tr!("uploaded-media")}
+                                </a>
+                            </p>
+                            <p class="my-4">
+                                <a class="link" href="/federation">
+                                    {tr!("federation")}
+                                </a>
+                            </p>
+                            <p class="my-4">
+                                <a class="link" href="/admin/delivery">
+                                    {tr!("delivery-status")}
+                                </a>
+                            </p>
+
                             <Show when=move || saved.get()>
                                 <div class="toast">
                                     <div class="alert alert-info">
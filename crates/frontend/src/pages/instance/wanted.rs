@@ -0,0 +1,56 @@
+use ibis_api_client::{CLIENT, article::ListWantedArticlesParams};
+use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
+    suspense_error::SuspenseError,
+    utils::i18n::IbisTitle,
+};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use leptos_router::location::Url;
+
+/// Titles which are linked from other articles, but don't exist yet
+#[component]
+pub fn WantedArticles() -> impl IntoView {
+    let wanted = Resource::new(cursor_param, |cursor| async move {
+        let params = ListWantedArticlesParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.list_wanted_articles(&params).await
+    });
+
+    view! {
+        <IbisTitle key="wanted-articles" />
+        <h1 class="my-4 font-serif text-4xl font-bold">{tr!("wanted-articles")}</h1>
+        <SuspenseError result=wanted>
+            {move || Suspend::new(async move {
+                wanted
+                    .await
+                    .map(|wanted| {
+                        let next_page = wanted.next_page;
+                        view! {
+                            <ul class="list-disc">
+                                {wanted
+                                    .items
+                                    .into_iter()
+                                    .map(|w| {
+                                        let href = format!("/create-article?title={}", Url::escape(&w.title));
+                                        view! {
+                                            <li class="m-2">
+                                                <a class="link" href=href>
+                                                    {w.title}
+                                                </a>
+                                                " "
+                                                {tr!("wanted-article-links", {"count" => w.link_count})}
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                            <NextPageLink next_page />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -0,0 +1,167 @@
+use ibis_api_client::{CLIENT, errors::FrontendResultExt, user::CreateApiTokenParams};
+use ibis_database::common::{
+    api_token::{ApiToken, ApiTokenScope},
+    newtypes::ApiTokenId,
+};
+use ibis_frontend_components::{suspense_error::SuspenseError, utils::formatting::edit_time};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Personal tokens for bots and scripts, which can be created and revoked by the user.
+#[component]
+pub fn ApiTokens() -> impl IntoView {
+    let tokens = Resource::new(
+        move || (),
+        |_| async move { CLIENT.list_api_tokens().await },
+    );
+    // the secret token is only shown once after creating it
+    let (new_token, set_new_token) = signal(None::<String>);
+    let name = signal(String::new());
+    let edit_articles = signal(false);
+    let comment = signal(false);
+    let bot_edits = signal(false);
+
+    let create_action = Action::new(move |params: &CreateApiTokenParams| {
+        let params = params.clone();
+        async move {
+            CLIENT.create_api_token(&params).await.error_popup(|res| {
+                set_new_token.set(Some(res.token));
+                tokens.refetch();
+            });
+        }
+    });
+    let delete_action = Action::new(move |id: &ApiTokenId| {
+        let id = *id;
+        async move {
+            CLIENT
+                .delete_api_token(id)
+                .await
+                .error_popup(|_| tokens.refetch());
+        }
+    });
+
+    view! {
+        <h2 class="flex-auto my-6 font-serif text-2xl font-bold grow">{tr!("api-tokens")}</h2>
+        <p class="mb-2">{tr!("api-tokens-description")}</p>
+        <div class="flex flex-col gap-2 my-4 w-80">
+            <input
+                type="text"
+                class="input input-sm input-bordered"
+                placeholder=tr!("api-token-name")
+                bind:value=name
+            />
+            <label class="flex gap-2 items-center">
+                <input type="checkbox" class="checkbox checkbox-sm" bind:checked=edit_articles />
+                {scope_name(ApiTokenScope::EditArticles)}
+            </label>
+            <label class="flex gap-2 items-center">
+                <input type="checkbox" class="checkbox checkbox-sm" bind:checked=comment />
+                {scope_name(ApiTokenScope::Comment)}
+            </label>
+            <label class="flex gap-2 items-center">
+                <input type="checkbox" class="checkbox checkbox-sm" bind:checked=bot_edits />
+                {tr!("api-token-bot-edits")}
+            </label>
+            <button
+                class="btn btn-sm btn-primary"
+                disabled=move || name.0.get().trim().is_empty()
+                on:click=move |_| {
+                    create_action
+                        .dispatch(CreateApiTokenParams {
+                            name: name.0.get(),
+                            edit_articles: edit_articles.0.get(),
+                            comment: comment.0.get(),
+                            bot_edits: bot_edits.0.get(),
+                        });
+                }
+            >
+                {tr!("api-token-create")}
+            </button>
+        </div>
+        {move || {
+            new_token
+                .get()
+                .map(|token| {
+                    view! {
+                        <div class="my-4 alert alert-info">
+                            <div>
+                                <p>{tr!("api-token-created")}</p>
+                                <code class="break-all">{token}</code>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+        <SuspenseError result=tokens>
+            {move || Suspend::new(async move {
+                tokens
+                    .await
+                    .map(|tokens| {
+                        if tokens.is_empty() {
+                            return Either::Left(view! { <p>{tr!("api-tokens-empty")}</p> });
+                        }
+                        Either::Right(
+                            view! {
+                                <table class="table my-4">
+                                    <thead>
+                                        <tr>
+                                            <th>{tr!("api-token-name")}</th>
+                                            <th>{tr!("api-token-scopes")}</th>
+                                            <th>{tr!("api-token-last-used")}</th>
+                                            <th></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {tokens
+                                            .into_iter()
+                                            .map(|token| token_row(token, delete_action))
+                                            .collect::<Vec<_>>()}
+                                    </tbody>
+                                </table>
+                            },
+                        )
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn token_row(token: ApiToken, delete_action: Action<ApiTokenId, ()>) -> impl IntoView {
+    let scopes = token
+        .scopes
+        .iter()
+        .map(|s| scope_name(*s))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let id = token.id;
+    view! {
+        <tr>
+            <td>{token.name}</td>
+            <td>{scopes}</td>
+            <td>
+                {match token.last_used {
+                    Some(l) => Either::Left(edit_time(l)),
+                    None => Either::Right(tr!("api-token-never-used")),
+                }}
+            </td>
+            <td>
+                <button
+                    class="btn btn-sm"
+                    on:click=move |_| {
+                        delete_action.dispatch(id);
+                    }
+                >
+                    {tr!("api-token-revoke")}
+                </button>
+            </td>
+        </tr>
+    }
+}
+
+fn scope_name(scope: ApiTokenScope) -> String {
+    match scope {
+        ApiTokenScope::Read => tr!("api-token-scope-read"),
+        ApiTokenScope::EditArticles => tr!("api-token-scope-edit-articles"),
+        ApiTokenScope::Comment => tr!("api-token-scope-comment"),
+    }
+}
@@ -1,3 +1,4 @@
+use super::{api_tokens::ApiTokens, sessions::Sessions, totp::TwoFactorAuth};
 use ibis_api_client::{
     CLIENT,
     errors::FrontendResultExt,
@@ -42,8 +43,10 @@ pub fn UserEditProfile() -> impl IntoView {
             {Suspend::new(async move {
                 site.await
                     .ok()
-                    .and_then(|site| site.my_profile)
-                    .map(|my_profile| {
+                    .and_then(|site| Some((site.my_profile?, site.instance)))
+                    .map(|(my_profile, instance)| {
+                        let totp_required = instance.require_totp_for_moderators
+                            && my_profile.local_user.role.can_moderate();
                         let display_name = signal(
                             my_profile.person.display_name.clone().unwrap_or_default(),
                         );
@@ -169,6 +172,21 @@ pub fn UserEditProfile() -> impl IntoView {
                                 {tr!("save")}
                             </button>
 
+                            <div class="divider"></div>
+
+                            <TwoFactorAuth
+                                enabled=my_profile.local_user.totp_enabled
+                                required=totp_required
+                            />
+
+                            <div class="divider"></div>
+
+                            <ApiTokens />
+
+                            <div class="divider"></div>
+
+                            <Sessions />
+
                             <Show when=move || saved.get()>
                                 <div class="toast">
                                     <div class="alert alert-info">
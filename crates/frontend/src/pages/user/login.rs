@@ -1,4 +1,5 @@
-use ibis_api_client::{CLIENT, errors::FrontendResultExt, user::LoginUserParams};
+use ibis_api_client::{CLIENT, errors::ErrorPopup, user::LoginUserParams};
+use ibis_database::common::totp::TOTP_REQUIRED_ERROR;
 use ibis_frontend_components::{
     oauth_login_button::OauthLoginButtons,
     utils::{i18n::IbisTitle, resources::site},
@@ -11,22 +12,33 @@ use leptos_router::components::Redirect;
 pub fn Login() -> impl IntoView {
     let password = signal(String::new());
     let username_or_email = signal(String::new());
+    let totp_code = signal(String::new());
+    // second login step, shown if the user has two-factor authentication enabled
+    let (totp_required, set_totp_required) = signal(false);
     let (login_response, set_login_response) = signal(false);
     let (loading, set_loading) = signal(false);
 
     let login_action = Action::new(move |(): &()| {
         let username_or_email = username_or_email.0.get().clone();
         let password = password.0.get().clone();
+        let totp_code = Some(totp_code.0.get()).filter(|c| !c.is_empty());
         let params = LoginUserParams {
             username_or_email,
             password,
+            totp_code,
         };
         async move {
             set_loading.set(true);
-            CLIENT.login(params).await.error_popup(|_| {
-                site().refetch();
-                set_login_response.set(true);
-            });
+            match CLIENT.login(params).await {
+                Ok(_) => {
+                    site().refetch();
+                    set_login_response.set(true);
+                }
+                Err(e) if e.to_string() == TOTP_REQUIRED_ERROR => {
+                    set_totp_required.set(true);
+                }
+                Err(e) => ErrorPopup::set(e.message()),
+            }
             set_loading.set(false);
         }
     });
@@ -63,6 +75,18 @@ pub fn Login() -> impl IntoView {
                     prop:disabled=move || loading.get()
                     bind:value=password
                 />
+                <Show when=move || totp_required.get()>
+                    <p class="my-1 text-sm">{tr!("totp-login-description")}</p>
+                    <input
+                        type="text"
+                        inputmode="numeric"
+                        autocomplete="one-time-code"
+                        class="input input-primary input-bordered my-1"
+                        placeholder=tr!("totp-code")
+                        prop:disabled=move || loading.get()
+                        bind:value=totp_code
+                    />
+                </Show>
                 <a href="/account/request_password_reset" class="link text-sm">
                     {tr!("reset-password")}
                 </a>
@@ -1,3 +1,4 @@
+pub mod api_tokens;
 pub mod edit_profile;
 pub mod login;
 pub mod notifications;
@@ -6,4 +7,6 @@ pub mod profile;
 pub mod register;
 pub mod request_password_reset;
 pub mod reset_password;
+pub mod sessions;
+pub mod totp;
 pub mod verify_email;
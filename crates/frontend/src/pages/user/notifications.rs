@@ -1,32 +1,39 @@
 use ibis_api_client::{
     CLIENT,
     errors::{FrontendError, FrontendResultExt},
+    notifications::ListNotificationsParams,
 };
 use ibis_database::common::{
     article::Edit,
     comment::Comment,
     newtypes::ConflictId,
     notifications::{ApiNotification, ApiNotificationData},
+    pagination::PaginatedResponse,
+    report::Report,
 };
 use ibis_frontend_components::{
+    pagination::{NextPageLink, cursor_param},
     suspense_error::SuspenseError,
     utils::{
         formatting::{article_link, article_path, comment_path, edit_path, time_ago, user_link},
         i18n::IbisTitle,
     },
 };
-use leptos::{either::EitherOf4, prelude::*};
+use leptos::{either::EitherOf5, prelude::*};
 use leptos_fluent::tr;
 use phosphor_leptos::{CHECK, Icon, IconData, LINK, TRASH};
 
-type NotificationsResource = Resource<Result<Vec<ApiNotification>, FrontendError>>;
+type NotificationsResource = Resource<Result<PaginatedResponse<ApiNotification>, FrontendError>>;
 
 #[component]
 pub fn Notifications() -> impl IntoView {
-    let notifications = Resource::new(
-        move || {},
-        |_| async move { CLIENT.notifications_list().await },
-    );
+    let notifications = Resource::new(cursor_param, |cursor| async move {
+        let params = ListNotificationsParams {
+            cursor,
+            ..Default::default()
+        };
+        CLIENT.notifications_list(&params).await
+    });
 
     view! {
         <IbisTitle key="notifications" />
@@ -39,11 +46,14 @@ pub fn Notifications() -> impl IntoView {
                     notifications
                         .await
                         .map(|n| {
-                            if !n.is_empty() {
-                                n.iter()
+                            if !n.items.is_empty() {
+                                let next_page = n.next_page.clone();
+                                let items = n
+                                    .items
+                                    .iter()
                                     .map(|notif| {
                                         use ApiNotificationData::*;
-                                        use EitherOf4::*;
+                                        use EitherOf5::*;
                                         let refresh_res = notifications;
                                         match &notif.data {
                                             EditConflict { conflict_id, summary } => {
@@ -54,9 +64,14 @@ pub fn Notifications() -> impl IntoView {
                                             ArticleCreated => B(article_view(notif, refresh_res)),
                                             Comment(c) => C(comment_view(notif, c, refresh_res)),
                                             Edit(e) => D(edit_view(notif, e, refresh_res)),
+                                            Report(r) => E(report_view(notif, r, refresh_res)),
                                         }
                                     })
-                                    .collect::<Vec<_>>()
+                                    .collect::<Vec<_>>();
+                                view! {
+                                    {items}
+                                    <NextPageLink next_page />
+                                }
                                     .into_any()
                             } else {
                                 view! {
@@ -149,6 +164,24 @@ fn edit_view(
     }
 }
 
+fn report_view(
+    notif: &ApiNotification,
+    report: &Report,
+    refresh_res: NotificationsResource,
+) -> impl IntoView {
+    view! {
+        <li class="py-2">
+            <CardTitle notif=notif.clone() />
+            <div>{tr!("notification-new-report", {"text" => report.reason.clone()})}</div>
+            <CardActions
+                href="/admin/reports".to_string()
+                notif=notif.clone()
+                refresh_res=refresh_res
+            />
+        </li>
+    }
+}
+
 #[component]
 fn CardTitle(notif: ApiNotification) -> impl IntoView {
     view! {
@@ -1,7 +1,7 @@
 use ibis_api_client::{
     CLIENT,
     errors::{ErrorPopup, FrontendResultExt},
-    user::AuthenticateWithOauth,
+    user::{AuthenticateWithOauth, LoginTotpParams},
 };
 use ibis_frontend_components::{oauth_login_button::OauthCookie, utils::use_cookie};
 use leptos::{prelude::*, task::spawn};
@@ -10,6 +10,22 @@ use leptos_router::hooks::use_query_map;
 
 #[component]
 pub fn OauthCallback() -> impl IntoView {
+    // second login step, if the user has two-factor authentication enabled
+    let (totp_token, set_totp_token) = signal(None::<String>);
+    let totp_code = signal(String::new());
+    let login_totp_action = Action::new(move |(): &()| {
+        let params = LoginTotpParams {
+            token: totp_token.get_untracked().unwrap_or_default(),
+            code: totp_code.0.get_untracked(),
+        };
+        async move {
+            CLIENT
+                .login_totp(params)
+                .await
+                .error_popup(|_| window().location().set_pathname("/").expect("set location"));
+        }
+    });
+
     use_cookie("oauth_state")
         .0
         .with(|cookie: &Option<OauthCookie>| {
@@ -33,9 +49,37 @@ pub fn OauthCallback() -> impl IntoView {
                 CLIENT
                     .oauth_authenticate(params)
                     .await
-                    .error_popup(|_| window().location().set_pathname("/").expect("set location"));
+                    .error_popup(|res| match res.totp_token {
+                        Some(token) => set_totp_token.set(Some(token)),
+                        None => window().location().set_pathname("/").expect("set location"),
+                    });
             });
         });
 
-    view! { {tr!("loading")} }
+    view! {
+        <Show when=move || totp_token.get().is_some() fallback=move || tr!("loading")>
+            <form class="form-control max-w-80" on:submit=|ev| ev.prevent_default()>
+                <p class="my-1 text-sm">{tr!("totp-login-description")}</p>
+                <input
+                    type="text"
+                    inputmode="numeric"
+                    autocomplete="one-time-code"
+                    class="input input-primary input-bordered my-1"
+                    placeholder=tr!("totp-code")
+                    bind:value=totp_code
+                />
+                <div>
+                    <button
+                        class="my-2 btn btn-primary"
+                        prop:disabled=move || totp_code.0.get().trim().is_empty()
+                        on:click=move |_| {
+                            login_totp_action.dispatch(());
+                        }
+                    >
+                        {tr!("login")}
+                    </button>
+                </div>
+            </form>
+        </Show>
+    }
 }
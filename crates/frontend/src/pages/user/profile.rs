@@ -1,11 +1,27 @@
-use ibis_api_client::{CLIENT, user::GetUserParams};
+use chrono::{Days, Utc};
+use ibis_api_client::{
+    CLIENT,
+    errors::{FrontendResult, FrontendResultExt},
+    user::{BanPersonParams, GetUserParams, SetUserRoleParams, UnbanPersonParams},
+};
+use ibis_database::common::{
+    ban::PersonBan,
+    newtypes::PersonId,
+    user::{PersonView, Role},
+};
 use ibis_frontend_components::{
     edit_list::EditList,
+    feed_links::FeedLinks,
     suspense_error::SuspenseError,
-    utils::i18n::IbisTitle,
+    utils::{
+        formatting::edit_time,
+        i18n::IbisTitle,
+        resources::{can_moderate, is_admin, my_profile},
+    },
 };
 use ibis_markdown::render_article_markdown;
 use leptos::prelude::*;
+use leptos_fluent::tr;
 use leptos_router::hooks::use_params_map;
 
 #[component]
@@ -21,22 +37,80 @@ pub fn UserProfile() -> impl IntoView {
         let params = GetUserParams { name, domain };
         CLIENT.get_user(params).await
     });
+    let role_action = Action::new(move |params: &SetUserRoleParams| {
+        let params = params.clone();
+        async move {
+            CLIENT
+                .set_user_role(&params)
+                .await
+                .error_popup(|_| user_profile.refetch());
+        }
+    });
 
     view! {
         <SuspenseError result=user_profile>
             {move || Suspend::new(async move {
                 let edits = Resource::new(
                     move || user_profile.get(),
-                    move |_| async move { CLIENT.get_person_edits(user_profile.await?.id).await },
+                    move |_| async move { CLIENT.get_person_edits(user_profile.await?.person.id).await },
                 );
                 user_profile
                     .await
-                    .map(|person| {
+                    .map(|profile| {
+                        let person = profile.person;
+                        let person_id = person.id;
+                        let role = profile.role;
+                        let ban = profile.ban.clone();
+                        // admins can change roles of other local users
+                        let can_set_role = role.is_some()
+                            && is_admin()
+                            && my_profile().map(|p| p.person.id) != Some(person_id);
                         view! {
                             <IbisTitle key="profile" />
-                            <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
-                                {person.title()}
-                            </h1>
+                            <div class="flex items-center gap-4">
+                                <h1 class="my-6 font-serif text-4xl font-bold">{person.title()}</h1>
+                                {role
+                                    .filter(|r| *r != Role::User)
+                                    .map(|r| {
+                                        view! { <span class="badge badge-neutral">{role_name(r)}</span> }
+                                    })}
+                                {ban.clone().map(|ban| view! { <BanBadge ban /> })}
+                            </div>
+                            <Show when=move || can_set_role>
+                                <div class="flex items-center gap-2 mb-4">
+                                    <label for="role">{tr!("role")}</label>
+                                    <select
+                                        id="role"
+                                        class="select select-sm select-bordered"
+                                        on:change=move |ev| {
+                                            if let Ok(role) = event_target_value(&ev).parse() {
+                                                role_action
+                                                    .dispatch(SetUserRoleParams {
+                                                        person_id,
+                                                        role,
+                                                        reason: None,
+                                                    });
+                                            }
+                                        }
+                                    >
+                                        {[Role::User, Role::TrustedEditor, Role::Moderator, Role::Admin]
+                                            .into_iter()
+                                            .map(|r| {
+                                                view! {
+                                                    <option value=r.as_str() selected=role == Some(r)>
+                                                        {role_name(r)}
+                                                    </option>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </select>
+                                </div>
+                            </Show>
+                            <Show when=move || {
+                                can_moderate() && my_profile().map(|p| p.person.id) != Some(person_id)
+                            }>
+                                <BanForm person_id banned=ban.is_some() user_profile />
+                            </Show>
 
                             <div
                                 class="mb-2 max-w-full prose prose-slate text-ellipsis overflow-x-hidden"
@@ -50,6 +124,7 @@ pub fn UserProfile() -> impl IntoView {
                                         .map(|edits| {
                                             view! {
                                                 <h2 class="font-serif text-xl font-bold">Edits</h2>
+                                                <FeedLinks path=format!("/user/{}", name()) />
                                                 <EditList edits=edits for_article=false />
                                             }
                                         })
@@ -61,3 +136,112 @@ pub fn UserProfile() -> impl IntoView {
         </SuspenseError>
     }
 }
+
+fn role_name(role: Role) -> String {
+    match role {
+        Role::User => tr!("role-user"),
+        Role::TrustedEditor => tr!("role-trusted-editor"),
+        Role::Moderator => tr!("role-moderator"),
+        Role::Admin => tr!("role-admin"),
+    }
+}
+
+#[component]
+fn BanBadge(ban: PersonBan) -> impl IntoView {
+    let title = ban.reason.clone();
+    view! {
+        <span class="badge badge-error" title=title>
+            {tr!("banned")}
+            {ban.expires.map(|e| view! { " (" {tr!("until")} " " {edit_time(e)} ")" })}
+        </span>
+    }
+}
+
+/// Allows moderators to ban the user, or to lift an existing ban.
+#[component]
+fn BanForm(
+    person_id: PersonId,
+    banned: bool,
+    user_profile: Resource<FrontendResult<PersonView>>,
+) -> impl IntoView {
+    let reason = signal(String::new());
+    let days = signal(String::new());
+    let remove_content_days = signal(String::new());
+    let ban_action = Action::new(move |params: &BanPersonParams| {
+        let params = params.clone();
+        async move {
+            CLIENT
+                .ban_person(&params)
+                .await
+                .error_popup(|_| user_profile.refetch());
+        }
+    });
+    let unban_action = Action::new(move |_: &()| async move {
+        let params = UnbanPersonParams {
+            person_id,
+            reason: None,
+        };
+        CLIENT
+            .unban_person(&params)
+            .await
+            .error_popup(|_| user_profile.refetch());
+    });
+
+    if banned {
+        return view! {
+            <button class="mb-4 btn btn-sm" on:click=move |_| { unban_action.dispatch(()); }>
+                {tr!("unban")}
+            </button>
+        }
+        .into_any();
+    }
+    view! {
+        <details class="mb-4">
+            <summary class="cursor-pointer">{tr!("ban-user")}</summary>
+            <div class="flex flex-col gap-2 mt-2 w-80">
+                <input
+                    type="text"
+                    class="input input-sm input-bordered"
+                    placeholder=tr!("ban-reason")
+                    bind:value=reason
+                />
+                <input
+                    type="number"
+                    min="1"
+                    class="input input-sm input-bordered"
+                    placeholder=tr!("ban-days")
+                    bind:value=days
+                />
+                <input
+                    type="number"
+                    min="1"
+                    class="input input-sm input-bordered"
+                    placeholder=tr!("ban-remove-content-days")
+                    bind:value=remove_content_days
+                />
+                <button
+                    class="btn btn-sm btn-error"
+                    disabled=move || reason.0.get().trim().is_empty()
+                    on:click=move |_| {
+                        let expires = days
+                            .0
+                            .get()
+                            .parse()
+                            .ok()
+                            .and_then(|d| Utc::now().checked_add_days(Days::new(d)));
+                        ban_action
+                            .dispatch(BanPersonParams {
+                                person_id,
+                                reason: reason.0.get(),
+                                expires,
+                                remove_content_days: remove_content_days.0.get().parse().ok(),
+                            });
+                    }
+                >
+                    {tr!("ban")}
+                </button>
+            </div>
+        </details>
+    }
+    .into_any()
+}
@@ -0,0 +1,87 @@
+use ibis_api_client::{CLIENT, errors::FrontendResultExt};
+use ibis_database::common::{newtypes::LoginSessionId, session::LoginSession};
+use ibis_frontend_components::{suspense_error::SuspenseError, utils::formatting::edit_time};
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Devices where the user is logged in. Other sessions can be logged out from here.
+#[component]
+pub fn Sessions() -> impl IntoView {
+    let sessions = Resource::new(move || (), |_| async move { CLIENT.list_sessions().await });
+    let revoke_action = Action::new(move |id: &LoginSessionId| {
+        let id = *id;
+        async move {
+            CLIENT
+                .revoke_session(id)
+                .await
+                .error_popup(|_| sessions.refetch());
+        }
+    });
+
+    view! {
+        <h2 class="flex-auto my-6 font-serif text-2xl font-bold grow">{tr!("sessions")}</h2>
+        <SuspenseError result=sessions>
+            {move || Suspend::new(async move {
+                sessions
+                    .await
+                    .map(|res| {
+                        view! {
+                            <table class="table my-4">
+                                <thead>
+                                    <tr>
+                                        <th>{tr!("session-device")}</th>
+                                        <th>{tr!("session-ip")}</th>
+                                        <th>{tr!("session-last-seen")}</th>
+                                        <th></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {res
+                                        .sessions
+                                        .into_iter()
+                                        .map(|session| {
+                                            let current = res.current == Some(session.id);
+                                            session_row(session, current, revoke_action)
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn session_row(
+    session: LoginSession,
+    current: bool,
+    revoke_action: Action<LoginSessionId, ()>,
+) -> impl IntoView {
+    let id = session.id;
+    view! {
+        <tr>
+            <td>{session.user_agent.unwrap_or_default()}</td>
+            <td>{session.ip.unwrap_or_default()}</td>
+            <td>{edit_time(session.last_seen)}</td>
+            <td>
+                {if current {
+                    Either::Left(view! { <span class="badge">{tr!("session-current")}</span> })
+                } else {
+                    Either::Right(
+                        view! {
+                            <button
+                                class="btn btn-sm"
+                                on:click=move |_| {
+                                    revoke_action.dispatch(id);
+                                }
+                            >
+                                {tr!("session-logout")}
+                            </button>
+                        },
+                    )
+                }}
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,137 @@
+use ibis_api_client::{CLIENT, errors::FrontendResultExt};
+use ibis_database::common::totp::TotpSetupResponse;
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::tr;
+
+/// Enable or disable two-factor authentication with an authenticator app. `required` shows a
+/// warning if the user can't use moderator permissions without it.
+#[component]
+pub fn TwoFactorAuth(enabled: bool, required: bool) -> impl IntoView {
+    let (enabled, set_enabled) = signal(enabled);
+    let (setup, set_setup) = signal(None::<TotpSetupResponse>);
+    // recovery codes are only shown once after enabling
+    let (recovery_codes, set_recovery_codes) = signal(None::<Vec<String>>);
+    let code = signal(String::new());
+
+    let setup_action = Action::new(move |(): &()| async move {
+        CLIENT
+            .setup_totp()
+            .await
+            .error_popup(|res| set_setup.set(Some(res)));
+    });
+    let enable_action = Action::new(move |code: &String| {
+        let code = code.clone();
+        async move {
+            CLIENT.enable_totp(code).await.error_popup(|res| {
+                set_enabled.set(true);
+                set_setup.set(None);
+                set_recovery_codes.set(Some(res.recovery_codes));
+            });
+        }
+    });
+    let disable_action = Action::new(move |code: &String| {
+        let code = code.clone();
+        async move {
+            CLIENT.disable_totp(code).await.error_popup(|_| {
+                set_enabled.set(false);
+                set_recovery_codes.set(None);
+            });
+        }
+    });
+    let submit = move |_| {
+        let c = code.0.get();
+        code.1.set(String::new());
+        if enabled.get_untracked() {
+            disable_action.dispatch(c);
+        } else {
+            enable_action.dispatch(c);
+        }
+    };
+
+    view! {
+        <h2 class="flex-auto my-6 font-serif text-2xl font-bold grow">{tr!("totp")}</h2>
+        <p class="mb-2">{tr!("totp-description")}</p>
+        <Show when=move || required && !enabled.get()>
+            <div class="my-4 alert alert-warning">{tr!("totp-required-warning")}</div>
+        </Show>
+        {move || {
+            recovery_codes
+                .get()
+                .map(|codes| {
+                    view! {
+                        <div class="my-4 alert alert-info">
+                            <div>
+                                <p>{tr!("totp-recovery-codes")}</p>
+                                <ul class="font-mono">
+                                    {codes
+                                        .into_iter()
+                                        .map(|c| view! { <li>{c}</li> })
+                                        .collect::<Vec<_>>()}
+                                </ul>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+        {move || {
+            if enabled.get() {
+                Either::Left(view! { <p class="mb-2 font-bold">{tr!("totp-enabled")}</p> })
+            } else {
+                Either::Right(
+                    match setup.get() {
+                        Some(setup) => {
+                            Either::Left(
+                                view! {
+                                    <p class="mb-2">{tr!("totp-scan")}</p>
+                                    <div class="w-52 bg-white" inner_html=setup.qr_code />
+                                    <p class="my-2">
+                                        <a class="link" href=setup.uri>
+                                            {tr!("totp-open-app")}
+                                        </a>
+                                    </p>
+                                    <p class="my-2">
+                                        {tr!("totp-secret")} " "
+                                        <code class="break-all">{setup.secret}</code>
+                                    </p>
+                                },
+                            )
+                        }
+                        None => {
+                            Either::Right(
+                                view! {
+                                    <button
+                                        class="btn btn-sm btn-primary"
+                                        on:click=move |_| {
+                                            setup_action.dispatch(());
+                                        }
+                                    >
+                                        {tr!("totp-setup")}
+                                    </button>
+                                },
+                            )
+                        }
+                    },
+                )
+            }
+        }}
+        <Show when=move || enabled.get() || setup.get().is_some()>
+            <div class="flex flex-row gap-2 my-4">
+                <input
+                    type="text"
+                    inputmode="numeric"
+                    autocomplete="one-time-code"
+                    class="w-40 input input-sm input-bordered"
+                    placeholder=tr!("totp-code")
+                    bind:value=code
+                />
+                <button
+                    class="btn btn-sm"
+                    disabled=move || code.0.get().trim().is_empty()
+                    on:click=submit
+                >
+                    {move || if enabled.get() { tr!("totp-disable") } else { tr!("totp-enable") }}
+                </button>
+            </div>
+        </Show>
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{prevent_navigation, utils::use_cookie};
+use ibis_api_client::{CLIENT, errors::FrontendResultExt};
 use ibis_markdown::render_article_markdown;
-use leptos::{html::Textarea, prelude::*};
+use leptos::{ev::Event, html::Textarea, prelude::*, task::spawn_local};
 use leptos_fluent::tr;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlInputElement, js_sys::Uint8Array};
 
 #[component]
 pub fn EditorView(
@@ -15,6 +18,26 @@ pub fn EditorView(
 
     prevent_navigation(content);
 
+    // Upload the selected image and embed it at the end of the text
+    let upload_image = move |evt: Event| {
+        let input: HtmlInputElement = event_target(&evt);
+        let Some(file) = input.files().and_then(|f| f.get(0)) else {
+            return;
+        };
+        spawn_local(async move {
+            let Ok(buffer) = JsFuture::from(file.array_buffer()).await else {
+                return;
+            };
+            let data = Uint8Array::new(&buffer).to_vec();
+            CLIENT.upload_media(data).await.error_popup(|media| {
+                let mut text = content.get_untracked();
+                text.push_str(&format!("\n![]({})\n", media.path()));
+                set_preview.set(render_article_markdown(&text));
+                set_content.set(text);
+            });
+        });
+    };
+
     view! {
         <div>
             <div class="flex my-4 w-full max-sm:flex-col">
@@ -46,6 +69,10 @@ pub fn EditorView(
                 >
                     {tr!("preview")}
                 </button>
+                <label class="ml-2 btn btn-secondary btn-sm">
+                    {tr!("upload-image")}
+                    <input type="file" accept="image/*" class="hidden" on:change=upload_image />
+                </label>
                 <p class="mx-4">
                     <a
                         class="link link-secondary"
@@ -2,7 +2,7 @@ use crate::{
     Pending,
     utils::{
         formatting::article_path,
-        resources::{is_admin, is_logged_in},
+        resources::{can_moderate, is_logged_in},
     },
 };
 use ibis_api_client::{
@@ -22,9 +22,11 @@ use phosphor_leptos::{
     FEDIVERSE_LOGO,
     GEAR_SIX,
     Icon,
+    LINK,
     LIST,
     LOCK_SIMPLE,
     PENCIL,
+    SIGNATURE,
     TRASH,
 };
 
@@ -33,6 +35,8 @@ pub enum ActiveTab {
     Read,
     Discussion,
     History,
+    Blame,
+    Backlinks,
     Edit,
     Actions,
 }
@@ -97,9 +101,27 @@ pub fn ArticleNav(
                                     <Icon icon=LIST />
                                     {tr!("history-tab")}
                                 </A>
+                                <A
+                                    href=format!("{article_link}/blame")
+                                    {..}
+                                    role="tab"
+                                    class="tab md:flex-auto"
+                                >
+                                    <Icon icon=SIGNATURE />
+                                    {tr!("blame-tab")}
+                                </A>
+                                <A
+                                    href=format!("{article_link}/backlinks")
+                                    {..}
+                                    role="tab"
+                                    class="tab md:flex-auto"
+                                >
+                                    <Icon icon=LINK />
+                                    {tr!("backlinks-tab")}
+                                </A>
                                 <Show when=move || {
                                     is_logged_in()
-                                        && can_edit_article(&article_.article, is_admin()).is_ok()
+                                        && can_edit_article(&article_.article, can_moderate()).is_ok()
                                 }>
                                     <A
                                         href=format!("{article_link}/edit")
@@ -112,7 +134,7 @@ pub fn ArticleNav(
                                     </A>
                                 </Show>
                                 <Suspense>
-                                    <Show when=is_admin>
+                                    <Show when=can_moderate>
                                         <A
                                             href=format!("{article_link_}/actions")
                                             {..}
@@ -173,6 +195,8 @@ fn page_title(active_tab: ActiveTab, article_title: &str) -> String {
         ActiveTab::Read => return article_title.to_string(),
         ActiveTab::Discussion => tr!("discussion-tab"),
         ActiveTab::History => tr!("history-tab"),
+        ActiveTab::Blame => tr!("blame-tab"),
+        ActiveTab::Backlinks => tr!("backlinks-tab"),
         ActiveTab::Edit => tr!("edit-tab"),
         ActiveTab::Actions => tr!("actions-tab"),
     };
@@ -1,5 +1,6 @@
 use crate::{
     comment_editor::{CommentEditorView, EditParams},
+    report_button::ReportButton,
     utils::{
         formatting::{comment_path, time_ago, user_link},
         resources::my_profile,
@@ -127,6 +128,12 @@ pub fn CommentView(
                                 <Icon icon=TRASH />
                             </a>
                         </Show>
+                        <Show when=move || !is_creator && !comment.comment.deleted>
+                            <ReportButton
+                                article_id=comment.comment.article_id
+                                comment_id=comment.comment.id
+                            />
+                        </Show>
                     </div>
                 </Show>
             </div>
@@ -2,13 +2,19 @@ use crate::{
     Pending,
     utils::formatting::{article_link, edit_path, edit_time, user_link},
 };
-use ibis_database::common::article::EditView;
+use ibis_database::common::article::{EditVersion, EditView};
 use leptos::{either::Either, prelude::*};
 
 // If `for_article` is true, edit entries link to the respective user account. Otherwise
 // if edits for a user is rendered, entries link to the respective article.
+//
+// With `compare`, each entry has a checkbox to select up to two versions for comparison.
 #[component]
-pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
+pub fn EditList(
+    edits: Vec<EditView>,
+    for_article: bool,
+    #[prop(optional)] compare: Option<RwSignal<Vec<EditVersion>>>,
+) -> impl IntoView {
     view! {
         <div>
             <ul class="list-disc">
@@ -35,10 +41,36 @@ pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
                                 },
                             )
                         };
+                        let checkbox = compare
+                            .map(|compare| {
+                                let hash = edit.edit.hash.clone();
+                                let hash_ = hash.clone();
+                                view! {
+                                    <input
+                                        type="checkbox"
+                                        class="mr-2 checkbox checkbox-sm"
+                                        prop:checked=move || compare.read().contains(&hash)
+                                        on:change=move |_| {
+                                            compare
+                                                .update(|selected| {
+                                                    if let Some(i) = selected.iter().position(|h| h == &hash_) {
+                                                        selected.remove(i);
+                                                    } else {
+                                                        selected.push(hash_.clone());
+                                                        if selected.len() > 2 {
+                                                            selected.remove(0);
+                                                        }
+                                                    }
+                                                })
+                                        }
+                                    />
+                                }
+                            });
                         view! {
                             <li class="m-2 card card-compact bg-base-100 card-bordered rounded-s">
                                 <div class="card-body">
                                     <div class="flex w-full">
+                                        {checkbox}
                                         <a class="text-lg grow link link-primary" href=path>
                                             {edit.edit.summary}
                                         </a>
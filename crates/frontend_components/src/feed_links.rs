@@ -0,0 +1,24 @@
+use leptos::prelude::*;
+use leptos_meta::Link;
+use phosphor_leptos::{Icon, RSS};
+
+/// Links to the Atom and RSS feeds at `/feed/<format><path>`, which are also announced in the
+/// page head so that feed readers can discover them.
+#[component]
+pub fn FeedLinks(path: String) -> impl IntoView {
+    let atom = format!("/feed/atom{path}");
+    let rss = format!("/feed/rss{path}");
+    view! {
+        <Link rel="alternate" type_="application/atom+xml" href=atom.clone() />
+        <Link rel="alternate" type_="application/rss+xml" href=rss.clone() />
+        <div class="flex gap-2 items-center m-2 text-sm">
+            <Icon icon=RSS />
+            <a class="link" href=atom rel="external">
+                Atom
+            </a>
+            <a class="link" href=rss rel="external">
+                RSS
+            </a>
+        </div>
+    }
+}
@@ -6,10 +6,13 @@ pub mod article_nav;
 pub mod comment;
 pub mod comment_editor;
 pub mod edit_list;
+pub mod feed_links;
 pub mod instance_follow_button;
 pub mod nav;
 pub mod oauth_login_button;
+pub mod pagination;
 pub mod protected_route;
+pub mod report_button;
 pub mod suspense_error;
 pub mod utils;
 
@@ -2,7 +2,7 @@ use crate::utils::{
     dark_mode::DarkMode,
     formatting::instance_title,
     i18n::LanguageSelector,
-    resources::{config, is_admin, is_logged_in, my_profile, site},
+    resources::{can_moderate, config, is_admin, is_logged_in, my_profile, site},
 };
 use ibis_api_client::{CLIENT, errors::FrontendResultExt};
 use leptos::{IntoView, component, ev, prelude::*, view};
@@ -11,12 +11,16 @@ use leptos_router::hooks::use_navigate;
 use phosphor_leptos::{
     BELL_RINGING,
     CARDS,
+    CLOCK_COUNTER_CLOCKWISE,
     EXCLAMATION_MARK,
+    FLAG,
     GEAR,
     HOUSE,
     Icon,
+    LIST_CHECKS,
     MAGNIFYING_GLASS,
     PLUS,
+    PROHIBIT,
 };
 
 #[component]
@@ -72,6 +76,12 @@ pub fn Nav() -> impl IntoView {
                                     {move || tr!("explore")}
                                 </a>
                             </li>
+                            <li>
+                                <a href="/recent-changes">
+                                    <Icon icon=CLOCK_COUNTER_CLOCKWISE />
+                                    {move || tr!("recent-changes")}
+                                </a>
+                            </li>
                             <li>
                                 <a href="/about">
                                     <Icon icon=EXCLAMATION_MARK />
@@ -95,6 +105,26 @@ pub fn Nav() -> impl IntoView {
                                     </a>
                                 </li>
                             </Show>
+                            <Show when=can_moderate>
+                                <li>
+                                    <a href="/admin/review">
+                                        <Icon icon=LIST_CHECKS />
+                                        {move || tr!("review-queue")}
+                                    </a>
+                                </li>
+                                <li>
+                                    <a href="/admin/reports">
+                                        <Icon icon=FLAG />
+                                        {move || tr!("reports")}
+                                    </a>
+                                </li>
+                                <li>
+                                    <a href="/admin/bans">
+                                        <Icon icon=PROHIBIT />
+                                        {move || tr!("banned-users")}
+                                    </a>
+                                </li>
+                            </Show>
                             <Show when=is_admin>
                                 <li>
                                     <a href="/admin">
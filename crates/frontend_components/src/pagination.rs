@@ -0,0 +1,21 @@
+use ibis_database::common::pagination::PaginationCursor;
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use leptos_router::{hooks::use_query_map, location::Url};
+
+/// Cursor of the current page, which is passed as query parameter.
+pub fn cursor_param() -> Option<PaginationCursor> {
+    use_query_map().get().get("cursor").map(PaginationCursor)
+}
+
+/// Link to the following page of a paginated list, or nothing on the last page.
+#[component]
+pub fn NextPageLink(next_page: Option<PaginationCursor>) -> impl IntoView {
+    next_page.map(|next_page| {
+        view! {
+            <a class="link my-4 block" href=format!("?cursor={}", Url::escape(&next_page.0))>
+                {tr!("next-page")}
+            </a>
+        }
+    })
+}
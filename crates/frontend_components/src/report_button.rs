@@ -0,0 +1,69 @@
+use crate::utils::resources::is_logged_in;
+use ibis_api_client::{CLIENT, errors::FrontendResultExt, report::CreateReportParams};
+use ibis_database::common::newtypes::{ArticleId, CommentId, EditId};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use phosphor_leptos::{FLAG, Icon};
+
+/// Flag icon which opens an inline form to report the article, or one of its edits or comments,
+/// to the moderators.
+#[component]
+pub fn ReportButton(
+    article_id: ArticleId,
+    #[prop(optional)] edit_id: Option<EditId>,
+    #[prop(optional)] comment_id: Option<CommentId>,
+) -> impl IntoView {
+    let show_form = signal(false);
+    let reported = signal(false);
+    let reason = signal(String::new());
+    let report_action = Action::new(move |reason: &String| {
+        let params = CreateReportParams {
+            article_id,
+            edit_id,
+            comment_id,
+            reason: reason.clone(),
+        };
+        async move {
+            CLIENT.create_report(&params).await.error_popup(|_| {
+                show_form.1.set(false);
+                reported.1.set(true);
+            });
+        }
+    });
+
+    view! {
+        <Show when=is_logged_in>
+            <span class="inline-flex gap-2 items-center">
+                <Show
+                    when=move || !reported.0.get()
+                    fallback=move || view! { <span class="text-xs">{tr!("report-sent")}</span> }
+                >
+                    <a
+                        class="link"
+                        title=tr!("report")
+                        on:click=move |_| show_form.1.update(|s| *s = !*s)
+                    >
+                        <Icon icon=FLAG />
+                    </a>
+                </Show>
+                <Show when=move || show_form.0.get()>
+                    <input
+                        type="text"
+                        class="input input-xs input-bordered"
+                        placeholder=tr!("report-reason")
+                        bind:value=reason
+                    />
+                    <button
+                        class="btn btn-xs btn-warning"
+                        disabled=move || reason.0.get().trim().is_empty()
+                        on:click=move |_| {
+                            report_action.dispatch(reason.0.get());
+                        }
+                    >
+                        {tr!("report")}
+                    </button>
+                </Show>
+            </span>
+        </Show>
+    }
+}
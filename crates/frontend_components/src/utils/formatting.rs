@@ -1,8 +1,9 @@
 use chrono::{DateTime, Local, TimeDelta, Utc};
 use ibis_database::common::{
-    article::{Article, Edit},
+    article::{Article, Edit, LinkedArticle},
     comment::Comment,
     instance::{Instance, InstanceView},
+    modlog::ModlogArticle,
     user::Person,
     utils::extract_domain,
 };
@@ -10,18 +11,14 @@ use leptos::prelude::*;
 use leptos_fluent::tr;
 use std::sync::OnceLock;
 use timeago::Formatter;
+use url::form_urlencoded;
 
 pub fn article_path(article: &Article) -> String {
-    let title = article.title.replace(" ", "_");
-    if article.local {
-        format!("/article/{title}")
-    } else {
-        format!(
-            "/article/{}@{}",
-            title,
-            extract_domain(article.ap_id.inner())
-        )
-    }
+    title_path(
+        &article.title,
+        article.local,
+        &extract_domain(article.ap_id.inner()),
+    )
 }
 
 pub fn article_link(article: &Article) -> impl IntoView {
@@ -33,8 +30,47 @@ pub fn article_link(article: &Article) -> impl IntoView {
     }
 }
 
-pub fn user_link(person: &Person) -> impl IntoView {
-    let creator_path = if person.local {
+/// Link to an article referenced in the modlog, which only has the fields needed for the path.
+pub fn modlog_article_link(article: &ModlogArticle) -> impl IntoView {
+    let article_path = title_path(
+        &article.title,
+        article.local,
+        &extract_domain(article.ap_id.inner()),
+    );
+    view! {
+        <a class="link" href=article_path>
+            {article.title.clone()}
+        </a>
+    }
+}
+
+/// Link to an article in a list of backlinks or orphaned articles.
+pub fn linked_article_link(article: &LinkedArticle) -> impl IntoView {
+    let article_path = title_path(&article.title, article.local, &article.domain);
+    view! {
+        <a class="link" href=article_path>
+            {article.title.clone()}
+        </a>
+    }
+}
+
+fn title_path(title: &str, local: bool, domain: &str) -> String {
+    let title = encode_title(title);
+    if local {
+        format!("/article/{title}")
+    } else {
+        format!("/article/{title}@{domain}")
+    }
+}
+
+/// Title for use in urls, with underscores instead of spaces and other special characters
+/// percent-encoded.
+fn encode_title(title: &str) -> String {
+    form_urlencoded::byte_serialize(title.replace(' ', "_").as_bytes()).collect()
+}
+
+pub fn person_path(person: &Person) -> String {
+    if person.local {
         format!("/user/{}", person.username)
     } else {
         format!(
@@ -42,7 +78,11 @@ pub fn user_link(person: &Person) -> impl IntoView {
             person.username,
             extract_domain(person.ap_id.inner())
         )
-    };
+    }
+}
+
+pub fn user_link(person: &Person) -> impl IntoView {
+    let creator_path = person_path(person);
     view! {
         <a class="link" href=creator_path>
             {person.title()}
@@ -108,7 +148,7 @@ pub fn comment_path(comment: &Comment, article: &Article) -> String {
 pub fn edit_path(edit: &Edit, article: &Article) -> String {
     format!(
         "/article/{}@{}/diff/{}",
-        article.title,
+        encode_title(&article.title),
         extract_domain(article.ap_id.inner()),
         edit.hash.0,
     )
@@ -35,5 +35,13 @@ pub fn is_logged_in() -> bool {
 }
 
 pub fn is_admin() -> bool {
-    my_profile().map(|p| p.local_user.admin).unwrap_or(false)
+    my_profile()
+        .map(|p| p.local_user.role.is_admin())
+        .unwrap_or(false)
+}
+
+pub fn can_moderate() -> bool {
+    my_profile()
+        .map(|p| p.local_user.role.can_moderate())
+        .unwrap_or(false)
 }
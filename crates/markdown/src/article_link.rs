@@ -6,10 +6,17 @@ use markdown_it::{
 };
 
 #[derive(Debug)]
-struct ArticleLink {
+pub(crate) struct ArticleLink {
     label: String,
-    title: String,
-    domain: String,
+    pub(crate) title: String,
+    pub(crate) domain: String,
+}
+
+/// Article which is linked with `[[Title@example.com]]`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArticleLinkTarget {
+    pub title: String,
+    pub domain: String,
 }
 
 // This defines how your custom node should be rendered.
@@ -58,7 +65,8 @@ impl InlineRule for ArticleLinkScanner {
 
 #[cfg(test)]
 mod test {
-    use crate::render_article_markdown;
+    use super::ArticleLinkTarget;
+    use crate::{extract_article_links, render_article_markdown};
 
     #[test]
     fn test_markdown_article_link() {
@@ -74,4 +82,24 @@ mod test {
             with_label
         );
     }
+
+    #[test]
+    fn test_extract_article_links() {
+        let links = extract_article_links(
+            "[[Title@example.com]] and [[Other_Title@example.com|label]]\n\n[[Title@example.com]]",
+        );
+        assert_eq!(
+            vec![
+                ArticleLinkTarget {
+                    title: "Other Title".to_string(),
+                    domain: "example.com".to_string(),
+                },
+                ArticleLinkTarget {
+                    title: "Title".to_string(),
+                    domain: "example.com".to_string(),
+                },
+            ],
+            links
+        );
+    }
 }
@@ -1,21 +1,25 @@
-use article_link::ArticleLinkScanner;
+use article_link::{ArticleLink, ArticleLinkScanner};
 use fmtm_ytmimi_markdown_fmt::{Config, OrderedListMarker, UnorderedListMarker};
 use markdown_it::{
     MarkdownIt,
     plugins::cmark::block::{heading::ATXHeading, lheading::SetextHeader},
 };
 use math_equation::MathEquationScanner;
+use media::replace_external_image;
 use std::sync::OnceLock;
 use table_of_contents::{TocMarkerScanner, TocScanner};
 
 mod article_link;
 mod external_link;
 mod math_equation;
+mod media;
 mod table_of_contents;
 
+pub use article_link::ArticleLinkTarget;
+pub use media::extract_media_filenames;
+
 pub fn render_article_markdown(text: &str) -> String {
-    static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
-    let mut parsed = INSTANCE.get_or_init(article_markdown).parse(text);
+    let mut parsed = article_parser().parse(text);
 
     // Make markdown headings one level smaller, so that h1 becomes h2 etc, and markdown titles
     // are smaller than page title.
@@ -26,10 +30,32 @@ pub fn render_article_markdown(text: &str) -> String {
         if let Some(heading) = node.cast_mut::<SetextHeader>() {
             heading.level += 1;
         }
+        replace_external_image(node);
     });
     parsed.render()
 }
 
+/// Returns all articles which are linked from the text, without duplicates.
+pub fn extract_article_links(text: &str) -> Vec<ArticleLinkTarget> {
+    let mut links = vec![];
+    article_parser().parse(text).walk(|node, _| {
+        if let Some(link) = node.cast::<ArticleLink>() {
+            links.push(ArticleLinkTarget {
+                title: link.title.replace('_', " "),
+                domain: link.domain.clone(),
+            });
+        }
+    });
+    links.sort();
+    links.dedup();
+    links
+}
+
+fn article_parser() -> &'static MarkdownIt {
+    static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
+    INSTANCE.get_or_init(article_markdown)
+}
+
 pub fn render_comment_markdown(text: &str) -> String {
     static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
     INSTANCE.get_or_init(common_markdown).parse(text).render()
@@ -61,7 +87,7 @@ fn common_markdown() -> MarkdownIt {
     let p = &mut parser;
     {
         // Markdown-it inline core features. Image is disabled to prevent embedding external
-        // images. Later we need to add proper image support using pictrs.
+        // images, it is only enabled for articles which can embed uploaded media.
         // External link uses a custom impl further down with icon.
         use markdown_it::plugins::cmark::inline::*;
         newline::add(p);
@@ -0,0 +1,70 @@
+use crate::{article_parser, external_link::ExternalLink};
+use markdown_it::{Node, parser::inline::Text, plugins::cmark::inline::image::Image};
+
+/// Path prefix of media which was uploaded to an Ibis instance.
+const MEDIA_PREFIX: &str = "/media/";
+
+/// Only uploaded media is embedded, so that readers don't load anything from external servers.
+/// Other images are rendered as links instead.
+pub(crate) fn replace_external_image(node: &mut Node) {
+    let Some(image) = node.cast::<Image>() else {
+        return;
+    };
+    if image.url.starts_with(MEDIA_PREFIX) {
+        return;
+    }
+    let link = ExternalLink {
+        url: image.url.clone(),
+        title: image.title.clone(),
+    };
+    if node.children.is_empty() {
+        node.children.push(Node::new(Text {
+            content: link.url.clone(),
+        }));
+    }
+    node.replace(link);
+}
+
+/// Returns filenames of all uploaded media which is embedded in the text, without duplicates.
+pub fn extract_media_filenames(text: &str) -> Vec<String> {
+    let mut filenames = vec![];
+    article_parser().parse(text).walk(|node, _| {
+        if let Some(image) = node.cast::<Image>() {
+            let filename = image.url.strip_prefix(MEDIA_PREFIX);
+            if let Some(filename) = filename.filter(|f| !f.is_empty() && !f.contains('/')) {
+                filenames.push(filename.to_string());
+            }
+        }
+    });
+    filenames.sort();
+    filenames.dedup();
+    filenames
+}
+
+#[cfg(test)]
+mod test {
+    use super::extract_media_filenames;
+    use crate::render_article_markdown;
+
+    #[test]
+    fn test_render_external_image() {
+        let html = render_article_markdown("![cat](/media/abc.png)");
+        assert!(html.contains(r#"<img src="/media/abc.png" alt="cat">"#));
+
+        let html = render_article_markdown("![cat](https://tracker.example/x.png)");
+        assert!(!html.contains("<img"));
+        assert!(html.contains(r#"<a href="https://tracker.example/x.png">cat"#));
+
+        let html = render_article_markdown("![](//tracker.example/x.png)");
+        assert!(!html.contains("<img"));
+        assert!(html.contains(r#"<a href="//tracker.example/x.png">//tracker.example/x.png"#));
+    }
+
+    #[test]
+    fn test_extract_media_filenames() {
+        let filenames = extract_media_filenames(
+            "![cat](/media/abc.png) ![](https://example.com/media/def.png)\n\n![cat again](/media/abc.png)\n\n![thumb](/media/thumbnail/abc.png)",
+        );
+        assert_eq!(vec!["abc.png".to_string()], filenames);
+    }
+}
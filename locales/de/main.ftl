@@ -3,4 +3,153 @@ explore = Erkunden
 about = Über
 create-article = Artikel Erstellen
 admin-settings = Admin Einstellungen
-notifications = Benachrichtigungen
\ No newline at end of file
+notifications = Benachrichtigungen
+revert-to-version = Auf diese Version zurücksetzen
+revert-to-version-title = Den Artikeltext wiederherstellen, wie er nach dieser Bearbeitung war
+move-article = Artikel verschieben
+move-article-description = Den Titel dieses Artikels ändern. Links auf den alten Titel werden auf den neuen weitergeleitet.
+backlinks-tab = Links auf diese Seite
+no-backlinks = Keine Artikel verlinken auf diese Seite
+wanted-articles = Gewünschte Artikel
+wanted-article-links = ({ $count } Links)
+orphaned-articles = Verwaiste Artikel
+next-page = Weiter
+search-federated = Auch auf anderen Instanzen suchen
+search-local = Nur auf dieser Instanz suchen
+search-timed-out = Keine Antwort von { $instances }
+upload-image = Bild hochladen
+uploaded-media = Hochgeladene Medien
+blame-tab = Autoren
+compare-selected = Ausgewählte Versionen vergleichen
+compare-lines = Zeilen
+compare-words = Wörter
+compare-rendered = Dargestellt
+review-queue = Prüfwarteschlange
+review-queue-empty = Keine Bearbeitungen warten auf Prüfung.
+approve = Annehmen
+reject = Ablehnen
+edit-review = Bearbeitungen prüfen
+review-all-edits = Bearbeitungen aller Artikel prüfen
+review-new-user-edits = Bearbeitungen von Benutzern prüfen mit weniger angenommenen Bearbeitungen als
+toggle-review = Prüfung umschalten
+toggle-review-title = Alle Bearbeitungen dieses Artikels müssen von einem Moderator angenommen werden
+role = Rolle
+role-user = Benutzer
+role-trusted-editor = Vertrauenswürdiger Autor
+role-moderator = Moderator
+role-admin = Admin
+banned = Gesperrt
+until = bis
+ban = Sperren
+unban = Entsperren
+ban-user = Benutzer sperren
+ban-reason = Grund
+ban-days = Tage bis zum Ablauf, leer für dauerhafte Sperre
+ban-remove-content-days = Inhalte der letzten Tage entfernen
+banned-users = Gesperrte Benutzer
+no-banned-users = Derzeit sind keine Benutzer gesperrt.
+banned-by = gesperrt von
+permanent = dauerhaft
+moderation-reason = Grund für die Moderationsaktion (optional)
+modlog = Moderationsprotokoll
+modlog-empty = Keine Moderationsaktionen gefunden.
+modlog-all-actions = Alle Aktionen
+modlog-clear-filters = Filter zurücksetzen
+modlog-filter = filtern
+modlog-protect-article = Artikel geschützt
+modlog-unprotect-article = Artikelschutz aufgehoben
+modlog-remove-article = Artikel entfernt
+modlog-restore-article = Artikel wiederhergestellt
+modlog-revert-article = Artikel zurückgesetzt
+modlog-enable-review = Prüfung aktiviert
+modlog-disable-review = Prüfung deaktiviert
+modlog-approve-edit = Bearbeitung angenommen
+modlog-reject-edit = Bearbeitung abgelehnt
+modlog-set-role = Benutzerrolle geändert
+modlog-ban-user = Benutzer gesperrt
+modlog-unban-user = Benutzer entsperrt
+modlog-update-instance = Instanzeinstellungen geändert
+report = Melden
+report-reason = Grund der Meldung
+report-sent = Gemeldet
+report-article = Diesen Artikel den Moderatoren melden
+reports = Meldungen
+no-open-reports = Keine offenen Meldungen
+reported-by = gemeldet von
+reported-article = Artikel
+reported-edit = Bearbeitung
+reported-comment = Kommentar
+resolve = Erledigen
+dismiss = Verwerfen
+notification-new-report = Neue Meldung: { $text }
+modlog-resolve-report = Meldung erledigt
+modlog-dismiss-report = Meldung verworfen
+federation = Föderation
+federation-description = Instanzen, die für die Föderation erlaubt oder blockiert sind. Wenn eine Instanz erlaubt ist, wird die Föderation mit allen anderen Instanzen blockiert.
+federation-list-empty = Keine Instanzen sind erlaubt oder blockiert
+federation-mode = Modus
+federation-allow = Erlauben
+federation-block = Blockieren
+federation-days = Dauer in Tagen (leer für dauerhaft)
+blocked-content-keep = Vorhandene Inhalte behalten
+blocked-content-hide = Artikel und Kommentare verbergen
+blocked-content-purge = Artikel, Kommentare und Benutzer löschen
+domain = Domain
+reason = Grund
+expires = Läuft ab
+remove = Entfernen
+modlog-allow-domain = Föderation mit Instanz erlaubt
+modlog-block-domain = Instanz blockiert
+modlog-remove-federation-list-entry = Instanz von der Föderationsliste entfernt
+delivery-status = Zustellungsstatus
+delivery-status-empty = Es wurden noch keine Aktivitäten an andere Instanzen gesendet
+delivery-queue-depth = In Warteschlange
+delivery-fail-count = Fehlschläge
+delivery-last-success = Letzter Erfolg
+delivery-last-error = Letzter Fehler
+delivery-dead = Nicht erreichbar
+never = nie
+recent-changes = Letzte Änderungen
+recent-changes-all-instances = Alle Instanzen
+recent-changes-local-only = Nur lokale Artikel
+recent-changes-hide-bots = Bots ausblenden
+recent-changes-unreviewed-only = Nur ungeprüfte Bearbeitungen
+recent-changes-from = Von
+recent-changes-to = Bis
+recent-changes-empty = Keine Bearbeitungen gefunden
+diff = Unterschied
+api-tokens = API-Tokens
+api-tokens-description = Mit Tokens können Bots und Skripte die API in deinem Namen nutzen. Sende sie im Authorization-Header als Bearer-Token. Jedes Token kann lesen, andere Berechtigungen müssen aktiviert werden.
+api-tokens-empty = Noch keine API-Tokens.
+api-token-name = Name
+api-token-scopes = Berechtigungen
+api-token-last-used = Zuletzt verwendet
+api-token-never-used = Nie
+api-token-bot-edits = Bearbeitungen als Bot-Bearbeitungen markieren
+api-token-create = Token erstellen
+api-token-created = Kopiere das neue Token jetzt, es wird nicht noch einmal angezeigt:
+api-token-revoke = Widerrufen
+api-token-scope-read = Lesen
+api-token-scope-edit-articles = Artikel bearbeiten
+api-token-scope-comment = Kommentieren
+sessions = Sitzungen
+session-device = Gerät
+session-ip = IP-Adresse
+session-last-seen = Zuletzt gesehen
+session-current = Aktuelle Sitzung
+session-logout = Abmelden
+security = Sicherheit
+require-totp-for-moderators = Zwei-Faktor-Authentifizierung für Moderatoren und Admins verlangen
+totp = Zwei-Faktor-Authentifizierung
+totp-description = Die Anmeldung erfordert zusätzlich einen Einmalcode aus einer Authenticator-App.
+totp-required-warning = Moderatoren dieser Instanz benötigen Zwei-Faktor-Authentifizierung. Aktiviere sie, um deine Moderationsrechte zu nutzen.
+totp-setup = Einrichten
+totp-scan = Scanne den QR-Code mit deiner Authenticator-App und gib dann den angezeigten Code ein.
+totp-open-app = In Authenticator-App öffnen
+totp-secret = Geheimnis zur manuellen Eingabe:
+totp-code = Code
+totp-enable = Aktivieren
+totp-disable = Deaktivieren
+totp-enabled = Zwei-Faktor-Authentifizierung ist aktiviert.
+totp-recovery-codes = Bewahre diese Wiederherstellungscodes an einem sicheren Ort auf. Jeder davon kann einmal verwendet werden, um sich ohne Authenticator-App anzumelden. Sie werden nicht noch einmal angezeigt.
+totp-login-description = Gib den Code aus deiner Authenticator-App oder einen Wiederherstellungscode ein.
@@ -9,6 +9,10 @@ notification-inactive = Notifications inactive
 read-tab = Read
 discussion-tab = Discussion
 history-tab = History
+compare-selected = Compare selected versions
+compare-lines = Lines
+compare-words = Words
+compare-rendered = Rendered
 edit-tab = Edit
 actions-tab = Actions
 article-removed = Article was removed
@@ -73,4 +77,149 @@ notification-new-comment = New comment: { $text }
 notification-new-article = New article: { $text }
 notification-edit-conflict = New article: { $text }
 mark-as-read = Mark as read
-no-unread-notifications = No unread notifications
\ No newline at end of file
+no-unread-notifications = No unread notifications
+revert-to-version = Revert to this version
+revert-to-version-title = Restore the article text as it was after this edit
+move-article = Move Article
+move-article-description = Change the title of this article. Links to the old title are redirected to the new one.
+blame-tab = Blame
+backlinks-tab = What links here
+no-backlinks = No articles link to this page
+wanted-articles = Wanted Articles
+wanted-article-links = ({ $count } links)
+orphaned-articles = Orphaned Articles
+next-page = Next
+search-federated = Also search on other instances
+search-local = Only search on this instance
+search-timed-out = No response from { $instances }
+upload-image = Upload image
+uploaded-media = Uploaded Media
+review-queue = Review Queue
+review-queue-empty = No edits are waiting for review.
+approve = Approve
+reject = Reject
+edit-review = Edit Review
+review-all-edits = Review edits to all articles
+review-new-user-edits = Review edits by users with fewer approved edits than
+toggle-review = Toggle Edit Review
+toggle-review-title = Require approval by a moderator for all edits to this article
+role = Role
+role-user = User
+role-trusted-editor = Trusted Editor
+role-moderator = Moderator
+role-admin = Admin
+banned = Banned
+until = until
+ban = Ban
+unban = Unban
+ban-user = Ban user
+ban-reason = Reason
+ban-days = Days until expiry, empty for permanent ban
+ban-remove-content-days = Remove content from last days
+banned-users = Banned Users
+no-banned-users = No users are currently banned.
+banned-by = banned by
+permanent = permanent
+moderation-reason = Reason for moderation action (optional)
+modlog = Moderation Log
+modlog-empty = No moderation actions found.
+modlog-all-actions = All actions
+modlog-clear-filters = Clear filters
+modlog-filter = filter
+modlog-protect-article = Protected article
+modlog-unprotect-article = Unprotected article
+modlog-remove-article = Removed article
+modlog-restore-article = Restored article
+modlog-revert-article = Reverted article
+modlog-enable-review = Enabled edit review
+modlog-disable-review = Disabled edit review
+modlog-approve-edit = Approved edit
+modlog-reject-edit = Rejected edit
+modlog-set-role = Changed user role
+modlog-ban-user = Banned user
+modlog-unban-user = Unbanned user
+modlog-update-instance = Updated instance settings
+report = Report
+report-reason = Reason for report
+report-sent = Reported
+report-article = Report this article to the moderators
+reports = Reports
+no-open-reports = No open reports
+reported-by = reported by
+reported-article = Article
+reported-edit = Edit
+reported-comment = Comment
+resolve = Resolve
+dismiss = Dismiss
+notification-new-report = New report: { $text }
+modlog-resolve-report = Resolved report
+modlog-dismiss-report = Dismissed report
+federation = Federation
+federation-description = Instances which are allowed or blocked for federation. If any instance is allowed, federation with all other instances is blocked.
+federation-list-empty = No instances are allowed or blocked
+federation-mode = Mode
+federation-allow = Allow
+federation-block = Block
+federation-days = Duration in days (empty for permanent)
+blocked-content-keep = Keep existing content
+blocked-content-hide = Hide articles and comments
+blocked-content-purge = Delete articles, comments and users
+domain = Domain
+reason = Reason
+expires = Expires
+remove = Remove
+delivery-status = Delivery status
+delivery-status-empty = No activities were sent to other instances yet
+delivery-queue-depth = Queued
+delivery-fail-count = Failures
+delivery-last-success = Last success
+delivery-last-error = Last error
+delivery-dead = Unreachable
+never = never
+recent-changes = Recent changes
+recent-changes-all-instances = All instances
+recent-changes-local-only = Only local articles
+recent-changes-hide-bots = Hide bots
+recent-changes-unreviewed-only = Only unreviewed edits
+recent-changes-from = From
+recent-changes-to = To
+recent-changes-empty = No edits found
+diff = diff
+api-tokens = API tokens
+api-tokens-description = Tokens let bots and scripts use the API on your behalf. Send them in the Authorization header as Bearer token. Every token can read, other permissions need to be enabled.
+api-tokens-empty = No API tokens yet.
+api-token-name = Name
+api-token-scopes = Permissions
+api-token-last-used = Last used
+api-token-never-used = Never
+api-token-bot-edits = Mark edits as bot edits
+api-token-create = Create token
+api-token-created = Copy the new token now, it will not be shown again:
+api-token-revoke = Revoke
+api-token-scope-read = Read
+api-token-scope-edit-articles = Edit articles
+api-token-scope-comment = Comment
+sessions = Sessions
+session-device = Device
+session-ip = IP address
+session-last-seen = Last seen
+session-current = Current session
+session-logout = Logout
+security = Security
+require-totp-for-moderators = Require two-factor authentication for moderators and admins
+totp = Two-factor authentication
+totp-description = Login additionally requires a one-time code from an authenticator app.
+totp-required-warning = Moderators of this instance need two-factor authentication. Enable it to use your moderator permissions.
+totp-setup = Set up
+totp-scan = Scan the QR code with your authenticator app, then enter the code which it shows.
+totp-open-app = Open in authenticator app
+totp-secret = Secret for manual entry:
+totp-code = Code
+totp-enable = Enable
+totp-disable = Disable
+totp-enabled = Two-factor authentication is enabled.
+totp-recovery-codes = Store these recovery codes in a safe place. Each of them can be used once to login without the authenticator app. They will not be shown again.
+totp-login-description = Enter the code from your authenticator app, or a recovery code.
+modlog-allow-domain = Allowed federation with instance
+modlog-block-domain = Blocked instance
+modlog-remove-federation-list-entry = Removed instance from federation list
\ No newline at end of file
@@ -4,3 +4,152 @@ about = 关于
 create-article = 创建文章
 admin-settings = 管理员设置
 notifications = 通知
+revert-to-version = 恢复到此版本
+revert-to-version-title = 将文章内容恢复为此次编辑后的状态
+move-article = 移动文章
+move-article-description = 更改此文章的标题。指向旧标题的链接将重定向到新标题。
+backlinks-tab = 链入页面
+no-backlinks = 没有文章链接到此页面
+wanted-articles = 待撰文章
+wanted-article-links = （{ $count } 个链接）
+orphaned-articles = 孤立文章
+next-page = 下一页
+search-federated = 同时在其他实例上搜索
+search-local = 仅在此实例上搜索
+search-timed-out = { $instances } 没有响应
+upload-image = 上传图片
+uploaded-media = 已上传的媒体
+blame-tab = 逐行作者
+compare-selected = 比较所选版本
+compare-lines = 行
+compare-words = 词
+compare-rendered = 渲染
+review-queue = 审核队列
+review-queue-empty = 没有等待审核的编辑。
+approve = 批准
+reject = 拒绝
+edit-review = 编辑审核
+review-all-edits = 审核所有文章的编辑
+review-new-user-edits = 审核已批准编辑数少于此数的用户的编辑
+toggle-review = 切换编辑审核
+toggle-review-title = 此文章的所有编辑都需要由版主批准
+role = 角色
+role-user = 用户
+role-trusted-editor = 受信任的编辑者
+role-moderator = 版主
+role-admin = 管理员
+banned = 已封禁
+until = 直到
+ban = 封禁
+unban = 解除封禁
+ban-user = 封禁用户
+ban-reason = 原因
+ban-days = 到期天数，留空表示永久封禁
+ban-remove-content-days = 移除最近几天的内容
+banned-users = 已封禁用户
+no-banned-users = 当前没有被封禁的用户。
+banned-by = 封禁者
+permanent = 永久
+moderation-reason = 管理操作的原因（可选）
+modlog = 管理日志
+modlog-empty = 未找到管理操作。
+modlog-all-actions = 所有操作
+modlog-clear-filters = 清除筛选
+modlog-filter = 筛选
+modlog-protect-article = 保护了文章
+modlog-unprotect-article = 取消了文章保护
+modlog-remove-article = 移除了文章
+modlog-restore-article = 恢复了文章
+modlog-revert-article = 回退了文章
+modlog-enable-review = 启用了编辑审核
+modlog-disable-review = 停用了编辑审核
+modlog-approve-edit = 批准了编辑
+modlog-reject-edit = 拒绝了编辑
+modlog-set-role = 更改了用户角色
+modlog-ban-user = 封禁了用户
+modlog-unban-user = 解除了用户封禁
+modlog-update-instance = 更新了实例设置
+report = 举报
+report-reason = 举报原因
+report-sent = 已举报
+report-article = 向版主举报此文章
+reports = 举报
+no-open-reports = 没有未处理的举报
+reported-by = 举报者
+reported-article = 文章
+reported-edit = 编辑
+reported-comment = 评论
+resolve = 处理
+dismiss = 驳回
+notification-new-report = 新举报：{ $text }
+modlog-resolve-report = 处理了举报
+modlog-dismiss-report = 驳回了举报
+federation = 联邦
+federation-description = 允许或屏蔽联邦的实例。如果允许了任何实例，则会屏蔽与所有其他实例的联邦。
+federation-list-empty = 没有允许或屏蔽的实例
+federation-mode = 模式
+federation-allow = 允许
+federation-block = 屏蔽
+federation-days = 持续天数（留空表示永久）
+blocked-content-keep = 保留现有内容
+blocked-content-hide = 隐藏文章和评论
+blocked-content-purge = 删除文章、评论和用户
+domain = 域名
+reason = 原因
+expires = 到期
+remove = 移除
+modlog-allow-domain = 允许了与实例的联邦
+modlog-block-domain = 屏蔽了实例
+modlog-remove-federation-list-entry = 从联邦列表中移除了实例
+delivery-status = 投递状态
+delivery-status-empty = 尚未向其他实例发送任何活动
+delivery-queue-depth = 排队中
+delivery-fail-count = 失败次数
+delivery-last-success = 上次成功
+delivery-last-error = 上次错误
+delivery-dead = 无法访问
+never = 从不
+recent-changes = 最近更改
+recent-changes-all-instances = 所有实例
+recent-changes-local-only = 仅本地文章
+recent-changes-hide-bots = 隐藏机器人
+recent-changes-unreviewed-only = 仅未审核的编辑
+recent-changes-from = 从
+recent-changes-to = 到
+recent-changes-empty = 未找到编辑
+diff = 差异
+api-tokens = API 令牌
+api-tokens-description = 令牌允许机器人和脚本代表你使用 API。请在 Authorization 请求头中以 Bearer 令牌发送。每个令牌都可以读取，其他权限需要单独启用。
+api-tokens-empty = 还没有 API 令牌。
+api-token-name = 名称
+api-token-scopes = 权限
+api-token-last-used = 上次使用
+api-token-never-used = 从未使用
+api-token-bot-edits = 将编辑标记为机器人编辑
+api-token-create = 创建令牌
+api-token-created = 请立即复制新令牌，它不会再次显示：
+api-token-revoke = 撤销
+api-token-scope-read = 读取
+api-token-scope-edit-articles = 编辑文章
+api-token-scope-comment = 评论
+sessions = 会话
+session-device = 设备
+session-ip = IP 地址
+session-last-seen = 最后活动
+session-current = 当前会话
+session-logout = 退出登录
+security = 安全
+require-totp-for-moderators = 要求版主和管理员使用双重认证
+totp = 双重认证
+totp-description = 登录时还需要输入身份验证器应用生成的一次性代码。
+totp-required-warning = 此实例的版主需要启用双重认证。启用后才能使用你的版主权限。
+totp-setup = 设置
+totp-scan = 使用身份验证器应用扫描二维码，然后输入显示的代码。
+totp-open-app = 在身份验证器应用中打开
+totp-secret = 手动输入的密钥：
+totp-code = 代码
+totp-enable = 启用
+totp-disable = 停用
+totp-enabled = 双重认证已启用。
+totp-recovery-codes = 请将这些恢复代码保存在安全的地方。每个代码都可以在没有身份验证器应用的情况下登录一次。它们不会再次显示。
+totp-login-description = 输入身份验证器应用中的代码，或一个恢复代码。